@@ -0,0 +1,266 @@
+//! Generating the code which records the values of the sub-expressions while evaluating the assertion.
+use crate::parse::{BinKind, BinOp, Expr, ExprKind};
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// How an expression's value is used by its parent, which decides whether it can be recorded.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Use {
+    /// Taken by value, so the expression can be moved into a temporary to record it.
+    Value,
+    /// Used as a place (like the receiver of a method call) or passed as an argument.
+    /// Only expressions creating a new value are recorded,
+    /// since moving a place could change the meaning of the code (or make it invalid).
+    Place,
+    /// Recorded by the parent itself, like the operands of a comparison.
+    Parent,
+}
+
+pub(crate) fn expand(krate: &TokenStream, expr: &Expr, args: &[TokenTree]) -> TokenStream {
+    let expander = Expander { krate };
+    template(
+        "{
+            #[allow(unused_imports)]
+            use $0::internal::{PowerDebug as _, PowerNoDebug as _};
+            #[allow(unused_mut)]
+            let mut __bassert_values = $0::internal::PowerValues::new();
+            #[allow(unused_parens)]
+            let __bassert_ok: bool = $1;
+            if !__bassert_ok {
+                $0::internal::bassert_panic($0::internal::bassert_power_failed(
+                    $2,
+                    __bassert_values,
+                    $0::bassert_internal!(@args $3),
+                ))
+            }
+        }",
+        &[
+            krate.clone(),
+            expander.expr(expr, Use::Parent),
+            source(&expr.tokens),
+            stream(args),
+        ],
+    )
+}
+
+/// Passes expressions which are not understood to `bassert!`.
+pub(crate) fn fallback(krate: &TokenStream, tokens: &[TokenTree]) -> TokenStream {
+    template("$0::bassert!($1)", &[krate.clone(), stream(tokens)])
+}
+
+struct Expander<'a> {
+    krate: &'a TokenStream,
+}
+
+impl Expander<'_> {
+    fn expr(&self, expr: &Expr, use_: Use) -> TokenStream {
+        let code = match &expr.kind {
+            ExprKind::Literal | ExprKind::Opaque => return stream(&expr.tokens),
+            ExprKind::Paren(group, inner) => return regroup(group, self.expr(inner, use_)),
+            ExprKind::Path => stream(&expr.tokens),
+            ExprKind::Unary(op, inner) => {
+                let inner_use = match op.first() {
+                    Some(TokenTree::Punct(p)) if matches!(p.as_char(), '!' | '-') => Use::Value,
+                    _ => Use::Place,
+                };
+                concat([stream(op), self.expr(inner, inner_use)])
+            }
+            ExprKind::Binary(lhs, op, rhs) if op.kind == BinKind::Comparison => {
+                self.comparison(lhs, op, rhs)
+            }
+            ExprKind::Binary(lhs, op, rhs) => concat([
+                self.expr(lhs, Use::Value),
+                stream(&op.tokens),
+                self.expr(rhs, Use::Value),
+            ]),
+            ExprKind::Cast(inner, ty) => concat([self.expr(inner, Use::Value), stream(ty)]),
+            ExprKind::Try(inner, question) => concat([
+                self.expr(inner, Use::Value),
+                TokenStream::from(question.clone()),
+            ]),
+            ExprKind::Field(base, name) => concat([self.expr(base, Use::Place), stream(name)]),
+            ExprKind::Index(base, group, index) => concat([
+                self.expr(base, Use::Place),
+                regroup(group, self.expr(index, Use::Value)),
+            ]),
+            ExprKind::Call(func, group, args) => {
+                concat([self.expr(func, Use::Parent), self.args(group, args)])
+            }
+            ExprKind::MethodCall(receiver, name, group, args) => concat([
+                self.expr(receiver, Use::Place),
+                stream(name),
+                self.args(group, args),
+            ]),
+        };
+
+        let recorded = match use_ {
+            Use::Value => true,
+            Use::Place => !expr.is_place() && !expr.is_borrow(),
+            Use::Parent => false,
+        };
+        if recorded {
+            template(
+                "(match ($0) { __bassert_value => { $1 __bassert_value } })",
+                &[code, self.record(expr, "&__bassert_value")],
+            )
+        } else {
+            code
+        }
+    }
+
+    /// Comparisons take their operands by reference, so both can always be recorded.
+    fn comparison(&self, lhs: &Expr, op: &BinOp, rhs: &Expr) -> TokenStream {
+        template(
+            "match (&($0), &($1)) {
+                (__bassert_lhs, __bassert_rhs) => {
+                    $2
+                    $3
+                    __bassert_lhs $4 __bassert_rhs
+                }
+            }",
+            &[
+                self.expr(lhs, Use::Parent),
+                self.expr(rhs, Use::Parent),
+                self.record_operand(lhs, "__bassert_lhs"),
+                self.record_operand(rhs, "__bassert_rhs"),
+                stream(&op.tokens),
+            ],
+        )
+    }
+
+    fn record_operand(&self, operand: &Expr, reference: &str) -> TokenStream {
+        match operand.kind {
+            ExprKind::Literal => TokenStream::new(),
+            _ => self.record(operand, reference),
+        }
+    }
+
+    /// A statement recording the value behind `reference` as the value of `expr`.
+    fn record(&self, expr: &Expr, reference: &str) -> TokenStream {
+        let reference = template(reference, &[]);
+        template(
+            "(&&$1::internal::PowerValue($2))
+                .bassert_power_record(&mut __bassert_values, $0);",
+            &[source(&expr.tokens), self.krate.clone(), reference],
+        )
+    }
+
+    fn args(&self, group: &Group, args: &Option<Vec<Expr>>) -> TokenStream {
+        let Some(args) = args else {
+            return TokenStream::from(TokenTree::Group(group.clone()));
+        };
+        let mut code = TokenStream::new();
+        for (idx, arg) in args.iter().enumerate() {
+            if idx > 0 {
+                code.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+            }
+            code.extend(self.expr(arg, Use::Place));
+        }
+        regroup(group, code)
+    }
+}
+
+fn stream(tokens: &[TokenTree]) -> TokenStream {
+    tokens.iter().cloned().collect()
+}
+
+/// A string literal containing `tokens` as they were written (as far as whitespace between tokens is concerned).
+///
+/// `stringify!` is not used, because it inserts spaces (like in `v [0]`) into tokens which passed through a procedural macro.
+fn source(tokens: &[TokenTree]) -> TokenStream {
+    let mut source = String::new();
+    write_source(&mut source, tokens.iter().cloned(), None);
+    TokenStream::from(TokenTree::Literal(Literal::string(&source)))
+}
+
+/// Writes `tokens` to `source`, returning the span of the last token written (if any).
+fn write_source(
+    source: &mut String,
+    tokens: impl IntoIterator<Item = TokenTree>,
+    mut previous: Option<Span>,
+) -> Option<Span> {
+    for token in tokens {
+        let start = match &token {
+            TokenTree::Group(group) => group.span_open(),
+            _ => token.span(),
+        };
+        if previous.is_some_and(|previous| !adjacent(previous, start)) {
+            source.push(' ');
+        }
+
+        match &token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+                source.push_str(open);
+                let last = write_source(source, group.stream(), Some(group.span_open()));
+                if last.is_some_and(|last| !adjacent(last, group.span_close())) {
+                    source.push(' ');
+                }
+                source.push_str(close);
+                previous = Some(group.span_close());
+            }
+            _ => {
+                source.push_str(&token.to_string());
+                previous = Some(token.span());
+            }
+        }
+    }
+    previous
+}
+
+fn adjacent(previous: Span, next: Span) -> bool {
+    let (end, start) = (previous.end(), next.start());
+    end.line() == start.line() && end.column() == start.column()
+}
+
+fn concat<const N: usize>(streams: [TokenStream; N]) -> TokenStream {
+    streams.into_iter().collect()
+}
+
+/// A group with the same delimiter and span as `group`, but containing `contents`.
+fn regroup(group: &Group, contents: TokenStream) -> TokenStream {
+    let mut new_group = Group::new(group.delimiter(), contents);
+    new_group.set_span(group.span());
+    TokenStream::from(TokenTree::Group(new_group))
+}
+
+/// Parses `code`, replacing `$0`, `$1`, ... by the given token streams.
+///
+/// The identifiers starting with `__bassert` get a `mixed_site` span,
+/// so they cannot clash with the identifiers in the assertion.
+fn template(code: &str, args: &[TokenStream]) -> TokenStream {
+    substitute(code.parse().expect("templates are valid"), args)
+}
+
+fn substitute(code: TokenStream, args: &[TokenStream]) -> TokenStream {
+    let mut output = TokenStream::new();
+    let mut tokens = code.into_iter();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(p) if p.as_char() == '$' => {
+                let idx: usize = match tokens.next() {
+                    Some(TokenTree::Literal(idx)) => {
+                        idx.to_string().parse().expect("argument index")
+                    }
+                    _ => panic!("templates only use `$` for arguments"),
+                };
+                output.extend(args[idx].clone());
+            }
+            TokenTree::Group(group) => {
+                output.extend(regroup(&group, substitute(group.stream(), args)))
+            }
+            TokenTree::Ident(ident) if ident.to_string().starts_with("__bassert") => {
+                output.extend([TokenTree::Ident(Ident::new(
+                    &ident.to_string(),
+                    Span::mixed_site(),
+                ))]);
+            }
+            other => output.extend([other]),
+        }
+    }
+    output
+}
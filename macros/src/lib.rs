@@ -0,0 +1,30 @@
+//! The procedural macro behind the `power` feature of `bassert`.
+//!
+//! It is meant to be used through `bassert::power_bassert!`, which passes along the path to the `bassert` crate.
+//! To stay free of dependencies, the expression is parsed by the small parser in the `parse` module,
+//! which only understands expressions made up of operators, calls, method calls, field accesses and indexing.
+//! Any other expression is handed to `bassert!` unchanged.
+extern crate proc_macro;
+
+mod expand;
+mod parse;
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn power_bassert_impl(input: TokenStream) -> TokenStream {
+    let mut tokens: Vec<TokenTree> = input.into_iter().collect();
+    let krate = match tokens.first() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+            group.stream()
+        }
+        _ => panic!("`power_bassert_impl!` should only be called by `bassert::power_bassert!`"),
+    };
+    let tokens = tokens.split_off(1);
+
+    match parse::parse_assertion(&tokens) {
+        Some((expr, args)) => expand::expand(&krate, &expr, &args),
+        None => expand::fallback(&krate, &tokens),
+    }
+}
@@ -0,0 +1,474 @@
+//! Parsing the subset of Rust expressions whose sub-expressions can be printed.
+//!
+//! Every parsed expression keeps the tokens it was parsed from,
+//! so it can be stringified exactly like it was written.
+use proc_macro::{Delimiter, Group, Spacing, TokenTree};
+
+pub(crate) struct Expr {
+    pub(crate) tokens: Vec<TokenTree>,
+    pub(crate) kind: ExprKind,
+}
+
+pub(crate) enum ExprKind {
+    /// Literals like `1` or `true`, whose value is not worth printing.
+    Literal,
+    /// Paths like `x` or `u8::MAX`.
+    Path,
+    /// Expressions which are emitted as-is, without printing anything inside of them:
+    /// macro calls, closures, arrays, tuples, blocks and struct literals.
+    Opaque,
+    Paren(Group, Box<Expr>),
+    /// `!`, `-`, `*`, `&` or `&mut`.
+    Unary(Vec<TokenTree>, Box<Expr>),
+    Binary(Box<Expr>, BinOp, Box<Expr>),
+    /// The tokens are those of `as` and the type.
+    Cast(Box<Expr>, Vec<TokenTree>),
+    Try(Box<Expr>, TokenTree),
+    /// The tokens are those of the `.` and the field.
+    Field(Box<Expr>, Vec<TokenTree>),
+    Index(Box<Expr>, Group, Box<Expr>),
+    /// The arguments are `None` when they could not be parsed, in which case they are emitted as-is.
+    Call(Box<Expr>, Group, Option<Vec<Expr>>),
+    /// The tokens are those of the `.`, the method name and its generic arguments (if any).
+    MethodCall(Box<Expr>, Vec<TokenTree>, Group, Option<Vec<Expr>>),
+}
+
+impl Expr {
+    /// Whether the expression refers to an existing value in memory,
+    /// which would be moved if it were used by value.
+    pub(crate) fn is_place(&self) -> bool {
+        match &self.kind {
+            ExprKind::Path | ExprKind::Field(..) | ExprKind::Index(..) => true,
+            ExprKind::Unary(op, _) => {
+                matches!(op.first(), Some(TokenTree::Punct(p)) if p.as_char() == '*')
+            }
+            ExprKind::Paren(_, inner) => inner.is_place(),
+            _ => false,
+        }
+    }
+
+    /// Whether the expression only borrows another, like `&x`, whose value would be printed twice.
+    pub(crate) fn is_borrow(&self) -> bool {
+        matches!(&self.kind, ExprKind::Unary(op, _) if matches!(op.first(), Some(TokenTree::Punct(p)) if p.as_char() == '&'))
+    }
+}
+
+pub(crate) struct BinOp {
+    pub(crate) tokens: Vec<TokenTree>,
+    pub(crate) kind: BinKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BinKind {
+    /// Arithmetic, bitwise and logical operators, which take their operands by value.
+    Value,
+    /// Comparison operators, which take their operands by reference.
+    Comparison,
+}
+
+/// Parses the asserted expression, followed by the (optional) custom message.
+///
+/// Returns `None` if the expression is not understood.
+pub(crate) fn parse_assertion(tokens: &[TokenTree]) -> Option<(Expr, Vec<TokenTree>)> {
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.expr(0)?;
+    match tokens.get(parser.pos) {
+        None => Some((expr, Vec::new())),
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {
+            Some((expr, tokens[parser.pos + 1..].to_vec()))
+        }
+        _ => None,
+    }
+}
+
+/// Parses all of `tokens` as a single expression.
+fn parse_all(tokens: &[TokenTree]) -> Option<Expr> {
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.expr(0)?;
+    (parser.pos == tokens.len()).then_some(expr)
+}
+
+/// Parses the contents of `group` as a comma-separated list of expressions, like the arguments of a call.
+fn parse_list(group: &Group) -> Option<Vec<Expr>> {
+    let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+    };
+    let mut exprs = Vec::new();
+    while parser.pos < tokens.len() {
+        exprs.push(parser.expr(0)?);
+        match tokens.get(parser.pos) {
+            None => break,
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => parser.pos += 1,
+            _ => return None,
+        }
+    }
+    Some(exprs)
+}
+
+fn group_tokens(group: &Group) -> Vec<TokenTree> {
+    group.stream().into_iter().collect()
+}
+
+/// Operators consisting of multiple punctuation tokens, longest first.
+const MULTI_CHAR_OPERATORS: &[&str] = &[
+    "..=", "...", "<<=", ">>=", "==", "!=", "<=", ">=", "&&", "||", "<<", ">>", "::", "->", "=>",
+    "..", "+=", "-=", "*=", "/=", "%=", "^=", "&=", "|=",
+];
+
+/// Expressions starting with these keywords are not understood.
+const KEYWORDS: &[&str] = &[
+    "if", "match", "loop", "while", "for", "unsafe", "async", "move", "return", "break",
+    "continue", "let", "yield", "static", "const",
+];
+
+const CAST_PRECEDENCE: u8 = 11;
+
+fn binary_op(op: &str) -> Option<(u8, BinKind)> {
+    let precedence = match op {
+        "*" | "/" | "%" => 10,
+        "+" | "-" => 9,
+        "<<" | ">>" => 8,
+        "&" => 7,
+        "^" => 6,
+        "|" => 5,
+        "==" | "!=" | "<" | ">" | "<=" | ">=" => return Some((4, BinKind::Comparison)),
+        "&&" => 3,
+        "||" => 2,
+        _ => return None,
+    };
+    Some((precedence, BinKind::Value))
+}
+
+struct Parser<'a> {
+    tokens: &'a [TokenTree],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a TokenTree> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_ident(&self, name: &str) -> bool {
+        matches!(self.peek(), Some(TokenTree::Ident(ident)) if ident.to_string() == name)
+    }
+
+    fn peek_punct(&self, ch: char) -> bool {
+        matches!(self.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ch)
+    }
+
+    /// The operator at the current position (gluing joint punctuation like `==`),
+    /// and the number of tokens it consists of.
+    fn peek_op(&self) -> Option<(String, usize)> {
+        let mut chars = String::new();
+        for token in self.tokens[self.pos..].iter().take(3) {
+            match token {
+                TokenTree::Punct(p) => {
+                    chars.push(p.as_char());
+                    if p.spacing() == Spacing::Alone {
+                        break;
+                    }
+                }
+                _ => break,
+            }
+        }
+        if chars.is_empty() {
+            return None;
+        }
+
+        for len in (2..=chars.len()).rev() {
+            if MULTI_CHAR_OPERATORS.contains(&&chars[..len]) {
+                return Some((chars[..len].to_string(), len));
+            }
+        }
+        Some((chars[..1].to_string(), 1))
+    }
+
+    fn peek_op_is(&self, op: &str) -> bool {
+        matches!(self.peek_op(), Some((found, _)) if found == op)
+    }
+
+    fn expr_from(&self, start: usize, kind: ExprKind) -> Expr {
+        Expr {
+            tokens: self.tokens[start..self.pos].to_vec(),
+            kind,
+        }
+    }
+
+    /// Parses an expression whose binary operators bind at least as tightly as `min_precedence`.
+    fn expr(&mut self, min_precedence: u8) -> Option<Expr> {
+        let start = self.pos;
+        let mut lhs = self.unary()?;
+        loop {
+            if min_precedence <= CAST_PRECEDENCE && self.peek_ident("as") {
+                let ty_start = self.pos;
+                self.pos += 1;
+                self.ty()?;
+                let ty = self.tokens[ty_start..self.pos].to_vec();
+                lhs = self.expr_from(start, ExprKind::Cast(Box::new(lhs), ty));
+                continue;
+            }
+
+            let Some((op, len)) = self.peek_op() else {
+                break;
+            };
+            let Some((precedence, kind)) = binary_op(&op) else {
+                break;
+            };
+            if precedence < min_precedence {
+                break;
+            }
+            // Like in Rust itself, comparisons cannot be chained.
+            if kind == BinKind::Comparison
+                && matches!(&lhs.kind, ExprKind::Binary(_, op, _) if op.kind == BinKind::Comparison)
+            {
+                return None;
+            }
+
+            let op = BinOp {
+                tokens: self.tokens[self.pos..self.pos + len].to_vec(),
+                kind,
+            };
+            self.pos += len;
+            let rhs = self.expr(precedence + 1)?;
+            lhs = self.expr_from(start, ExprKind::Binary(Box::new(lhs), op, Box::new(rhs)));
+        }
+        Some(lhs)
+    }
+
+    fn unary(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        let op = match self.peek()? {
+            // Only a single `&` of `&&` is taken, so `&&x` is parsed as `& &x`.
+            TokenTree::Punct(p) if p.as_char() == '&' => p,
+            TokenTree::Punct(p)
+                if matches!(p.as_char(), '!' | '-' | '*')
+                    && matches!(self.peek_op(), Some((_, 1))) =>
+            {
+                p
+            }
+            _ => {
+                let primary = self.primary()?;
+                return self.postfix(start, primary);
+            }
+        };
+
+        self.pos += 1;
+        let mut op_tokens = vec![TokenTree::Punct(op.clone())];
+        if op.as_char() == '&' && self.peek_ident("mut") {
+            op_tokens.extend(self.peek().cloned());
+            self.pos += 1;
+        }
+        let inner = self.unary()?;
+        let kind = match inner.kind {
+            ExprKind::Literal if op.as_char() == '-' => ExprKind::Literal,
+            _ => ExprKind::Unary(op_tokens, Box::new(inner)),
+        };
+        Some(self.expr_from(start, kind))
+    }
+
+    fn primary(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        let kind = match self.peek()? {
+            TokenTree::Literal(_) => {
+                self.pos += 1;
+                ExprKind::Literal
+            }
+            TokenTree::Ident(ident) if matches!(ident.to_string().as_str(), "true" | "false") => {
+                self.pos += 1;
+                ExprKind::Literal
+            }
+            TokenTree::Ident(ident) if KEYWORDS.contains(&ident.to_string().as_str()) => {
+                return None
+            }
+            TokenTree::Ident(_) | TokenTree::Punct(_) => {
+                self.path()?;
+                self.after_path()
+            }
+            TokenTree::Group(group) => {
+                self.pos += 1;
+                match group.delimiter() {
+                    Delimiter::Parenthesis | Delimiter::None => {
+                        match parse_all(&group_tokens(group)) {
+                            Some(inner) => ExprKind::Paren(group.clone(), Box::new(inner)),
+                            // The unit value, tuples and unsupported expressions.
+                            None => ExprKind::Opaque,
+                        }
+                    }
+                    Delimiter::Bracket | Delimiter::Brace => ExprKind::Opaque,
+                }
+            }
+        };
+        Some(self.expr_from(start, kind))
+    }
+
+    /// Parses a path like `x`, `::std::u8::MAX` or `Vec::<u8>::new`.
+    fn path(&mut self) -> Option<()> {
+        if self.peek_op_is("::") {
+            self.pos += 2;
+        }
+        loop {
+            match self.peek()? {
+                TokenTree::Ident(_) => self.pos += 1,
+                _ => return None,
+            }
+            if !self.peek_op_is("::") {
+                return Some(());
+            }
+            self.pos += 2;
+            if self.peek_punct('<') {
+                self.generics()?;
+                if !self.peek_op_is("::") {
+                    return Some(());
+                }
+                self.pos += 2;
+            }
+        }
+    }
+
+    /// Macro calls and struct literals start with a path as well.
+    fn after_path(&mut self) -> ExprKind {
+        if self.peek_op_is("!") {
+            if let Some(TokenTree::Group(_)) = self.tokens.get(self.pos + 1) {
+                self.pos += 2;
+                return ExprKind::Opaque;
+            }
+        }
+        if let Some(TokenTree::Group(group)) = self.peek() {
+            if group.delimiter() == Delimiter::Brace {
+                self.pos += 1;
+                return ExprKind::Opaque;
+            }
+        }
+        ExprKind::Path
+    }
+
+    /// Skips a list of generic arguments like `<Vec<u8>>`, starting at its `<`.
+    fn generics(&mut self) -> Option<()> {
+        let mut depth = 0_usize;
+        loop {
+            match self.peek()? {
+                TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+                TokenTree::Punct(p) if p.as_char() == '>' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.pos += 1;
+                        return Some(());
+                    }
+                }
+                // The `>` of `->` (like in `Box<dyn Fn() -> u8>`) does not close anything.
+                TokenTree::Punct(p) if p.as_char() == '-' && p.spacing() == Spacing::Joint => {
+                    self.pos += 1;
+                }
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Skips the type of a cast, like `u8`, `*const T` or `Wrapping<u8>`.
+    fn ty(&mut self) -> Option<()> {
+        while matches!(self.peek(), Some(TokenTree::Punct(p)) if matches!(p.as_char(), '&' | '*'))
+            || self.peek_ident("mut")
+            || self.peek_ident("const")
+            || self.peek_ident("dyn")
+        {
+            self.pos += 1;
+        }
+        if let TokenTree::Group(_) = self.peek()? {
+            self.pos += 1;
+            return Some(());
+        }
+
+        if self.peek_op_is("::") {
+            self.pos += 2;
+        }
+        loop {
+            match self.peek()? {
+                TokenTree::Ident(_) => self.pos += 1,
+                _ => return None,
+            }
+            if self.peek_punct('<') {
+                self.generics()?;
+            }
+            if !self.peek_op_is("::") {
+                return Some(());
+            }
+            self.pos += 2;
+        }
+    }
+
+    /// Parses the method calls, field accesses, calls, indexing and `?`s following `expr`.
+    fn postfix(&mut self, start: usize, mut expr: Expr) -> Option<Expr> {
+        loop {
+            let kind = match self.peek() {
+                Some(question @ TokenTree::Punct(p)) if p.as_char() == '?' => {
+                    self.pos += 1;
+                    ExprKind::Try(Box::new(expr), question.clone())
+                }
+                Some(TokenTree::Punct(_)) if self.peek_op_is(".") => self.member(expr)?,
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                    self.pos += 1;
+                    ExprKind::Call(Box::new(expr), group.clone(), parse_list(group))
+                }
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => {
+                    self.pos += 1;
+                    let tokens = group_tokens(group);
+                    let index = parse_all(&tokens).unwrap_or(Expr {
+                        tokens,
+                        kind: ExprKind::Opaque,
+                    });
+                    ExprKind::Index(Box::new(expr), group.clone(), Box::new(index))
+                }
+                _ => return Some(expr),
+            };
+            expr = self.expr_from(start, kind);
+        }
+    }
+
+    /// Parses a method call or field access, starting at its `.`.
+    fn member(&mut self, receiver: Expr) -> Option<ExprKind> {
+        let dot = self.pos;
+        self.pos += 1;
+        match self.peek()? {
+            TokenTree::Ident(ident) if ident.to_string() == "await" => None,
+            TokenTree::Ident(_) => {
+                self.pos += 1;
+                let turbofish = self.peek_op_is("::");
+                if turbofish {
+                    self.pos += 2;
+                    if !self.peek_punct('<') {
+                        return None;
+                    }
+                    self.generics()?;
+                }
+                let name = self.tokens[dot..self.pos].to_vec();
+                match self.peek() {
+                    Some(TokenTree::Group(group))
+                        if group.delimiter() == Delimiter::Parenthesis =>
+                    {
+                        self.pos += 1;
+                        let args = parse_list(group);
+                        Some(ExprKind::MethodCall(
+                            Box::new(receiver),
+                            name,
+                            group.clone(),
+                            args,
+                        ))
+                    }
+                    _ if turbofish => None,
+                    _ => Some(ExprKind::Field(Box::new(receiver), name)),
+                }
+            }
+            // Tuple fields, like `pair.0`.
+            TokenTree::Literal(_) => {
+                self.pos += 1;
+                let name = self.tokens[dot..self.pos].to_vec();
+                Some(ExprKind::Field(Box::new(receiver), name))
+            }
+            _ => None,
+        }
+    }
+}
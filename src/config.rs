@@ -0,0 +1,226 @@
+//! Global configuration of how failure messages are formatted.
+#[cfg(feature = "std")]
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+#[cfg(feature = "std")]
+static CONFIG: RwLock<BassertConfig> = RwLock::new(BassertConfig::new());
+
+/// The maximum length of a formatted operand,
+/// unless overridden by the `BASSERT_MAX_LEN` environment variable or [`BassertConfig::max_len`].
+pub const DEFAULT_MAX_LEN: usize = 4096;
+
+/// Configures how the failure messages of all assertions are formatted.
+///
+/// A configuration is built using its builder methods,
+/// after which it is activated (for the whole process) using [`BassertConfig::install`]:
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// bassert::BassertConfig::new().pretty(true).install();
+/// ```
+///
+/// The default configuration formats failure messages exactly as described in the documentation of [`bassert!`](crate::bassert!).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BassertConfig {
+    pub(crate) pretty: bool,
+    max_len: Option<usize>,
+    style: Option<MessageStyle>,
+    color: Option<bool>,
+    show_types: Option<bool>,
+    pub(crate) escape_control: bool,
+    pub(crate) align: bool,
+    pub(crate) verbose_operators: bool,
+}
+
+/// The layout of the failure messages, see [`BassertConfig::style`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MessageStyle {
+    /// The expression on the first line, followed by one line per value,
+    /// exactly as described in the documentation of [`bassert!`](crate::bassert!).
+    #[default]
+    Default,
+    /// Everything on a single line, like `` assertion failed: `a == b` (a: `1`, b: `2`) ``.
+    ///
+    /// The lines describing the difference between the operands (and the diff and backtrace) are left out.
+    Compact,
+    /// Like [`MessageStyle::Default`], but with the lines listing the values before the expression.
+    ValuesFirst,
+}
+
+impl BassertConfig {
+    /// Creates the default configuration.
+    pub const fn new() -> Self {
+        BassertConfig {
+            pretty: false,
+            max_len: None,
+            style: None,
+            color: None,
+            show_types: None,
+            escape_control: false,
+            align: false,
+            verbose_operators: false,
+        }
+    }
+
+    /// Format the operands using `{:#?}` (pretty-printed [`Debug`](core::fmt::Debug)) instead of `{:?}`.
+    ///
+    /// Useful for nested data, which is hard to read on a single line.
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// The maximum number of characters the formatted value of a single operand may take up.
+    ///
+    /// Longer values are truncated, ending in a note saying how many characters were left out.
+    /// When not set, the value of the `BASSERT_MAX_LEN` environment variable is used,
+    /// or [`DEFAULT_MAX_LEN`] if that is not set either.
+    /// Pass `usize::MAX` to never truncate.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    pub(crate) fn effective_max_len(&self) -> usize {
+        self.max_len.or_else(env_max_len).unwrap_or(DEFAULT_MAX_LEN)
+    }
+
+    /// The layout of the failure messages.
+    ///
+    /// When not set, the value of the `BASSERT_STYLE` environment variable is used
+    /// (`default`, `compact` or `values-first`),
+    /// or [`MessageStyle::Default`] if that is not set either.
+    pub fn style(mut self, style: MessageStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub(crate) fn effective_style(&self) -> MessageStyle {
+        self.style.or_else(env_style).unwrap_or_default()
+    }
+
+    /// Whether to color the panic messages.
+    ///
+    /// When not set, they are colored when written to a terminal,
+    /// following the `NO_COLOR`, `CLICOLOR` and `CLICOLOR_FORCE` environment variables (see the `color` feature).
+    /// Setting this overrides them, which is useful in tests checking the (un)colored output.
+    /// Messages are never colored without the `color` feature.
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub(crate) fn effective_color(&self, detect: impl FnOnce() -> bool) -> bool {
+        cfg!(all(feature = "color", not(test))) && self.color.unwrap_or_else(detect)
+    }
+
+    /// Whether to print the type of each value after it, like `` x: `10` (i32) ``.
+    ///
+    /// When not set, types are only printed with the `type-names` feature.
+    pub fn show_types(mut self, show_types: bool) -> Self {
+        self.show_types = Some(show_types);
+        self
+    }
+
+    pub(crate) fn effective_show_types(&self) -> bool {
+        // Like with colors, the crate's own unit tests match on the default messages.
+        // The `type-names` feature is tested by the `type_names` integration test instead.
+        self.show_types
+            .unwrap_or(cfg!(all(feature = "type-names", not(test))))
+    }
+
+    /// Escape the control characters (like a carriage return or `\x1b`) in the formatted operands, like `\r` or `\u{1b}`.
+    ///
+    /// This keeps `Debug` implementations printing raw bytes from garbling the message in a terminal.
+    /// The line breaks of [`pretty`](BassertConfig::pretty) values are kept, whereas any other line break is escaped as well,
+    /// so each value stays on its own line(s).
+    pub fn escape_control(mut self, escape_control: bool) -> Self {
+        self.escape_control = escape_control;
+        self
+    }
+
+    /// Pad the lines listing the values, so the values start in the same column:
+    ///
+    /// ```text
+    /// assertion failed: `x.len() == y`
+    /// x.len(): `3`,
+    /// y:       `4`
+    /// ```
+    ///
+    /// This does not affect [`MessageStyle::Compact`] messages, which put all values on a single line.
+    pub fn align(mut self, align: bool) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Describe failed comparisons in words, like `` assertion failed: `x` is not greater than `y` ``
+    /// instead of `` assertion failed: `x > y` ``.
+    ///
+    /// The phrases are those of [`Kind::failed_phrase`](crate::Kind::failed_phrase).
+    /// Other assertions (like chained comparisons, or plain boolean expressions) are printed as they are written.
+    pub fn verbose_operators(mut self, verbose_operators: bool) -> Self {
+        self.verbose_operators = verbose_operators;
+        self
+    }
+
+    /// Makes this the configuration used by all following failing assertions.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn install(self) {
+        *CONFIG.write().unwrap_or_else(PoisonError::into_inner) = self;
+    }
+
+    /// Returns (a copy of) the currently installed configuration.
+    #[cfg(feature = "std")]
+    pub fn current() -> Self {
+        CONFIG
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Returns (a copy of) the currently installed configuration.
+    ///
+    /// Without the `std` feature no configuration can be installed,
+    /// so this is always the default configuration.
+    #[cfg(not(feature = "std"))]
+    pub fn current() -> Self {
+        BassertConfig::new()
+    }
+}
+
+#[cfg(feature = "std")]
+fn env_max_len() -> Option<usize> {
+    static ENV_MAX_LEN: OnceLock<Option<usize>> = OnceLock::new();
+
+    *ENV_MAX_LEN.get_or_init(|| std::env::var("BASSERT_MAX_LEN").ok()?.parse().ok())
+}
+
+#[cfg(not(feature = "std"))]
+fn env_max_len() -> Option<usize> {
+    None
+}
+
+#[cfg(feature = "std")]
+fn env_style() -> Option<MessageStyle> {
+    static ENV_STYLE: OnceLock<Option<MessageStyle>> = OnceLock::new();
+
+    *ENV_STYLE.get_or_init(|| match std::env::var("BASSERT_STYLE").ok()?.as_str() {
+        "default" => Some(MessageStyle::Default),
+        "compact" => Some(MessageStyle::Compact),
+        "values-first" => Some(MessageStyle::ValuesFirst),
+        _ => None,
+    })
+}
+
+#[cfg(not(feature = "std"))]
+fn env_style() -> Option<MessageStyle> {
+    None
+}
+
+impl Default for BassertConfig {
+    fn default() -> Self {
+        BassertConfig::new()
+    }
+}
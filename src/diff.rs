@@ -0,0 +1,78 @@
+//! Line-based diffing of the pretty-printed operands of a failed `==` assertion.
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// Renders a line-by-line diff of `lhs` and `rhs`.
+///
+/// Lines only present in `lhs` are prefixed with `-`,
+/// lines only present in `rhs` are prefixed with `+`,
+/// and lines present in both are prefixed with a space.
+pub(crate) fn line_diff(lhs: &str, rhs: &str) -> String {
+    let lhs: Vec<&str> = lhs.lines().collect();
+    let rhs: Vec<&str> = rhs.lines().collect();
+
+    // `lengths[i][j]` is the length of the longest common subsequence of `lhs[i..]` and `rhs[j..]`.
+    let mut lengths = vec![vec![0usize; rhs.len() + 1]; lhs.len() + 1];
+    for i in (0..lhs.len()).rev() {
+        for j in (0..rhs.len()).rev() {
+            lengths[i][j] = if lhs[i] == rhs[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < lhs.len() && j < rhs.len() {
+        if lhs[i] == rhs[j] {
+            push_line(&mut out, ' ', lhs[i]);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            push_line(&mut out, '-', lhs[i]);
+            i += 1;
+        } else {
+            push_line(&mut out, '+', rhs[j]);
+            j += 1;
+        }
+    }
+    for line in &lhs[i..] {
+        push_line(&mut out, '-', line);
+    }
+    for line in &rhs[j..] {
+        push_line(&mut out, '+', line);
+    }
+    out
+}
+
+fn push_line(out: &mut String, prefix: char, line: &str) {
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    let _ = write!(out, "{}{}", prefix, line);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::line_diff;
+
+    #[test]
+    fn identical_input_has_no_changes() {
+        bassert!((line_diff("a\nb", "a\nb")) == " a\n b");
+    }
+
+    #[test]
+    fn changed_line_is_removed_and_added() {
+        bassert!((line_diff("a\nb\nc", "a\nx\nc")) == " a\n-b\n+x\n c");
+    }
+
+    #[test]
+    fn extra_lines_at_the_end() {
+        bassert!((line_diff("a", "a\nb\nc")) == " a\n+b\n+c");
+        bassert!((line_diff("a\nb\nc", "a")) == " a\n-b\n-c");
+    }
+}
@@ -0,0 +1,246 @@
+//! Normalizing the whitespace of stringified expressions.
+//!
+//! `stringify!` keeps the spacing the assertion was written with (like `x+ 1` or `len()==2`),
+//! so the expressions in failure messages are normalized to put a single space around binary operators,
+//! and to collapse any other whitespace (like the line breaks of a multi-line closure) into a single space.
+//! String and character literals are left as they are.
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+/// Operators which are always binary, so they always get a space on both sides.
+/// This includes the `~=` of approximate comparisons, which is not Rust but is printed like an operator.
+const BINARY: &[&str] = &[
+    "~=", "==", "!=", "<=", ">=", "=>", "+=", "-=", "*=", "/=", "%=", "^=", "&=", "|=", "<<=",
+    ">>=", "=", "+", "/", "%", "^",
+];
+
+/// Operators which can also be a prefix (like `&&x`, `-x` or the closure `|| x`) or part of generics (like `Vec<u8>`).
+/// When they follow an operand, they are spaced like binary operators if they were written with a space on either side.
+const AMBIGUOUS: &[&str] = &["&&", "||", "<<", ">>", "<", ">", "-", "*", "&"];
+
+/// All multi-character punctuation, longest first, so the tokens are split like the compiler does.
+const PUNCTUATION: &[&str] = &[
+    "~=", "<<=", ">>=", "...", "..=", "::", "->", "=>", "==", "!=", "<=", ">=", "&&", "||", "+=",
+    "-=", "*=", "/=", "%=", "^=", "&=", "|=", "<<", ">>", "..",
+];
+
+/// Keywords after which an operator starts an operand, like in `move || x`.
+const PREFIX_KEYWORDS: &[&str] = &["move", "return", "in", "if", "match", "else", "break", "as"];
+
+/// Normalizes a stringified expression, without allocating when it is normalized already.
+pub fn normalize(expr: &'static str) -> Cow<'static, str> {
+    match normalized(expr) {
+        Some(normalized) => Cow::Owned(normalized),
+        None => Cow::Borrowed(expr),
+    }
+}
+
+/// Normalizes an expression built while the assertion failed, like `x == y` for a chained comparison.
+pub(crate) fn normalize_owned(expr: String) -> String {
+    normalized(&expr).unwrap_or(expr)
+}
+
+/// The normalized form of `expr`, or `None` if it is normalized already.
+fn normalized(expr: &str) -> Option<String> {
+    let mut out = String::with_capacity(expr.len());
+    // Whether whitespace was skipped since the last token.
+    let mut space = false;
+    // Whether the last token ended an operand (like an identifier or a closing parenthesis),
+    // so an operator following it is binary.
+    let mut after_operand = false;
+    let mut prev = "";
+    // The number of `<` opening generic arguments (like in `Vec<u8>` or `parse::<u8>`) which are not closed yet.
+    let mut generics = 0_usize;
+    let mut rest = expr.trim_start();
+
+    while !rest.is_empty() {
+        let len = token_len(rest);
+        let token = &rest[..len];
+        rest = &rest[len..];
+        let trimmed = rest.trim_start();
+        let space_after = trimmed.len() < rest.len();
+        rest = trimmed;
+
+        let binary = if BINARY.contains(&token) {
+            true
+        } else if token == "<" && (prev == "::" || (!space && starts_type(prev))) {
+            generics += 1;
+            false
+        } else if generics > 0 && matches!(token, ">" | ">>") {
+            generics = generics.saturating_sub(len);
+            false
+        } else {
+            AMBIGUOUS.contains(&token) && after_operand && (space || space_after)
+        };
+
+        if binary {
+            if !out.is_empty() {
+                out.push(' ');
+            }
+            out.push_str(token);
+            space = true;
+        } else {
+            if space {
+                out.push(' ');
+            }
+            out.push_str(token);
+            space = space_after;
+        }
+        after_operand = ends_operand(token);
+        prev = token;
+    }
+
+    (out != expr).then_some(out)
+}
+
+fn ends_operand(token: &str) -> bool {
+    match token.chars().next() {
+        Some(c) if c.is_alphanumeric() || c == '_' => !PREFIX_KEYWORDS.contains(&token),
+        Some('"' | '\'' | ')' | ']' | '}' | '?') => true,
+        _ => false,
+    }
+}
+
+/// Whether `token` is a type name (like `Vec`), which may be followed by generic arguments.
+fn starts_type(token: &str) -> bool {
+    token.starts_with(|c: char| c.is_uppercase())
+}
+
+/// The length of the token at the start of `rest`, which does not start with whitespace.
+fn token_len(rest: &str) -> usize {
+    let c = rest.chars().next().expect("not empty");
+    if c == '"' {
+        return string_len(rest);
+    }
+    if c == '\'' {
+        return char_len(rest);
+    }
+    if c.is_alphanumeric() || c == '_' {
+        let len = rest
+            .find(|c: char| !c.is_alphanumeric() && c != '_')
+            .unwrap_or(rest.len());
+        let (word, after) = rest.split_at(len);
+        // The exponent of a float like `1e-5` is part of the number.
+        if c.is_ascii_digit()
+            && word.ends_with(['e', 'E'])
+            && after.starts_with(['+', '-'])
+            && after[1..].starts_with(|c: char| c.is_ascii_digit())
+        {
+            return len + 1 + token_len(&after[1..]);
+        }
+        // Prefixed strings like `b"..."` or `r#"..."#` are a single token.
+        return match word {
+            "b" | "c" if after.starts_with('"') => len + string_len(after),
+            "r" | "br" | "cr" if after.trim_start_matches('#').starts_with('"') => {
+                len + raw_string_len(after)
+            }
+            _ => len,
+        };
+    }
+    PUNCTUATION
+        .iter()
+        .find(|punct| rest.starts_with(*punct))
+        .map_or(c.len_utf8(), |punct| punct.len())
+}
+
+/// The length of the string literal at the start of `rest`, including its quotes.
+fn string_len(rest: &str) -> usize {
+    let mut escaped = false;
+    for (idx, c) in rest.char_indices().skip(1) {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return idx + 1,
+            _ => escaped = false,
+        }
+    }
+    rest.len()
+}
+
+/// The length of a raw string literal (like `#"..."#`) without its `r` prefix.
+fn raw_string_len(rest: &str) -> usize {
+    let hashes = rest.len() - rest.trim_start_matches('#').len();
+    let body = &rest[hashes + 1..];
+    let mut closing = String::from("\"");
+    closing.extend(core::iter::repeat_n('#', hashes));
+    body.find(closing.as_str())
+        .map_or(rest.len(), |idx| hashes + 1 + idx + closing.len())
+}
+
+/// The length of the character literal (like `'a'` or `'\n'`) or lifetime (like `'a`) at the start of `rest`.
+fn char_len(rest: &str) -> usize {
+    let mut chars = rest.char_indices().skip(1);
+    match chars.next() {
+        // The escaped character (like the quote in `'\''`) is skipped.
+        Some((_, '\\')) => rest
+            .get(3..)
+            .and_then(|after| after.find('\''))
+            .map_or(rest.len(), |idx| idx + 4),
+        Some((idx, c)) => {
+            let next = idx + c.len_utf8();
+            if rest[next..].starts_with('\'') {
+                next + 1
+            } else {
+                // A lifetime or label, which continues like an identifier.
+                rest[next..]
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .map_or(rest.len(), |len| next + len)
+            }
+        }
+        None => rest.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize;
+
+    #[test]
+    fn spaces_binary_operators() {
+        bassert!((normalize("x+ 1")) == "x + 1");
+        bassert!((normalize("*x >5 && y.len()==2")) == "*x > 5 && y.len() == 2");
+        bassert!((normalize("a%b^c/ d")) == "a % b ^ c / d");
+        bassert!((normalize("a -b *c")) == "a - b * c");
+        bassert!(
+            (normalize("match x { Some(y)=>y, None => 0 }"))
+                == "match x { Some(y) => y, None => 0 }"
+        );
+    }
+
+    #[test]
+    fn collapses_whitespace() {
+        bassert!(
+            (normalize("v.iter().map(|x| {\n        x * 2\n    })"))
+                == "v.iter().map(|x| { x * 2 })"
+        );
+        bassert!((normalize("  a   as   u8 ")) == "a as u8");
+        bassert!((normalize("x + 1 == y")) == "x + 1 == y");
+    }
+
+    #[test]
+    fn keeps_prefix_operators_and_generics() {
+        bassert!((normalize("&&x")) == "&&x");
+        bassert!(
+            (normalize("v.iter().any(|| true) || f(&&a, -1, *b)"))
+                == "v.iter().any(|| true) || f(&&a, -1, *b)"
+        );
+        bassert!((normalize("move ||x")) == "move ||x");
+        bassert!((normalize("x-1")) == "x-1");
+        bassert!(
+            (normalize("\"5\".parse::<Vec<Vec<u8>>>() == Ok(v)"))
+                == "\"5\".parse::<Vec<Vec<u8>>>() == Ok(v)"
+        );
+        bassert!((normalize("x as Vec<u8> >= y")) == "x as Vec<u8> >= y");
+        bassert!((normalize("Some(n @ 1..=5)")) == "Some(n @ 1..=5)");
+        bassert!((normalize("1e-5+2.5E+3")) == "1e-5 + 2.5E+3");
+    }
+
+    #[test]
+    fn keeps_literals() {
+        bassert!((normalize(r#"s=="a  +b""#)) == r#"s == "a  +b""#);
+        bassert!((normalize(r#"s=="\"  ==""#)) == r#"s == "\"  ==""#);
+        bassert!((normalize(r##"s==r#"a\"  +"#"##)) == r##"s == r#"a\"  +"#"##);
+        bassert!((normalize(r#"s==b"a  +b""#)) == r#"s == b"a  +b""#);
+        bassert!((normalize("c=='+' || c == '\\''")) == "c == '+' || c == '\\''");
+        bassert!((normalize("f::<'a>(x)+1")) == "f::<'a>(x) + 1");
+    }
+}
@@ -0,0 +1,725 @@
+//! The structured description of a failed assertion.
+use crate::expr;
+use crate::internal::Difference;
+use crate::style::{Painter, TypeName};
+use crate::{BassertConfig, Kind, MessageStyle};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::panic::Location;
+
+/// A failed assertion, as returned by [`try_bassert!`](crate::try_bassert!).
+///
+/// It holds everything the panic message of [`bassert!`](crate::bassert!) is built from:
+/// the kind of assertion, the stringified expressions and the formatted values of the operands.
+///
+/// Its [`Display`](fmt::Display) implementation prints the message `bassert!` would have panicked with
+/// (without colors, even when the `color` feature is enabled),
+/// followed by the [`location`](BassertFailure::location) of the assertion, like `at src/main.rs:4:5`.
+/// Because it implements [`std::error::Error`] (with the default `std` feature),
+/// it can be returned from `main` or converted into other error types using `?`:
+///
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// # #[cfg(feature = "std")]
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let (x, y) = (1, 2);
+///     try_bassert!(x < y)?;
+///     Ok(())
+/// }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BassertFailure {
+    // Boxed to keep `Result<_, BassertFailure>` small.
+    pub(crate) inner: Box<Inner>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Inner {
+    pub(crate) kind: Option<Kind>,
+    /// The full asserted expression, like `x < y`.
+    pub(crate) expr: String,
+    pub(crate) lhs_expr: Cow<'static, str>,
+    pub(crate) rhs_expr: Option<Cow<'static, str>>,
+    pub(crate) lhs: Option<String>,
+    pub(crate) rhs: Option<String>,
+    pub(crate) message: Option<String>,
+    /// The label passed before the assertion, like `"user age bounds"` in `bassert!("user age bounds"; age < 150)`.
+    pub(crate) label: Option<&'static str>,
+    /// Lines explaining which part of the assertion failed, like the broken link of a chained comparison.
+    pub(crate) notes: Vec<(&'static str, String)>,
+    /// Whether the operands of an ordering comparison could not be compared at all, like when one of them is `NaN`.
+    pub(crate) incomparable: bool,
+    /// The lines listing the values, in the order they are printed.
+    pub(crate) values: Vec<Value>,
+    pub(crate) difference: Option<Difference>,
+    pub(crate) diff: Option<String>,
+    /// Only captured with the `backtrace` feature, when enabled using `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE`.
+    /// It is stored formatted, so failures can still be cloned and compared.
+    pub(crate) backtrace: Option<String>,
+    /// The source line of the assertion with a caret under its column, only with the `source-snippet` feature.
+    pub(crate) snippet: Option<String>,
+    /// The name (or id) of the thread the assertion failed on, only with the `thread-info` feature.
+    pub(crate) thread: Option<String>,
+    pub(crate) location: &'static Location<'static>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Value {
+    pub(crate) expr: Cow<'static, str>,
+    pub(crate) value: String,
+    pub(crate) type_name: Option<TypeName>,
+}
+
+impl BassertFailure {
+    #[track_caller]
+    pub(crate) fn new(
+        kind: Option<Kind>,
+        expr: String,
+        lhs_expr: &'static str,
+        rhs_expr: Option<&'static str>,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> Self {
+        let inner = Inner {
+            kind,
+            expr: expr::normalize_owned(expr),
+            lhs_expr: expr::normalize(lhs_expr),
+            rhs_expr: rhs_expr.map(expr::normalize),
+            lhs: None,
+            rhs: None,
+            message: args.map(|args| args.to_string()),
+            label: None,
+            notes: Vec::new(),
+            incomparable: false,
+            values: Vec::new(),
+            difference: None,
+            diff: None,
+            backtrace: capture_backtrace(),
+            thread: current_thread(),
+            snippet: capture_snippet(Location::caller()),
+            location: Location::caller(),
+        };
+        BassertFailure {
+            inner: Box::new(inner),
+        }
+    }
+
+    /// Adds a line printing `value` as the value of `expr`.
+    pub(crate) fn push_value(
+        &mut self,
+        expr: &'static str,
+        value: String,
+        type_name: Option<TypeName>,
+    ) {
+        self.inner.values.push(Value {
+            expr: expr::normalize(expr),
+            value,
+            type_name,
+        });
+    }
+
+    /// Adds a line explaining which part of the assertion failed, like the guard of a pattern.
+    pub(crate) fn push_note(&mut self, label: &'static str, expr: String) {
+        self.inner.notes.push((label, expr::normalize_owned(expr)));
+    }
+
+    /// The kind of comparison that failed.
+    ///
+    /// For chained comparisons, this is the kind of the broken link.
+    /// It is `None` for assertions which are not a comparison:
+    /// range membership (`in`), negations and plain boolean expressions.
+    pub fn kind(&self) -> Option<Kind> {
+        self.inner.kind
+    }
+
+    /// The stringified left-hand side of the assertion.
+    ///
+    /// This is the pattern for `=`, the inner expression for negations,
+    /// and the whole expression for plain boolean assertions.
+    pub fn lhs_expr(&self) -> &str {
+        &self.inner.lhs_expr
+    }
+
+    /// The stringified right-hand side of the assertion, if it has one.
+    pub fn rhs_expr(&self) -> Option<&str> {
+        self.inner.rhs_expr.as_deref()
+    }
+
+    /// The formatted value of the left-hand side, if it has one.
+    ///
+    /// Patterns and plain boolean expressions have no value printed.
+    pub fn lhs(&self) -> Option<&str> {
+        self.inner.lhs.as_deref()
+    }
+
+    /// The formatted value of the right-hand side, if it has one.
+    pub fn rhs(&self) -> Option<&str> {
+        self.inner.rhs.as_deref()
+    }
+
+    /// The custom message passed to the assertion, if any.
+    pub fn message(&self) -> Option<&str> {
+        self.inner.message.as_deref()
+    }
+
+    /// The label passed before the assertion, if any.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate bassert;
+    /// let age = 200;
+    /// let failure = try_bassert!("user age bounds"; age < 150).unwrap_err();
+    /// bassert!((failure.label()) == (Some("user age bounds")));
+    /// ```
+    pub fn label(&self) -> Option<&str> {
+        self.inner.label
+    }
+
+    /// The location of the assertion in the source code.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate bassert;
+    /// # fn main() {
+    /// let (x, y) = (1, 2);
+    /// let failure = try_bassert!(x > y).unwrap_err();
+    /// bassert!((failure.location().line()) == (line!() - 1));
+    /// bassert!((failure.location().file()) == (file!()));
+    /// # }
+    /// ```
+    pub fn location(&self) -> &'static Location<'static> {
+        self.inner.location
+    }
+
+    /// Writes the message `bassert!` panics with.
+    pub(crate) fn write_message(&self, f: &mut dyn fmt::Write, paint: Painter) -> fmt::Result {
+        match BassertConfig::current().effective_style() {
+            MessageStyle::Default => {
+                self.write_header(f, paint)?;
+                self.write_values(f, paint, "\n")?;
+                self.write_custom_message(f)?;
+            }
+            MessageStyle::ValuesFirst if !self.inner.values.is_empty() => {
+                self.write_values(f, paint, "")?;
+                f.write_str("\n")?;
+                self.write_header(f, paint)?;
+                self.write_custom_message(f)?;
+            }
+            MessageStyle::ValuesFirst => {
+                self.write_header(f, paint)?;
+                self.write_custom_message(f)?;
+            }
+            MessageStyle::Compact => return self.write_compact(f, paint),
+        }
+        self.write_details(f, paint)?;
+        if let Some(snippet) = &self.inner.snippet {
+            write!(f, "\nsource:\n{}", snippet)?;
+        }
+        if let Some(backtrace) = &self.inner.backtrace {
+            write!(f, "\nbacktrace:\n{}", backtrace)?;
+        }
+        Ok(())
+    }
+
+    /// The `assertion failed: ` the message starts with, including the label (if any).
+    fn write_title(&self, f: &mut dyn fmt::Write, paint: Painter) -> fmt::Result {
+        write!(f, "{}", paint.header())?;
+        if let Some(label) = self.inner.label {
+            write!(f, " [{}]", label)?;
+        }
+        write!(f, ": ")
+    }
+
+    /// The first line (with the notes below it) of the message.
+    fn write_header(&self, f: &mut dyn fmt::Write, paint: Painter) -> fmt::Result {
+        let inner = &self.inner;
+        self.write_title(f, paint)?;
+        self.write_expr(f, paint)?;
+        if let Some(thread) = &inner.thread {
+            write!(f, " (thread: {})", thread)?;
+        }
+        for (label, expr) in &inner.notes {
+            write!(f, "\n{}: `{}`", label, paint.expr(expr))?;
+        }
+        if inner.incomparable {
+            write!(f, "\nvalues are not comparable (partial_cmp returned None)")?;
+        }
+        Ok(())
+    }
+
+    /// The asserted expression, in words with [`BassertConfig::verbose_operators`] if it is a single comparison.
+    fn write_expr(&self, f: &mut dyn fmt::Write, paint: Painter) -> fmt::Result {
+        let inner = &self.inner;
+        if let (Some(kind), Some(rhs_expr), true) = (
+            inner.kind,
+            &inner.rhs_expr,
+            BassertConfig::current().verbose_operators,
+        ) {
+            // Chained comparisons are left alone, as their expression is not that of the broken link.
+            if inner.expr == format!("{} {} {}", inner.lhs_expr, kind, rhs_expr) {
+                let (lhs_expr, rhs_expr) = match kind {
+                    Kind::Match => (&**rhs_expr, &*inner.lhs_expr),
+                    _ => (&*inner.lhs_expr, &**rhs_expr),
+                };
+                return write!(
+                    f,
+                    "`{}` {} `{}`",
+                    paint.expr(lhs_expr),
+                    kind.failed_phrase(),
+                    paint.expr(rhs_expr)
+                );
+            }
+        }
+        write!(f, "`{}`", paint.expr(&inner.expr))
+    }
+
+    /// The lines listing the values, the first one preceded by `first_separator`.
+    fn write_values(
+        &self,
+        f: &mut dyn fmt::Write,
+        paint: Painter,
+        first_separator: &str,
+    ) -> fmt::Result {
+        // With `BassertConfig::align`, the values are padded to start in the same column.
+        let width = match BassertConfig::current().align {
+            true => self
+                .inner
+                .values
+                .iter()
+                .map(|value| value.expr.chars().count())
+                .max(),
+            false => None,
+        };
+        for (idx, value) in self.inner.values.iter().enumerate() {
+            let separator = if idx == 0 { first_separator } else { ",\n" };
+            let padding = width.map_or(0, |width| width - value.expr.chars().count());
+            write!(
+                f,
+                "{}{}:{:padding$} `{}`",
+                separator,
+                paint.expr(&value.expr),
+                "",
+                paint.value(&value.value),
+                padding = padding
+            )?;
+            if let Some(type_name) = value.type_name {
+                write!(f, "{}", type_name)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_custom_message(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        if let Some(message) = &self.inner.message {
+            write!(f, ": {}", message)?;
+        }
+        Ok(())
+    }
+
+    /// The message on a single line, as used by [`MessageStyle::Compact`].
+    fn write_compact(&self, f: &mut dyn fmt::Write, paint: Painter) -> fmt::Result {
+        let inner = &self.inner;
+        self.write_title(f, paint)?;
+        self.write_expr(f, paint)?;
+        if let Some(thread) = &inner.thread {
+            write!(f, " (thread: {})", thread)?;
+        }
+        let mut separator = " (";
+        for (label, expr) in &inner.notes {
+            write!(f, "{}{}: `{}`", separator, label, paint.expr(expr))?;
+            separator = ", ";
+        }
+        if inner.incomparable {
+            write!(f, "{}not comparable", separator)?;
+            separator = ", ";
+        }
+        for value in &inner.values {
+            write!(
+                f,
+                "{}{}: `{}`",
+                separator,
+                paint.expr(&value.expr),
+                paint.value(&value.value)
+            )?;
+            if let Some(type_name) = value.type_name {
+                write!(f, "{}", type_name)?;
+            }
+            separator = ", ";
+        }
+        if separator == ", " {
+            f.write_str(")")?;
+        }
+        self.write_custom_message(f)
+    }
+
+    /// The lines explaining the difference between the operands.
+    fn write_details(&self, f: &mut dyn fmt::Write, paint: Painter) -> fmt::Result {
+        let inner = &self.inner;
+        match &inner.difference {
+            // The difference to a `NaN` is `NaN` as well, which is already explained by the values not being comparable.
+            Some(Difference::Numeric(_)) if inner.incomparable => {}
+            Some(Difference::Numeric(difference)) => write!(f, "\ndifference: `{}`", difference)?,
+            Some(Difference::Slice { first, lengths }) => {
+                if let Some((idx, lhs, rhs)) = first {
+                    write!(
+                        f,
+                        "\nfirst difference at index {}: left = `{}`, right = `{}`",
+                        idx,
+                        paint.value(lhs),
+                        paint.value(rhs)
+                    )?;
+                }
+                if let Some((lhs_len, rhs_len)) = lengths {
+                    write!(
+                        f,
+                        "\nlengths differ: left = `{}`, right = `{}`",
+                        lhs_len, rhs_len
+                    )?;
+                }
+            }
+            Some(Difference::Map {
+                only_lhs,
+                only_rhs,
+                different_values,
+            }) => {
+                if let Some(keys) = only_lhs {
+                    write!(f, "\nkeys only in left: `{}`", paint.value(keys))?;
+                }
+                if let Some(keys) = only_rhs {
+                    write!(f, "\nkeys only in right: `{}`", paint.value(keys))?;
+                }
+                for (key, lhs, rhs) in different_values {
+                    write!(
+                        f,
+                        "\ndifferent value for key `{}`: left = `{}`, right = `{}`",
+                        paint.value(key),
+                        paint.value(lhs),
+                        paint.value(rhs)
+                    )?;
+                }
+            }
+            Some(Difference::Set { only_lhs, only_rhs }) => {
+                if let Some(elements) = only_lhs {
+                    write!(f, "\nelements only in left: `{}`", paint.value(elements))?;
+                }
+                if let Some(elements) = only_rhs {
+                    write!(f, "\nelements only in right: `{}`", paint.value(elements))?;
+                }
+            }
+            Some(Difference::Bytes {
+                offset,
+                lengths,
+                dump,
+            }) => {
+                if let Some((lhs_len, rhs_len)) = lengths {
+                    write!(
+                        f,
+                        "\nlengths differ: left = `{}`, right = `{}`",
+                        lhs_len, rhs_len
+                    )?;
+                }
+                write!(
+                    f,
+                    "\nfirst difference at offset {} ({:#x}):",
+                    offset, offset
+                )?;
+                write!(f, "\n{}", dump)?;
+            }
+            Some(Difference::Unsorted {
+                index,
+                lhs,
+                rhs,
+                incomparable,
+                strict,
+            }) => write!(
+                f,
+                "\nelement at index {} (`{}`) {} element at index {} (`{}`)",
+                index,
+                paint.value(lhs),
+                match (incomparable, strict) {
+                    (true, _) => "cannot be compared to",
+                    (false, true) => ">=",
+                    (false, false) => ">",
+                },
+                index + 1,
+                paint.value(rhs)
+            )?,
+            Some(Difference::Predicate {
+                pred_expr,
+                value_expr,
+                value,
+            }) => write!(
+                f,
+                "\npredicate `{}` failed for {}: `{}`",
+                pred_expr,
+                value_expr,
+                paint.value(value)
+            )?,
+            Some(Difference::Duplicate { indices, value }) => write!(
+                f,
+                "\nelement at index {} (`{}`) == element at index {} (`{}`)",
+                indices[0],
+                paint.value(value),
+                indices[1],
+                paint.value(value)
+            )?,
+            Some(Difference::NoneOperand {
+                none_expr,
+                some_expr,
+            }) => write!(
+                f,
+                "\n{} is `{}`, but {} is not",
+                none_expr,
+                paint.value("None"),
+                some_expr
+            )?,
+            Some(Difference::Expected { expected, actual }) => write!(
+                f,
+                "\nexpected `{}`, got `{}`",
+                paint.value(expected),
+                paint.value(actual)
+            )?,
+            Some(Difference::Bits { lhs, rhs }) => write!(
+                f,
+                "\nbits: left = `{}`, right = `{}`",
+                paint.value(lhs),
+                paint.value(rhs)
+            )?,
+            Some(Difference::Pointers { lhs, rhs }) => write!(
+                f,
+                "\naddresses: left = `{}`, right = `{}`",
+                paint.value(lhs),
+                paint.value(rhs)
+            )?,
+            Some(Difference::Ended {
+                len,
+                lhs_ended,
+                next,
+            }) => {
+                let (ended, continues) = if *lhs_ended {
+                    ("left", "right")
+                } else {
+                    ("right", "left")
+                };
+                write!(
+                    f,
+                    "\n{} ended after `{}` elements, but {} continues with `{}`",
+                    ended,
+                    len,
+                    continues,
+                    paint.value(next)
+                )?
+            }
+            Some(Difference::Error(error)) => {
+                write!(f, "\ndisplayed error: `{}`", paint.value(error))?
+            }
+            Some(Difference::Variant(variant)) => {
+                write!(f, "\nactual variant: `{}`", paint.value(variant))?
+            }
+            Some(Difference::Inconsistent {
+                lhs_expr,
+                lhs,
+                rhs_expr,
+                rhs,
+            }) => write!(
+                f,
+                "\n{} returned `{}`, but {} returned `{}`",
+                lhs_expr,
+                paint.value(lhs),
+                rhs_expr,
+                paint.value(rhs)
+            )?,
+            Some(Difference::Found(offset)) => write!(f, "\nfound at byte offset `{}`", offset)?,
+            Some(Difference::Len { expected, actual }) => match expected {
+                Some(expected) => write!(
+                    f,
+                    "\nexpected length `{}`, actual length `{}`",
+                    expected, actual
+                )?,
+                None => write!(
+                    f,
+                    "\nexpected a non-zero length, actual length `{}`",
+                    actual
+                )?,
+            },
+            Some(Difference::Panic { expected, message }) => {
+                match expected {
+                    Some(expected) => write!(
+                        f,
+                        "\nexpected a panic containing `{}`",
+                        paint.value(expected)
+                    )?,
+                    None if message.is_some() => write!(f, "\nexpected no panic")?,
+                    None => write!(f, "\nexpected a panic")?,
+                }
+                match message {
+                    Some(message) => {
+                        write!(f, ", but it panicked with: `{}`", paint.value(message))?
+                    }
+                    None => write!(f, ", but it did not panic")?,
+                }
+            }
+            Some(Difference::Str {
+                line,
+                column,
+                before,
+                lhs_after,
+                rhs_after,
+            }) => {
+                write!(f, "\nmismatch at line {}, column {}:", line, column)?;
+                write!(f, "\nleft:  `{}{}`", before, paint.value(lhs_after))?;
+                write!(f, "\nright: `{}{}`", before, paint.value(rhs_after))?;
+                let caret = "right: `".len() + before.chars().count();
+                write!(f, "\n{:caret$}^", "", caret = caret)?;
+            }
+            None => {}
+        }
+        if let Some(diff) = &inner.diff {
+            write!(
+                f,
+                "\ndiff (- {}, + {}):\n{}",
+                inner.lhs_expr,
+                inner.rhs_expr.as_deref().unwrap_or_default(),
+                diff
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Like colors and type names, backtraces are left out of the crate's own unit tests,
+// which match on the default messages.
+#[cfg(all(feature = "backtrace", not(test)))]
+fn capture_backtrace() -> Option<String> {
+    use std::backtrace::{Backtrace, BacktraceStatus};
+
+    // Capturing is cheap when backtraces are disabled, which is checked only once.
+    let backtrace = Backtrace::capture();
+    match backtrace.status() {
+        BacktraceStatus::Captured => Some(backtrace.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(not(all(feature = "backtrace", not(test))))]
+fn capture_backtrace() -> Option<String> {
+    None
+}
+
+/// Reads the line of the assertion from its source file (relative to the current directory, like when running `cargo test`),
+/// which is skipped when it cannot be read.
+#[cfg(all(feature = "source-snippet", not(test)))]
+fn capture_snippet(location: &Location<'_>) -> Option<String> {
+    let source = std::fs::read_to_string(location.file()).ok()?;
+    let line = source
+        .lines()
+        .nth(location.line().checked_sub(1)? as usize)?;
+    Some(format_snippet(line, location.line(), location.column()))
+}
+
+#[cfg(not(all(feature = "source-snippet", not(test))))]
+fn capture_snippet(_location: &Location<'_>) -> Option<String> {
+    None
+}
+
+/// The line of source code prefixed by its number, with a caret under the (1-based) column below it.
+#[cfg(all(feature = "source-snippet", not(test)))]
+fn format_snippet(line: &str, line_number: u32, column: u32) -> String {
+    let gutter = line_number.to_string().len();
+    // Tabs are kept, so the caret lines up with the line above it.
+    let indent: String = line
+        .chars()
+        .take(column.saturating_sub(1) as usize)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    format!(
+        "{:>gutter$} | {}\n{:gutter$} | {}^",
+        line_number,
+        line.trim_end(),
+        "",
+        indent,
+        gutter = gutter
+    )
+}
+
+#[cfg(all(feature = "thread-info", not(test)))]
+fn current_thread() -> Option<String> {
+    let thread = std::thread::current();
+    Some(match thread.name() {
+        Some(name) => name.to_string(),
+        None => format!("{:?}", thread.id()),
+    })
+}
+
+#[cfg(not(all(feature = "thread-info", not(test))))]
+fn current_thread() -> Option<String> {
+    None
+}
+
+impl fmt::Display for BassertFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_message(f, Painter::plain())?;
+        let separator = match BassertConfig::current().effective_style() {
+            MessageStyle::Compact => " ",
+            _ => "\n",
+        };
+        write!(f, "{}at {}", separator, self.inner.location)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BassertFailure {}
+
+/// The message [`bassert!`](crate::bassert!) panics with when `lhs_expr <kind> rhs_expr` fails
+/// for the operands `lhs` and `rhs`, to check the messages of (wrappers around) assertions in tests.
+///
+/// The lines explaining the difference between the operands (like ``difference: `-1` `` for numbers) are not included,
+/// and neither are their types (even with the `type-names` feature),
+/// so the message of the assertion starts with this one (and is equal to it when there is nothing to explain):
+/// ```
+/// use bassert::{expected_message, Kind};
+///
+/// let (x, y) = (1, 2);
+/// let payload = std::panic::catch_unwind(|| bassert::bassert!(x == y)).unwrap_err();
+/// let message = payload.downcast_ref::<String>().unwrap();
+/// # #[cfg(not(any(feature = "type-names", feature = "thread-info")))]
+/// assert!(message.starts_with(&expected_message(Kind::Eq, "x", "y", &1, &2)));
+/// ```
+pub fn expected_message(
+    kind: Kind,
+    lhs_expr: &'static str,
+    rhs_expr: &'static str,
+    lhs: &dyn fmt::Debug,
+    rhs: &dyn fmt::Debug,
+) -> String {
+    let mut message = String::new();
+    // Writing to a `String` cannot fail.
+    let _ = crate::internal::write_failure(&mut message, kind, lhs_expr, rhs_expr, lhs, rhs, None);
+    message
+}
+
+/// Panics with `message`, or prints it to stderr (followed by the location of the caller)
+/// and aborts the process with the `abort-on-failure` feature.
+#[track_caller]
+pub(crate) fn fail(message: &dyn fmt::Display) -> ! {
+    #[cfg(feature = "abort-on-failure")]
+    {
+        std::eprintln!("{}\nat {}", message, Location::caller());
+        std::process::abort()
+    }
+    #[cfg(not(feature = "abort-on-failure"))]
+    panic!("{}", message)
+}
+
+/// Displays the message of a failure as it is printed to stderr when panicking,
+/// which is colored if enabled.
+pub(crate) struct PanicMessage<'a>(pub(crate) &'a BassertFailure);
+
+impl fmt::Display for PanicMessage<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write_message(f, Painter::stderr())
+    }
+}
@@ -0,0 +1,195 @@
+//! Collecting the failures of multiple assertions.
+use crate::failure::PanicMessage;
+use crate::BassertFailure;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// Collects the failures of multiple assertions,
+/// so they can all be reported at once instead of stopping at the first one.
+///
+/// Assertions are recorded using [`soft_bassert!`](crate::soft_bassert!)
+/// (or by passing the result of [`try_bassert!`](crate::try_bassert!) to [`BassertGroup::check`]),
+/// after which [`BassertGroup::assert_all`] panics if any of them failed
+/// (or [`BassertGroup::assert_any`] if all of them did), listing every failure.
+///
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// # fn main() {
+/// let (x, y) = (1, 2);
+/// let mut group = bassert::BassertGroup::new();
+/// soft_bassert!(group, x > y);
+/// soft_bassert!(group, x == y, "still checked after the first failure");
+/// group.assert_all();
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BassertGroup {
+    checked: usize,
+    failures: Vec<BassertFailure>,
+}
+
+impl BassertGroup {
+    /// Creates an empty group.
+    pub fn new() -> Self {
+        BassertGroup::default()
+    }
+
+    /// Records the result of an assertion, like the one returned by [`try_bassert!`](crate::try_bassert!).
+    pub fn check(&mut self, result: Result<(), BassertFailure>) {
+        self.checked += 1;
+        if let Err(failure) = result {
+            self.failures.push(failure);
+        }
+    }
+
+    /// The failures recorded so far, in the order they were recorded.
+    pub fn failures(&self) -> &[BassertFailure] {
+        &self.failures
+    }
+
+    /// Panics if any of the recorded assertions failed, with a message listing all of their failures.
+    #[track_caller]
+    pub fn assert_all(self) {
+        if self.failures.is_empty() {
+            return;
+        }
+
+        let header = format!(
+            "{} of {} assertions failed",
+            self.failures.len(),
+            self.checked
+        );
+        self.fail(header)
+    }
+
+    /// Panics if none of the recorded assertions held (or none were recorded at all),
+    /// with a message listing all of their failures.
+    #[track_caller]
+    pub fn assert_any(self) {
+        if self.failures.len() < self.checked {
+            return;
+        }
+
+        let header = format!("none of {} assertions held", self.checked);
+        self.fail(header)
+    }
+
+    #[track_caller]
+    fn fail(self, mut message: String) -> ! {
+        for (idx, failure) in self.failures.iter().enumerate() {
+            // Writing to a `String` cannot fail.
+            let _ = write!(message, "\n\n{}. {}", idx + 1, PanicMessage(failure));
+        }
+        crate::failure::fail(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_all_passes_without_failures() {
+        let mut group = BassertGroup::new();
+        let one = 1;
+        soft_bassert!(group, one < 2);
+        soft_bassert!(group, (one + 1) == 2, "math works");
+        bassert!((group.failures().len()) == 0);
+        group.assert_all();
+    }
+
+    #[test]
+    fn failures_are_recorded_in_order() {
+        let (x, y) = (1, 2);
+        let mut group = BassertGroup::new();
+        soft_bassert!(group, x > y);
+        soft_bassert!(group, x < y);
+        group.check(try_bassert!(x == y));
+        check_positive(&mut group, -1);
+
+        let kinds: Vec<_> = group
+            .failures()
+            .iter()
+            .map(|failure| failure.kind())
+            .collect();
+        bassert!(
+            kinds
+                == [
+                    Some(crate::Kind::Gt),
+                    Some(crate::Kind::Eq),
+                    Some(crate::Kind::Gt)
+                ]
+        );
+    }
+
+    fn check_positive(group: &mut BassertGroup, x: i32) {
+        soft_bassert!(group, x > 0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "2 of 3 assertions failed\n\n1. assertion failed: `x > y`\nx: `1`,\ny: `2`\ndifference: `-1`\n\n2. assertion failed: `x == y`\nx: `1`,\ny: `2`: some message\ndifference: `-1`"
+    )]
+    fn assert_all_lists_every_failure() {
+        let (x, y) = (1, 2);
+        let mut group = BassertGroup::new();
+        soft_bassert!(group, x > y);
+        soft_bassert!(group, x < y);
+        soft_bassert!(group, x == y, "some {}", "message");
+        group.assert_all();
+    }
+
+    #[test]
+    #[should_panic(expected = "none of 2 assertions held\n\n1. assertion failed: `x > y`")]
+    fn assert_any_panics_if_all_failed() {
+        let (x, y) = (1, 2);
+        let mut group = BassertGroup::new();
+        soft_bassert!(group, x > y);
+        soft_bassert!(group, x == y);
+        group.assert_any();
+    }
+
+    #[test]
+    #[should_panic(expected = "none of 0 assertions held")]
+    fn assert_any_panics_without_assertions() {
+        BassertGroup::new().assert_any();
+    }
+
+    #[test]
+    fn bassert_all_passes_if_all_hold() {
+        let (x, y, z) = (1, 2, Some(3));
+        bassert_all!(x < y, x != y, Some(_) = z, x in 0..10, 0 < x < y);
+        bassert_all!(x < y,);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "2 of 3 assertions failed\n\n1. assertion failed: `x == y`\nx: `1`,\ny: `2`\ndifference: `-1`\n\n2. assertion failed: `Some(_) = z`\nz: `None`"
+    )]
+    fn bassert_all_lists_every_failure() {
+        let (x, y, z) = (1, 2, None::<i32>);
+        bassert_all!(x == y, x < y, Some(_) = z);
+    }
+
+    #[test]
+    fn bassert_any_stops_at_the_first_assertion_which_holds() {
+        let mut calls = 0;
+        let mut next = || {
+            calls += 1;
+            calls
+        };
+        bassert_any!(next() == 5, next() == 2, next() == 3);
+        bassert!(calls == 2);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "none of 2 assertions held\n\n1. assertion failed: `status == 200`\nstatus: `503`,\n200: `200`\ndifference: `303`\n\n2. assertion failed: `status == 204`"
+    )]
+    fn bassert_any_lists_every_failure() {
+        let status = 503;
+        bassert_any!(status == 200, status == 204);
+    }
+}
@@ -0,0 +1,49 @@
+//! The global hook run when an assertion fails.
+use crate::BassertFailure;
+use alloc::boxed::Box;
+use std::sync::{PoisonError, RwLock};
+
+/// A function run with every failure of a panicking assertion, see [`set_failure_hook`].
+pub type FailureHook = Box<dyn Fn(&BassertFailure) + Send + Sync>;
+
+static HOOK: RwLock<Option<FailureHook>> = RwLock::new(None);
+
+/// Registers a function which is called with every failure of [`bassert!`](crate::bassert!) (and the other panicking macros),
+/// right before panicking.
+///
+/// This is useful to flush logs, increment a metric or write a crash file.
+/// Unlike a hook registered using [`std::panic::set_hook`], it gets the structured [`BassertFailure`]
+/// instead of a formatted message, and is not run for other panics.
+/// It replaces any previously registered hook.
+///
+/// Requires the `std` feature.
+/// ```
+/// bassert::set_failure_hook(Box::new(|failure| {
+///     eprintln!("[invariant broken] {}", failure);
+/// }));
+/// ```
+///
+/// When running tests in the browser (like with `wasm-bindgen-test`), where panic messages can get lost,
+/// a hook can print the failures to the developer tools' console using the `web-sys` crate
+/// (with its `console` feature enabled), while the normal panic still fails the test:
+/// ```ignore
+/// bassert::set_failure_hook(Box::new(|failure| {
+///     web_sys::console::error_1(&failure.to_string().into());
+/// }));
+/// ```
+pub fn set_failure_hook(hook: FailureHook) {
+    *HOOK.write().unwrap_or_else(PoisonError::into_inner) = Some(hook);
+}
+
+/// Unregisters the hook registered using [`set_failure_hook`], returning it (if any).
+///
+/// Requires the `std` feature.
+pub fn take_failure_hook() -> Option<FailureHook> {
+    HOOK.write().unwrap_or_else(PoisonError::into_inner).take()
+}
+
+pub(crate) fn run(failure: &BassertFailure) {
+    if let Some(hook) = &*HOOK.read().unwrap_or_else(PoisonError::into_inner) {
+        hook(failure);
+    }
+}
@@ -0,0 +1,122 @@
+//! Printing failures as a single line of JSON, for tools parsing the output of CI runs.
+use crate::BassertFailure;
+use core::fmt::{self, Write};
+use std::sync::OnceLock;
+
+/// Whether failures are printed as JSON: with the `json` feature, or when `BASSERT_JSON=1` is set.
+fn enabled() -> bool {
+    static ENV_JSON: OnceLock<bool> = OnceLock::new();
+
+    cfg!(feature = "json")
+        || *ENV_JSON.get_or_init(|| std::env::var_os("BASSERT_JSON").is_some_and(|val| val == "1"))
+}
+
+/// Prints `failure` to stderr as a JSON object on a single line, if enabled.
+pub(crate) fn print(failure: &BassertFailure) {
+    if enabled() {
+        let mut line = alloc::string::String::new();
+        let _ = write(&mut line, failure);
+        std::eprintln!("{}", line);
+    }
+}
+
+/// Writes the JSON object describing `failure`.
+///
+/// The `message` field is left out when the assertion has no custom message,
+/// whereas the other fields are `null` when they do not apply.
+fn write(f: &mut dyn Write, failure: &BassertFailure) -> fmt::Result {
+    let location = failure.location();
+    f.write_str("{\"kind\":")?;
+    write_opt(f, failure.kind().map(|kind| kind.op_str()))?;
+    f.write_str(",\"lhs_expr\":")?;
+    write_str(f, failure.lhs_expr())?;
+    f.write_str(",\"rhs_expr\":")?;
+    write_opt(f, failure.rhs_expr())?;
+    f.write_str(",\"lhs\":")?;
+    write_opt(f, failure.lhs())?;
+    f.write_str(",\"rhs\":")?;
+    write_opt(f, failure.rhs())?;
+    f.write_str(",\"file\":")?;
+    write_str(f, location.file())?;
+    write!(
+        f,
+        ",\"line\":{},\"column\":{}",
+        location.line(),
+        location.column()
+    )?;
+    if let Some(message) = failure.message() {
+        f.write_str(",\"message\":")?;
+        write_str(f, message)?;
+    }
+    f.write_char('}')
+}
+
+fn write_opt(f: &mut dyn Write, value: Option<&str>) -> fmt::Result {
+    match value {
+        Some(value) => write_str(f, value),
+        None => f.write_str("null"),
+    }
+}
+
+/// Writes `value` as a JSON string, escaping quotes, backslashes and control characters.
+fn write_str(f: &mut dyn Write, value: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in value.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write;
+    use alloc::string::String;
+
+    fn json(failure: &crate::BassertFailure) -> String {
+        let mut json = String::new();
+        write(&mut json, failure).unwrap();
+        json
+    }
+
+    #[test]
+    fn comparisons_print_all_fields() {
+        let (x, y) = (1, 2);
+        let failure = try_bassert!(x == y, "x is {}", x).unwrap_err();
+        let line = line!() - 1;
+        bassert!(
+            json(&failure)
+                == format!(
+                    r#"{{"kind":"==","lhs_expr":"x","rhs_expr":"y","lhs":"1","rhs":"2","file":"{}","line":{},"column":{},"message":"x is 1"}}"#,
+                    file!(),
+                    line,
+                    failure.location().column()
+                )
+        );
+    }
+
+    #[test]
+    fn missing_fields_are_null_and_strings_are_escaped() {
+        let name = "a \"quoted\"\\\n\u{1}name";
+        let failure = try_bassert!(name.is_empty()).unwrap_err();
+        bassert!(
+            json(&failure)
+                == format!(
+                    r#"{{"kind":null,"lhs_expr":"name.is_empty()","rhs_expr":null,"lhs":null,"rhs":null,"file":"{}","line":{},"column":{}}}"#,
+                    failure.location().file(),
+                    failure.location().line(),
+                    failure.location().column()
+                )
+        );
+
+        let failure = try_bassert!(name.is_empty(), "{}", name).unwrap_err();
+        bassert!((json(&failure)).ends_with(r#","message":"a \"quoted\"\\\n\u0001name"}"#));
+    }
+}
@@ -0,0 +1,100 @@
+//! The publicly exposed kinds of assertions.
+use core::fmt;
+
+/// The kind of comparison performed by an assertion.
+///
+/// This is exposed so that other assertion macros built on top of this crate
+/// can describe their assertions in the same way [`bassert!`](crate::bassert!) does.
+///
+/// Its [`Display`](fmt::Display) implementation prints its [`op_str`](Kind::op_str),
+/// the operator as it is written in the assertion.
+/// ```
+/// bassert::bassert!((bassert::Kind::Lte.to_string()) == "<=");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Kind {
+    /// `lhs == rhs`
+    Eq,
+    /// `lhs != rhs`
+    Ne,
+    /// `lhs > rhs`
+    Gt,
+    /// `lhs < rhs`
+    Lt,
+    /// `lhs >= rhs`
+    Gte,
+    /// `lhs <= rhs`
+    Lte,
+    /// `pattern = rhs`
+    Match,
+    /// `lhs ~= rhs`
+    Approx,
+}
+
+impl Kind {
+    /// The operator used to write this kind of assertion.
+    ///
+    /// ```
+    /// bassert::bassert!((bassert::Kind::Match.op_str()) == "=");
+    /// ```
+    pub fn op_str(&self) -> &'static str {
+        match self {
+            Kind::Eq => "==",
+            Kind::Ne => "!=",
+            Kind::Gt => ">",
+            Kind::Lt => "<",
+            Kind::Gte => ">=",
+            Kind::Lte => "<=",
+            Kind::Match => "=",
+            Kind::Approx => "~=",
+        }
+    }
+
+    /// How a failure of this kind of assertion is phrased in words, as used by [`BassertConfig::verbose_operators`](crate::BassertConfig::verbose_operators).
+    ///
+    /// The phrase goes between the left-hand and right-hand side, except for [`Kind::Match`] where it goes between the value and the pattern.
+    /// ```
+    /// bassert::bassert!((bassert::Kind::Gt.failed_phrase()) == "is not greater than");
+    /// ```
+    pub fn failed_phrase(&self) -> &'static str {
+        match self {
+            Kind::Eq => "is not equal to",
+            Kind::Ne => "is equal to",
+            Kind::Gt => "is not greater than",
+            Kind::Lt => "is not less than",
+            Kind::Gte => "is not greater than or equal to",
+            Kind::Lte => "is not less than or equal to",
+            Kind::Match => "does not match",
+            Kind::Approx => "is not approximately equal to",
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.op_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Kind;
+
+    #[test]
+    fn display_prints_op_str() {
+        let kinds = [
+            Kind::Eq,
+            Kind::Ne,
+            Kind::Gt,
+            Kind::Lt,
+            Kind::Gte,
+            Kind::Lte,
+            Kind::Match,
+            Kind::Approx,
+        ];
+        for kind in kinds {
+            bassert!((kind.to_string()) == (kind.op_str()));
+        }
+    }
+}
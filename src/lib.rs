@@ -1,3 +1,4 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 /// A 'better assert' which asserts that a boolean expression is `true` at runtime, and prints the values of the operands.
 ///
 /// The basic usage of this macro is similar to [`std::assert!`].
@@ -16,19 +17,54 @@
 /// - `>=` (greater than or equals)
 /// - `<` (less than)
 /// - `<=` (less than or equals)
-/// - `=` (match)
+/// - `~=` (approximately equals, for floating-point numbers. An explicit tolerance can be passed as `bassert!(a ~= b, eps = 1e-9)`,
+///   or a tolerance relative to the magnitude of the operands as `bassert!(a ~= b, rel = 1e-6)`)
+/// - `in` (range membership, like `x in 0..10` or `x in 0..=10`)
+/// - `=` (match, optionally with a guard like `Some(x) if *x > 5 = val`)
+/// - `&&` (and), where each side is checked as a separate assertion, so the failure message will tell you which side failed.
+///
+/// Chained comparisons (like `bassert!(0 <= idx < len)`) are supported as long as both operators point in the same direction.
+/// Each operand is evaluated only once, and the failure message tells you which link of the chain was broken.
+///
+/// Any other boolean expression (like `bassert!(x.is_valid())`) is accepted as well,
+/// in which case `bassert!` behaves just like [`std::assert!`].
+/// Negated expressions (like `bassert!(!list.is_empty())`) will additionally print the value of the inner expression.
 ///
 /// In all of these cases, if the assertion fails, the panic message will contain:
 ///  - the passed expression
 ///  - the actual value of the left-hand-side and right-hand-side operands to the operator.
 ///  - If a custom format string (and optional extra arguments) were passed, these are printed as well.
+///  - If both operands of `==`, `!=`, `<`, `<=`, `>` or `>=` are (primitive) numbers of the same type, their difference.
+///  - If both operands are slices, arrays or `Vec`s with the same type of elements, the index and values of the first differing elements,
+///    and the lengths of the operands if these differ.
+///  - If both operands are strings, the line and column of the first differing character, with the text around it.
+///    This also applies to references to these operands, and to smart pointers like `Box`, `Rc`, `Arc` and `Cow` wrapping them.
+///  - If the operands of `<`, `<=`, `>` or `>=` cannot be ordered at all (like when one of them is `NaN`), a line saying so.
+///  - If a `Result` did not match a pattern (like `Ok(_)`) because it is an `Err` whose error implements [`Display`](core::fmt::Display),
+///    the error using its `Display` representation, which is often more readable than its `Debug` one.
+///  - If a value did not match a pattern naming other enum variants (like `Shape::Circle { .. }`), the variant it turned out to be,
+///    like ``actual variant: `Square` ``. This is guessed from its `Debug` representation, so it only works for types deriving `Debug`.
 ///
 ///  ## Requirements
 ///
+///  - Rust 1.82 or newer (or 1.88 with the `power` feature).
 ///  - The left-hand-side and right-hand-side operands both need to implement the [`std::fmt::Debug`] trait.
 ///  - The particular traits required to evaluate the expression under consideration needs to be implemented. E.g. [`PartialEq`] or [`PartialOrd`].
-///  - If complex expressions are used as one (or both) of the operands, extra parentheses are required. This is a good idea for legibility,
-///    but also a requirement because of how the macro is written. If you forget, the compiler will remind you with a compiler error.
+///  - Operands can be arbitrary expressions, like in `bassert!(a + 1 == b * 2)`.
+///    Because the macro looks at the expression one token at a time, very long expressions may hit the compiler's
+///    recursion limit (which can be raised using `#![recursion_limit = "256"]`), or can be split up using parentheses.
+///  - Every operand is evaluated exactly once, whether the assertion holds or not,
+///    so operands with side effects (like `bassert!(iter.next() == Some(1))`) behave like they would in a normal `if`.
+///    The right-hand side of `&&` is only evaluated when the left-hand side holds.
+///  - Comparing two literals (like `bassert!(5 > 3)`) causes a `deprecated` warning, as such an assertion always holds (or always fails) and so tests nothing.
+///    Where this is intended, the warning can be silenced using `#[allow(deprecated)]`.
+///    ```compile_fail
+///    #![deny(deprecated)]
+///    # #[macro_use] extern crate bassert;
+///    # fn main() {
+///    bassert!(5 > 3);
+///    # }
+///    ```
 ///
 /// # Examples
 /// This will happily pass:
@@ -78,10 +114,149 @@
 /// (x + 2): `12`: to surprise of no-one, x is not larger than x plus two. some extra argument
 /// ```
 ///
+/// When building the message takes more than a format string, pass a closure (without arguments) instead.
+/// It is only called if the assertion fails, and may return anything implementing [`Display`](core::fmt::Display):
+///
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// # fn main() {
+/// let ids = [3, 1, 2];
+/// bassert!(ids[0] < ids[1], || ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", "))
+/// # }
+/// ```
+///
+/// To say what is being checked, the assertion can be preceded by a label (a string literal) and a `;`.
+/// Unlike a custom message, it is printed in the first line of the message:
+///
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// # fn main() {
+/// let age = 200;
+/// bassert!("user age bounds"; age < 150)
+/// # }
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed [user age bounds]: `age < 150`
+/// age: `200`,
+/// 150: `150`
+/// difference: `50`
+/// ```
+/// Both can be combined, like `bassert!("user age bounds"; age < 150, "for user {}", id)`.
+/// Labels are supported by [`try_bassert!`] as well, but not by [`bassert_val!`].
+/// Anything other than a literal is rejected:
+/// ```compile_fail
+/// # #[macro_use] extern crate bassert;
+/// # fn main() {
+/// let (label, age) = ("user age bounds", 200);
+/// bassert!(label; age < 150)
+/// # }
+/// ```
+///
+/// To get the failure returned as a [`BassertFailure`] instead of panicking, use [`try_bassert!`].
+///
+/// ## Printing operands
+/// Operands are printed using their [`Debug`](core::fmt::Debug) implementation.
+/// For comparisons (including chained ones), this is only required to print them:
+/// operands whose type does not implement `Debug` are printed as `<does not implement Debug>`,
+/// so asserting on them in generic code does not need a `Debug` bound.
+/// This is decided at compile time, so without a `Debug` bound, generic code prints the placeholder even for types implementing `Debug`.
+/// The operands of other assertions (like `=`, `in` and `~=`) do need to implement `Debug`.
+///
+/// For types with a more readable [`Display`](core::fmt::Display) implementation (like errors),
+/// start the comparison with `#display` to print both operands using `Display` instead:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let error = "1.5".parse::<i32>().unwrap_err();
+/// let expected = "".parse::<i32>().unwrap_err();
+/// bassert!(#display error == expected);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `error == expected`
+/// error: `invalid digit found in string`,
+/// expected: `cannot parse integer from empty string`
+/// ```
+/// This works for the comparison operators `==`, `!=`, `<`, `<=`, `>` and `>=`,
+/// with operands which are single tokens or parenthesized expressions.
+///
+/// ## Configuration
+/// Operands are formatted using `{:?}` by default. For nested data, `{:#?}` is often easier to read;
+/// this can be enabled for all assertions using [`BassertConfig::pretty`]:
+/// ```
+/// # #[cfg(feature = "std")]
+/// bassert::BassertConfig::new().pretty(true).install();
+/// ```
+///
+/// Operands whose formatted value is longer than [`DEFAULT_MAX_LEN`] (4096) characters are truncated.
+/// This limit can be changed using [`BassertConfig::max_len`] or the `BASSERT_MAX_LEN` environment variable.
+///
+/// Likewise, colors and type names can be turned on or off regardless of the `color` and `type-names` features
+/// (see below) using [`BassertConfig::color`] and [`BassertConfig::show_types`].
+///
+/// Control characters in the formatted operands (like the carriage returns or escape codes of binary-ish data)
+/// are printed as they are, unless they are escaped (like `\r` or `\u{1b}`) using [`BassertConfig::escape_control`].
+///
+/// To make values of expressions differing in length easier to compare, they can be lined up in a column using [`BassertConfig::align`].
+///
+/// For readers less used to the symbolic operators, like in teaching contexts,
+/// failed comparisons can be described in words (like `` `x` is not greater than `y` ``) using [`BassertConfig::verbose_operators`].
+///
+/// The layout of the messages can be changed using [`BassertConfig::style`] or the `BASSERT_STYLE` environment variable,
+/// for example to put everything on a single line using [`MessageStyle::Compact`]:
+/// ```text
+/// assertion failed: `x > (x + 2)` (x: `10`, (x + 2): `12`)
+/// ```
+///
+/// # Cargo features
+///
+/// The following optional features can be enabled:
+/// - `diff`: When an `==` assertion fails and the operands' pretty-printed (`{:#?}`) representations span multiple lines,
+///   a line-by-line diff of these is printed as well, with `-` marking lines only in the left-hand side and `+` marking lines only in the right-hand side.
+/// - `color`: Color the failure messages using ANSI escape codes when stderr is a terminal.
+///   Setting the `NO_COLOR` environment variable (or `CLICOLOR=0`) disables this, and setting `CLICOLOR_FORCE` enables it even when not writing to a terminal.
+/// - `type-names`: Print the type of each operand after its value, like `` x: `10` (i32) ``.
+/// - `backtrace`: Capture a backtrace when an assertion fails, and print it at the end of the message.
+///   Like for panics, this only happens when the `RUST_BACKTRACE` (or `RUST_LIB_BACKTRACE`) environment variable is set.
+/// - `thread-info`: Append the name of the thread the assertion failed on (or its id, if it is unnamed) to the first line of the message,
+///   like `` assertion failed: `x == y` (thread: worker-3) ``.
+/// - `json`: Before panicking, print the failure to stderr as a JSON object on a single line, for tools parsing the output of CI runs:
+///   ```text
+///   {"kind":"==","lhs_expr":"x","rhs_expr":"y","lhs":"1","rhs":"2","file":"src/lib.rs","line":12,"column":5,"message":"x is 1"}
+///   ```
+///   Fields which do not apply (like the `rhs` of a plain boolean assertion) are `null`, and `message` is only present for assertions with a custom message.
+///   Setting the `BASSERT_JSON` environment variable to `1` does the same without the feature (with the default `std` feature).
+/// - `abort-on-failure`: Instead of panicking, print the message to stderr (followed by the location of the assertion, like `at src/main.rs:4:5`)
+///   and abort the process using [`std::process::abort`], so a failed assertion never unwinds (which is undefined behavior across `extern "C"` functions).
+///   This also applies to [`BassertGroup::assert_all`] and [`BassertGroup::assert_any`].
+///   Note that this makes failures impossible to catch, so tests using `#[should_panic]` or [`bassert_panics!`] on failing assertions abort as well.
+/// - `source-snippet`: Print the line of source code containing the failed assertion after the message, with a caret under its column:
+///   ```text
+///   source:
+///   12 |     bassert!(x == y);
+///      |     ^
+///   ```
+///   The line is read from the file at the location of the assertion (relative to the current directory), so it is left out when that file is not available.
+/// - `power`: Provide [`power_bassert!`], which prints the value of every sub-expression of the assertion, like `user.age`, `bonus` and `user.age + bonus` in `power_bassert!(user.age + bonus > limit)`.
+///
+/// The `std` feature is enabled by default. Disabling it makes the crate `no_std`, only requiring `core` and `alloc`.
+/// Without it, no [`BassertConfig`] can be installed (so the default one is always used), `BassertFailure` does not implement `std::error::Error`,
+/// no failure hook can be set, [`bassert_set_eq!`], [`bassert_subset!`], [`bassert_superset!`], [`bassert_unique!`], [`bassert_panics!`] and [`bassert_no_panic!`] are not available,
+/// and enabling the `color`, `backtrace`, `thread-info`, `json`, `abort-on-failure` or `source-snippet` feature enables `std` as well.
+///
+/// The `assertions` feature is enabled by default as well. Disabling it turns every [`bassert!`] into a no-op,
+/// which also applies to [`debug_bassert!`], [`release_bassert!`] and [`optional_bassert!`] as these expand to [`bassert!`].
+/// The asserted expressions are still type-checked (so they do not cause warnings about unused variables), but never evaluated.
+/// Other macros (like [`try_bassert!`], which returns the failure instead of panicking, and [`bassert_matches!`]) are not affected.
+/// [`bassert_val!`] still evaluates and returns the left-hand side, but does not check the comparison.
+/// Because Cargo unifies features, the assertions are only disabled if no crate in the build enables this feature,
+/// including through the default features.
+///
 /// # A note on using `=`
 ///
 /// The `=` operator cannot do _everything_ that is possible with [`std::assert_matches::assert_matches!`].
 /// It allows exactly those cases that work in a normal `let` or `if let`.
+/// To keep the argument order of `assert_matches!` (the value first), use [`bassert_matches!`] instead.
 ///
 /// Simplified, `bassert!(Some(_) = y)` expands to:
 /// ```ignore
@@ -92,509 +267,7366 @@
 /// }
 ///
 /// ```
+///
+/// OR-patterns like `bassert!(Some(1) | Some(2) = y)` work as well, and are printed in full when the assertion fails.
+/// So do range patterns, `@` bindings and nested patterns, like `bassert!(Some(n @ 1..=5) = y)` or `bassert!('a'..='z' = c)`.
+///
+/// Like in a `match` arm, the pattern can be followed by a guard: `bassert!(Some(x) if *x > 5 = y)`.
+/// Because `y` is matched by reference, the bindings used in the guard are references as well.
+/// When such an assertion fails, the message tells whether the pattern did not match or the guard failed.
+/// If the guard failed on a pattern like `Some(x)`, `Some(x @ 1..=5)` or `Point { x, y }`, the values of its bindings are printed too.
+#[cfg(feature = "assertions")]
 #[macro_export]
 macro_rules! bassert {
-    ($lhs:tt > $rhs:tt $(,)?) => {
-        $crate::bassert_internal!(
-            $crate::internal::BassertKind::Gt,
-            lhs > rhs,
-            $lhs,
-            $rhs,
-            lhs,
-            rhs
-        )
+    ($($tokens:tt)+) => {
+        $crate::bassert_internal!(@parse [panic] $($tokens)+)
     };
+}
 
-    ($lhs:tt > $rhs:tt, $($arg:tt)+) => {
-        $crate::bassert_internal!(
-            $crate::internal::BassertKind::Gt,
-            lhs > rhs,
-            $lhs,
-            $rhs,
-            lhs,
-            rhs,
-            $($arg)+
-        )
+// Without the `assertions` feature, the assertion is still type-checked, but never evaluated.
+#[cfg(not(feature = "assertions"))]
+#[macro_export]
+macro_rules! bassert {
+    ($($tokens:tt)+) => {
+        if false {
+            $crate::bassert_internal!(@parse [panic] $($tokens)+)
+        }
     };
+}
 
-    ($lhs:tt < $rhs:tt $(,)?) => {
-        $crate::bassert_internal!(
-            $crate::internal::BassertKind::Lt,
-            lhs < rhs,
-            $lhs,
-            $rhs,
-            lhs,
-            rhs
-        )
+/// A version of [`bassert!`] which returns the failure instead of panicking.
+///
+/// It accepts exactly the same assertions as [`bassert!`],
+/// but evaluates to a `Result<(), BassertFailure>`:
+/// `Ok(())` when the assertion holds, and an `Err` with a [`BassertFailure`] describing what went wrong otherwise.
+/// This is useful in places where unwinding is not desired,
+/// like a REPL or a loop validating many inputs.
+///
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// use bassert::BassertFailure;
+///
+/// fn check(x: i32, y: i32) -> Result<(), BassertFailure> {
+///     try_bassert!(x < y)?;
+///     try_bassert!(x != 0, "x should not be zero")?;
+///     Ok(())
+/// }
+///
+/// # fn main() {
+/// assert!(check(1, 2).is_ok());
+/// assert!(check(2, 1).is_err());
+/// # }
+/// ```
+///
+/// For `&&`, the right-hand side is only checked when the left-hand side holds,
+/// and the failure of the first side that does not hold is returned.
+#[macro_export]
+macro_rules! try_bassert {
+    ($($tokens:tt)+) => {
+        $crate::bassert_internal!(@parse [try] $($tokens)+)
     };
+}
 
-    ($lhs:tt < $rhs:tt, $($arg:tt)+) => {
-        $crate::bassert_internal!(
-            $crate::internal::BassertKind::Lt,
-            lhs < rhs,
-            $lhs,
-            $rhs,
-            lhs,
-            rhs,
-            $($arg)+
-        )
+/// A version of [`try_bassert!`] for property tests, which returns the failure from the enclosing function.
+///
+/// Property-testing frameworks like `proptest` need a failing test case to return an error instead of panicking,
+/// so they can shrink its inputs. `prop_bassert!(a == b)` is the same as `try_bassert!(a == b)?`:
+/// when the assertion fails, it returns `Err(From::from(failure))`, converting the [`BassertFailure`]
+/// into the error type of the enclosing function, with the same message as [`bassert!`].
+///
+/// With `proptest`, this works without any extra feature, as its `TestCaseError` can be converted from any [`std::error::Error`]:
+/// ```ignore
+/// proptest! {
+///     #[test]
+///     fn reversing_twice_is_identity(v: Vec<i32>) {
+///         let mut reversed = v.clone();
+///         reversed.reverse();
+///         reversed.reverse();
+///         prop_bassert!(reversed == v);
+///     }
+/// }
+/// ```
+/// For `quickcheck`, a property can return the `Result<(), BassertFailure>` of [`try_bassert!`] directly.
+///
+/// Any other error type works as long as it implements `From<BassertFailure>`:
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// # #[cfg(feature = "std")]
+/// fn check(x: i32, y: i32) -> Result<(), Box<dyn std::error::Error>> {
+///     prop_bassert!(x < y, "{} should be smaller", x);
+///     Ok(())
+/// }
+///
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// assert!(check(1, 2).is_ok());
+/// assert!(check(2, 1).unwrap_err().to_string().starts_with("assertion failed: `x < y`"));
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! prop_bassert {
+    ($($tokens:tt)+) => {
+        if let ::core::result::Result::Err(failure) = $crate::try_bassert!($($tokens)+) {
+            return ::core::result::Result::Err(::core::convert::From::from(failure));
+        }
     };
+}
 
-    ($lhs:tt >= $rhs:tt $(,)?) => {
-        $crate::bassert_internal!(
-            $crate::internal::BassertKind::Gte,
-            lhs >= rhs,
-            $lhs,
-            $rhs,
-            lhs,
-            rhs
-        )
-    };
-    ($lhs:tt >= $rhs:tt, $($arg:tt)+) => {
-        $crate::bassert_internal!(
-            $crate::internal::BassertKind::Gte,
-            lhs >= rhs,
-            $lhs,
-            $rhs,
-            lhs,
-            rhs,
-            $($arg)+
-        )
+/// A version of [`bassert!`] which evaluates to the value of the left-hand side of the comparison.
+///
+/// This allows validating a value inline, without a separate `let`:
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// # fn compute() -> i32 { 42 }
+/// let x = bassert_val!(compute() > 0);
+/// assert_eq!(x, 42);
+///
+/// let len = bassert_val!(vec![1, 2, 3].len() == 3, "unexpected length");
+/// assert_eq!(len, 3);
+/// ```
+///
+/// Only comparisons (`==`, `!=`, `<`, `<=`, `>` and `>=`) are supported; other assertions fail to compile:
+/// ```compile_fail
+/// # #[macro_use] extern crate bassert;
+/// let x = 5;
+/// let y = bassert_val!(x in 0..10);
+/// ```
+/// The left-hand side is taken by value (so it is moved when it is not `Copy`),
+/// while the right-hand side is only borrowed, like with [`bassert!`].
+/// When the comparison fails, it panics with exactly the message [`bassert!`] would.
+/// Without the `assertions` feature, the left-hand side is still evaluated and returned, but nothing is checked.
+#[macro_export]
+macro_rules! bassert_val {
+    ($($tokens:tt)+) => {
+        $crate::bassert_internal!(@parse [val] $($tokens)+)
     };
+}
 
-    ($lhs:tt <= $rhs:tt $(,)?) => {
-        $crate::bassert_internal!(
-            $crate::internal::BassertKind::Lte,
-            lhs <= rhs,
-            $lhs,
-            $rhs,
-            lhs,
-            rhs
-        )
+/// Matches a value against a pattern like `let ... else`, evaluating to the bindings of the pattern.
+///
+/// When the value does not match, it panics with exactly the message `bassert!(pattern = value)` would.
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// let result: Result<i32, String> = Ok(42);
+/// let v = bassert_let!(Ok(v) = result);
+/// assert_eq!(v, 42);
+///
+/// #[derive(Debug)]
+/// struct Point { x: i32, y: i32 }
+/// let (x, y) = bassert_let!(Some(Point { x, y }) = Some(Point { x: 1, y: 2 }), "expected a point");
+/// assert_eq!((x, y), (1, 2));
+/// ```
+///
+/// Like in a `let`, the value is matched by value, so the bindings own (parts of) it.
+/// The bindings are taken from the innermost tuple variant or struct of the pattern:
+/// a single binding like in `Some(x)` evaluates to that binding,
+/// multiple ones like in `Point { x, y }` or `Some((a, b))` to a tuple of them.
+/// Other patterns (like `Some(_)`, `None` or `(a, Some(b))`) fail to compile:
+/// ```compile_fail
+/// # #[macro_use] extern crate bassert;
+/// let value = Some(1);
+/// bassert_let!(Some(_) = value);
+/// ```
+///
+/// Because there would be nothing to evaluate to otherwise,
+/// the pattern is matched even without the `assertions` feature.
+#[macro_export]
+macro_rules! bassert_let {
+    ($($tokens:tt)+) => {
+        $crate::bassert_internal!(@let [] $($tokens)+)
     };
+}
 
-    ($lhs:tt <= $rhs:tt, $($arg:tt)+) => {
-        $crate::bassert_internal!(
-            $crate::internal::BassertKind::Lte,
-            lhs <= rhs,
-            $lhs,
-            $rhs,
-            lhs,
-            rhs,
-            $($arg)+
-        )
+/// Asserts that a value matches a pattern, with the same arguments as [`std::assert_matches::assert_matches!`].
+///
+/// `bassert_matches!(value, pattern)` checks the same as [`bassert!`]`(pattern = value)`,
+/// so it can replace `assert_matches!` without having to swap the value and the pattern.
+/// Like there, the pattern can be followed by a guard, and a custom message can be passed after it:
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// let value: Result<i32, String> = Ok(42);
+/// bassert_matches!(value, Ok(_));
+/// bassert_matches!(value, Ok(x) if x > 5, "expected a big value");
+/// bassert_matches!('c', 'a'..='z' | 'A'..='Z');
+/// ```
+/// When the value does not match, it panics with the same message as `bassert!(pattern = value)`:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let value: Option<i32> = Some(3);
+/// bassert_matches!(value, Some(x) if x > 5);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `Some(x) if x > 5 = value`
+/// guard failed: `x > 5`
+/// value: `Some(3)`,
+/// x: `3`
+/// ```
+///
+/// Like with `assert_matches!` (and unlike with `bassert!(pattern = value)`),
+/// the value itself is matched rather than a reference to it,
+/// so the bindings used in the guard are bound by value (like `x` above)
+/// and the value is moved into the assertion unless the pattern only borrows from it.
+#[macro_export]
+macro_rules! bassert_matches {
+    ($value:expr, $($tokens:tt)+) => {
+        $crate::bassert_internal!(@matches [$value] [] $($tokens)+)
     };
+}
 
-    ($lhs:tt == $rhs:tt $(,)?) => {
+/// Asserts that a `Result` is `Ok`, evaluating to the value inside it.
+///
+/// This is a replacement for `.unwrap()` with a more helpful panic message:
+/// like [`bassert!`]`(Ok(_) = result)`, it prints the `Err` using its [`Debug`](core::fmt::Debug) representation,
+/// and (if the error implements [`Display`](core::fmt::Display)) its `Display` representation too:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let port: u16 = bassert_ok!("80a".parse());
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `Ok(_) = "80a".parse()`
+/// "80a".parse(): `Err(ParseIntError { kind: InvalidDigit })`
+/// displayed error: `invalid digit found in string`
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the `Result`.
+/// Like [`bassert_let!`], the `Result` is checked even without the `assertions` feature.
+#[macro_export]
+macro_rules! bassert_ok {
+    ($result:expr $(, $($arg:tt)*)?) => {
         $crate::bassert_internal!(
-            $crate::internal::BassertKind::Eq,
-            lhs == rhs,
-            $lhs,
-            $rhs,
-            lhs,
-            rhs
+            @unwrap ::core::result::Result::Ok(value) => value, "Ok(_)", $result $(, $($arg)*)?
         )
     };
+}
 
-    ($lhs:tt == $rhs:tt, $($arg:tt)+) => {
+/// Asserts that a `Result` is `Err`, evaluating to the error inside it.
+///
+/// This is the opposite of [`bassert_ok!`], printing the `Ok` value when it fails.
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// let error = bassert_err!("80a".parse::<u16>());
+/// assert_eq!(error.to_string(), "invalid digit found in string");
+/// ```
+#[macro_export]
+macro_rules! bassert_err {
+    ($result:expr $(, $($arg:tt)*)?) => {
         $crate::bassert_internal!(
-            $crate::internal::BassertKind::Eq,
-            lhs == rhs,
-            $lhs,
-            $rhs,
-            lhs,
-            rhs,
-            $($arg)+
+            @unwrap ::core::result::Result::Err(error) => error, "Err(_)", $result $(, $($arg)*)?
         )
     };
+}
 
-    ($lhs:tt != $rhs:tt $(,)?) => {
+/// Asserts that an `Option` is `Some`, evaluating to the value inside it.
+///
+/// This is a replacement for `.unwrap()` which prints the expression which turned out to be `None`:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let names = ["alice", "bob"];
+/// let name = bassert_some!(names.iter().find(|name| name.starts_with('c')));
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `Some(_) = names.iter().find(|name| name.starts_with('c'))`
+/// names.iter().find(|name| name.starts_with('c')): `None`
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the `Option`.
+#[macro_export]
+macro_rules! bassert_some {
+    ($option:expr $(, $($arg:tt)*)?) => {
         $crate::bassert_internal!(
-            $crate::internal::BassertKind::Ne,
-            lhs != rhs,
-            $lhs,
-            $rhs,
-            lhs,
-            rhs
+            @unwrap ::core::option::Option::Some(value) => value, "Some(_)", $option $(, $($arg)*)?
         )
     };
+}
 
-    ($lhs:tt != $rhs:tt, $($arg:tt)+) => {
+/// Asserts that an `Option` is `None`, printing the value inside it otherwise.
+///
+/// Unlike [`bassert_some!`], this evaluates to `()`.
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// let v: Vec<i32> = Vec::new();
+/// bassert_none!(v.first());
+/// ```
+#[macro_export]
+macro_rules! bassert_none {
+    ($option:expr $(, $($arg:tt)*)?) => {
         $crate::bassert_internal!(
-            $crate::internal::BassertKind::Ne,
-            lhs != rhs,
-            $lhs,
-            $rhs,
-            lhs,
-            rhs,
-            $($arg)+
+            @unwrap ::core::option::Option::None => (), "None", $option $(, $($arg)*)?
         )
     };
-
-    ($lhs:pat = $rhs:tt $(,)?) => {
-        match &$rhs {
-            rhs => {
-                if let $lhs = rhs {
-                    // Assertion succeeded :-)
-                } else {
-                    $crate::internal::bassert_match_failed(
-                        stringify!($lhs),
-                        stringify!($rhs),
-                        &*rhs,
-                        ::std::option::Option::None,
-                    )
-                }
-            }
-        }
-    };
-
-    ($lhs:pat = $rhs:tt, $($arg:tt)+) => {
-        match &$rhs {
-            rhs => {
-                if let $lhs = rhs {
-                    // Assertion succeeded :-)
-                } else {
-                    $crate::internal::bassert_match_failed(
-                        stringify!($lhs),
-                        stringify!($rhs),
-                        &*rhs,
-                        ::std::option::Option::Some(::std::format_args!($($arg)+)),
-                    )
-                }
-            }
-        }
-    };
 }
 
-/// A version of [`bassert!`] which compiles down to a no-op outside of debug builds.
+/// Asserts that a `Result` is `Ok`, without taking it or evaluating to the value inside it.
 ///
-/// In debug builds (where the `debug_assertions` config attribute it set), it will
-/// perform exactly the same as writing [`bassert!`].
+/// Unlike [`bassert_ok!`], the `Result` is only borrowed, so it can still be used afterwards.
+/// When it is an `Err`, the whole `Result` is printed, followed by the variant that was expected:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let port = "80a".parse::<u16>();
+/// bassert_is_ok!(port);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `port.is_ok()`
+/// port: `Err(ParseIntError { kind: InvalidDigit })`
+/// expected `Ok(_)`, got `Err(_)`
+/// ```
 ///
-/// In non-debug builds, it will be a no-op.
+/// Like with [`bassert!`], a custom message can be passed after the `Result`.
+/// Like [`bassert_ok!`], the `Result` is checked even without the `assertions` feature.
+#[macro_export]
+macro_rules! bassert_is_ok {
+    ($result:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@is is_ok, "Ok(_)", "Err(_)", $result $(, $($arg)*)?)
+    };
+}
+
+/// Asserts that a `Result` is `Err`, without taking it or evaluating to the error inside it.
 ///
-/// Its usage is identical to the [`bassert!`] macro.
+/// This is the opposite of [`bassert_is_ok!`].
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// let port = "80a".parse::<u16>();
+/// bassert_is_err!(port, "{:?} is not a valid port", "80a");
+/// ```
 #[macro_export]
-macro_rules! debug_bassert {
-    ($($arg:tt)*) => {
-         if $crate::cfg!(debug_assertions) {
-             $crate::bassert!($($arg)*);
-         }
-     };
+macro_rules! bassert_is_err {
+    ($result:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@is is_err, "Err(_)", "Ok(_)", $result $(, $($arg)*)?)
+    };
 }
 
-// This macro is only used internally in another macro
+/// Asserts that an `Option` is `Some`, without taking it or evaluating to the value inside it.
+///
+/// Unlike [`bassert_some!`], the `Option` is only borrowed, so it can still be used afterwards.
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// let names = vec!["alice", "bob"];
+/// bassert_is_some!(names.first());
+/// ```
 #[macro_export]
-#[doc(hidden)]
-#[allow(unused_macros)]
-macro_rules! bassert_internal {
-    ($kind:expr, $expr:expr, $lhs_expr:tt, $rhs_expr:tt, $lhs_var:ident, $rhs_var:ident) => {
-        match (&$lhs_expr, &$rhs_expr) {
-            ($lhs_var, $rhs_var) => {
-                if !$expr {
-                    let kind = $kind;
-                    $crate::internal::bassert_failed(
-                        kind,
-                        stringify!($lhs_expr),
-                        stringify!($rhs_expr),
-                        &*$lhs_var,
-                        &*$rhs_var,
-                        ::std::option::Option::None,
-                    )
-                }
-            }
-        }
+macro_rules! bassert_is_some {
+    ($option:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@is is_some, "Some(_)", "None", $option $(, $($arg)*)?)
     };
+}
 
-    ($kind:expr, $expr:expr, $lhs_expr:tt, $rhs_expr:tt, $lhs_var:ident, $rhs_var:ident, $($arg:tt)+) => {
-        match (&$lhs_expr, &$rhs_expr) {
-            ($lhs_var, $rhs_var) => {
-                if !$expr {
-                    let kind = $kind;
-                    $crate::internal::bassert_failed(
-                        kind,
-                        stringify!($lhs_expr),
-                        stringify!($rhs_expr),
-                        &*$lhs_var,
-                        &*$rhs_var,
-                        ::std::option::Option::Some(::std::format_args!($($arg)+)),
-                    )
-                }
-            }
-        }
+/// Asserts that an `Option` is `None`, without taking it.
+///
+/// This is the opposite of [`bassert_is_some!`]. When the `Option` is `Some`, the value inside it is printed:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let names = vec!["alice", "bob"];
+/// bassert_is_none!(names.iter().find(|name| name.starts_with('b')));
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `names.iter().find(|name| name.starts_with('b')).is_none()`
+/// names.iter().find(|name| name.starts_with('b')): `Some("bob")`
+/// expected `None`, got `Some(_)`
+/// ```
+#[macro_export]
+macro_rules! bassert_is_none {
+    ($option:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@is is_none, "None", "Some(_)", $option $(, $($arg)*)?)
     };
 }
 
+/// Asserts that a value satisfies a predicate, like a function `fn is_prime(n: &u64) -> bool` or a closure.
+///
+/// Unlike `bassert!(is_prime(&n))`, which can only tell that the call returned `false`,
+/// the failure message names the predicate and prints the value it failed for:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// fn is_prime(n: &u64) -> bool {
+///     *n > 1 && (2..*n).all(|d| n % d != 0)
+/// }
+///
+/// let n = 9;
+/// bassert_pred!(n, is_prime);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `is_prime(n)`
+/// predicate `is_prime` failed for n: `9`
+/// ```
+///
+/// Like the operands of [`bassert!`], the value is only borrowed, so the predicate is called with a reference to it.
+/// Like with [`bassert!`], a custom message can be passed after the predicate:
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// let words = vec!["apple", "avocado"];
+/// for word in &words {
+///     bassert_pred!(word, |word: &&str| word.starts_with('a'), "in {:?}", words);
+/// }
+/// ```
+#[macro_export]
+macro_rules! bassert_pred {
+    ($value:expr, $pred:path $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(
+            @pred
+            ::core::concat!(stringify!($pred), "(", stringify!($value), ")"),
+            $value,
+            $pred
+            $(, $($arg)*)?
+        )
+    };
+    ($value:expr, $pred:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(
+            @pred
+            ::core::concat!("(", stringify!($pred), ")(", stringify!($value), ")"),
+            $value,
+            $pred
+            $(, $($arg)*)?
+        )
+    };
+}
+
+/// Asserts that a collection (anything with a `len` method, like a slice, `Vec`, `str` or map) has the given length.
+///
+/// When it does not, both lengths are printed, as well as the collection itself:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let v = vec![1, 2];
+/// bassert_len!(v, 3);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `v.len() == 3`
+/// v: `[1, 2]`
+/// expected length `3`, actual length `2`
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the length.
+#[macro_export]
+macro_rules! bassert_len {
+    ($collection:expr, $len:expr $(, $($arg:tt)*)?) => {
+        match (&$collection, $len) {
+            (collection, expected) => {
+                let expected: usize = expected;
+                let actual = collection.len();
+                $crate::bassert_internal!(
+                    @check [panic]
+                    actual == expected,
+                    $crate::internal::bassert_len_failed(
+                        ::core::concat!(stringify!($collection), ".len() == ", stringify!($len)),
+                        stringify!($collection),
+                        $crate::internal::Operand::new(&collection),
+                        ::core::option::Option::Some(expected),
+                        actual,
+                        $crate::bassert_internal!(@args $($($arg)*)?),
+                    )
+                )
+            }
+        }
+    };
+}
+
+/// Asserts that a collection (anything with `len` and `is_empty` methods) is empty,
+/// printing its length and contents otherwise.
+///
+/// Like with [`bassert!`], a custom message can be passed after the collection.
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// let v: Vec<i32> = Vec::new();
+/// bassert_empty!(v);
+/// ```
+#[macro_export]
+macro_rules! bassert_empty {
+    ($collection:expr $(, $($arg:tt)*)?) => {
+        match &$collection {
+            collection => $crate::bassert_internal!(
+                @check [panic]
+                collection.is_empty(),
+                $crate::internal::bassert_len_failed(
+                    ::core::concat!(stringify!($collection), ".is_empty()"),
+                    stringify!($collection),
+                    $crate::internal::Operand::new(&collection),
+                    ::core::option::Option::Some(0),
+                    collection.len(),
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+}
+
+/// Asserts that a collection (anything with `len` and `is_empty` methods) is not empty.
+///
+/// Like with [`bassert!`], a custom message can be passed after the collection.
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let v: Vec<i32> = Vec::new();
+/// bassert_non_empty!(v, "there should be results");
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `!v.is_empty()`
+/// v: `[]`: there should be results
+/// expected a non-zero length, actual length `0`
+/// ```
+#[macro_export]
+macro_rules! bassert_non_empty {
+    ($collection:expr $(, $($arg:tt)*)?) => {
+        match &$collection {
+            collection => $crate::bassert_internal!(
+                @check [panic]
+                !collection.is_empty(),
+                $crate::internal::bassert_len_failed(
+                    ::core::concat!("!", stringify!($collection), ".is_empty()"),
+                    stringify!($collection),
+                    $crate::internal::Operand::new(&collection),
+                    ::core::option::Option::None,
+                    collection.len(),
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+}
+
+/// Asserts that the elements of a slice (or anything implementing `AsRef<[T]>`, like a `Vec` or an array) are sorted in ascending order.
+///
+/// When they are not, the first pair of neighbouring elements which is out of order is printed as well:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let v = vec![1, 2, 5, 3, 4];
+/// bassert_sorted!(v);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `v.is_sorted()`
+/// v: `[1, 2, 5, 3, 4]`
+/// element at index 2 (`5`) > element at index 3 (`3`)
+/// ```
+///
+/// Elements which cannot be compared (like `NaN`) are not sorted either.
+/// Like with [`bassert!`], a custom message can be passed after the slice.
+#[macro_export]
+macro_rules! bassert_sorted {
+    ($slice:expr $(, $($arg:tt)*)?) => {
+        match ::core::convert::AsRef::<[_]>::as_ref(&$slice) {
+            slice => $crate::bassert_internal!(
+                @check [panic]
+                slice.is_sorted(),
+                $crate::internal::bassert_sorted_failed(
+                    ::core::concat!(stringify!($slice), ".is_sorted()"),
+                    stringify!($slice),
+                    slice,
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+}
+
+/// Asserts that the elements of a slice (or anything implementing `AsRef<[T]>`) are strictly increasing,
+/// like the values of a counter or timestamps which should never repeat.
+///
+/// When they are not, the first pair of neighbouring elements which is not increasing is printed as well:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let ids = [1, 2, 2, 3];
+/// bassert_monotonic!(ids);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `ids.is_sorted_by(|a, b| a < b)`
+/// ids: `[1, 2, 2, 3]`
+/// element at index 1 (`2`) >= element at index 2 (`2`)
+/// ```
+///
+/// Use [`bassert_monotonic_non_decreasing!`] to allow neighbouring elements to be equal.
+/// Like with [`bassert!`], a custom message can be passed after the slice.
+#[macro_export]
+macro_rules! bassert_monotonic {
+    ($slice:expr $(, $($arg:tt)*)?) => {
+        match ::core::convert::AsRef::<[_]>::as_ref(&$slice) {
+            slice => $crate::bassert_internal!(
+                @check [panic]
+                slice.is_sorted_by(|a, b| a < b),
+                $crate::internal::bassert_monotonic_failed(
+                    ::core::concat!(stringify!($slice), ".is_sorted_by(|a, b| a < b)"),
+                    stringify!($slice),
+                    slice,
+                    true,
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+}
+
+/// Asserts that the elements of a slice (or anything implementing `AsRef<[T]>`) never decrease,
+/// printing the first pair of neighbouring elements which does like [`bassert_monotonic!`] does.
+///
+/// This is the same check as [`bassert_sorted!`], but reads better for sequences like measurements over time.
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// let timestamps = [10, 12, 12, 15];
+/// bassert_monotonic_non_decreasing!(timestamps);
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the slice.
+#[macro_export]
+macro_rules! bassert_monotonic_non_decreasing {
+    ($slice:expr $(, $($arg:tt)*)?) => {
+        match ::core::convert::AsRef::<[_]>::as_ref(&$slice) {
+            slice => $crate::bassert_internal!(
+                @check [panic]
+                slice.is_sorted_by(|a, b| a <= b),
+                $crate::internal::bassert_monotonic_failed(
+                    ::core::concat!(stringify!($slice), ".is_sorted_by(|a, b| a <= b)"),
+                    stringify!($slice),
+                    slice,
+                    false,
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+}
+
+/// Asserts that the elements of a slice are sorted according to a comparison function,
+/// like the one passed to [`slice::sort_by`].
+///
+/// It fails when the comparison function returns [`Ordering::Greater`](core::cmp::Ordering::Greater) for any two neighbouring elements,
+/// printing the first such pair like [`bassert_sorted!`] does.
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// let v = vec![3, 2, 1];
+/// bassert_sorted_by!(v, |a, b| b.cmp(a));
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the comparison function.
+#[macro_export]
+macro_rules! bassert_sorted_by {
+    ($slice:expr, $cmp:expr $(, $($arg:tt)*)?) => {
+        // Passed to a function, so the types of the arguments of a closure are inferred.
+        $crate::internal::bassert_sorted_by(
+            ::core::concat!(stringify!($slice), ".is_sorted_by(", stringify!($cmp), ")"),
+            stringify!($slice),
+            ::core::convert::AsRef::<[_]>::as_ref(&$slice),
+            $cmp,
+            $crate::bassert_internal!(@args $($($arg)*)?),
+        )
+    };
+}
+
+/// Asserts that two iterators (or anything implementing [`IntoIterator`]) yield equal elements.
+///
+/// The elements are compared one by one while iterating, without collecting them first,
+/// which makes this suitable for long (or even infinite, as long as they differ) iterators.
+/// On the first pair of elements which differ, only those are printed, with their index:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let squares = (1..).map(|n| n * n);
+/// bassert_iter_eq!(squares.take(4), [1, 4, 8, 16]);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `squares.take(4).into_iter().eq([1, 4, 8, 16])`
+/// first difference at index 2: left = `9`, right = `8`
+/// ```
+///
+/// When one of them ends before the other, the number of elements they had in common
+/// and the next element of the longer one are printed, like
+/// ``right ended after `3` elements, but left continues with `16` ``.
+///
+/// Like with [`bassert!`], a custom message can be passed after the iterators.
+#[macro_export]
+macro_rules! bassert_iter_eq {
+    ($lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        $crate::internal::bassert_iter_eq(
+            ::core::concat!(stringify!($lhs), ".into_iter().eq(", stringify!($rhs), ")"),
+            $lhs,
+            $rhs,
+            $crate::bassert_internal!(@args $($($arg)*)?),
+        )
+    };
+}
+
+/// Asserts that the given code panics.
+///
+/// When a string is passed after the code, the panic message should contain it as well.
+/// The panic is caught, so the test continues afterwards, and its message is not printed.
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// let v: Vec<i32> = Vec::new();
+/// bassert_panics!(v[0]);
+/// bassert_panics!(v[0], "index out of bounds");
+/// ```
+/// When the code does not panic (or panics with another message), this panics with a message like:
+/// ```text
+/// assertion failed: `v[0]`
+/// expected a panic containing `out of bounds`, but it panicked with: `something else`
+/// ```
+///
+/// Only available with the `std` feature, as catching panics requires unwinding.
+/// Panics on other threads (like those of other tests) are printed as usual while the code runs.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! bassert_panics {
+    ($code:expr $(,)?) => {
+        $crate::internal::bassert_panics(stringify!($code), ::core::option::Option::None, || {
+            #[allow(clippy::diverging_sub_expression)]
+            let _ = $code;
+        })
+    };
+
+    ($code:expr, $expected:expr $(,)?) => {
+        $crate::internal::bassert_panics(
+            stringify!($code),
+            ::core::option::Option::Some(::core::convert::AsRef::<str>::as_ref(&$expected)),
+            || {
+                #[allow(clippy::diverging_sub_expression)]
+                let _ = $code;
+            },
+        )
+    };
+}
+
+/// Asserts that the given code does not panic.
+///
+/// When it does, the panic is caught and this panics with a message containing the original panic message:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let v: Vec<i32> = Vec::new();
+/// bassert_no_panic!(v[0]);
+/// ```
+/// ```text
+/// assertion failed: `v[0]`
+/// expected no panic, but it panicked with: `index out of bounds: the len is 0 but the index is 0`
+/// ```
+///
+/// Only available with the `std` feature, as catching panics requires unwinding.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! bassert_no_panic {
+    ($code:expr $(,)?) => {
+        $crate::internal::bassert_no_panic(stringify!($code), || {
+            #[allow(clippy::diverging_sub_expression)]
+            let _ = $code;
+        })
+    };
+}
+
+/// A version of [`bassert!`] which records its failure into a [`BassertGroup`] instead of panicking.
+///
+/// The first argument is the group (or a mutable reference to it),
+/// followed by any assertion accepted by [`bassert!`].
+/// See [`BassertGroup`] for an example.
+#[macro_export]
+macro_rules! soft_bassert {
+    ($group:expr, $($tokens:tt)+) => {
+        $group.check($crate::try_bassert!($($tokens)+))
+    };
+}
+
+/// Asserts that all of the given assertions hold, reporting every one which does not.
+///
+/// Each comma-separated argument is any assertion accepted by [`bassert!`] (without a custom message).
+/// Unlike writing them as separate `bassert!`s, all of them are checked,
+/// after which this panics with a message listing every failure (like [`BassertGroup::assert_all`]):
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let (x, y, z) = (1, 2, None::<i32>);
+/// bassert_all!(x == y, x < y, Some(_) = z);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// 2 of 3 assertions failed
+///
+/// 1. assertion failed: `x == y`
+/// x: `1`,
+/// y: `2`
+/// difference: `-1`
+///
+/// 2. assertion failed: `Some(_) = z`
+/// z: `None`
+/// ```
+#[macro_export]
+macro_rules! bassert_all {
+    ($($tokens:tt)+) => {
+        $crate::bassert_internal!(@list [all] [] [] $($tokens)+)
+    };
+}
+
+/// Asserts that at least one of the given assertions holds.
+///
+/// Each comma-separated argument is any assertion accepted by [`bassert!`] (without a custom message).
+/// They are checked in order, stopping at the first one which holds (like `||`).
+/// When none of them does, this panics with a message listing every failure (like [`BassertGroup::assert_any`]):
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let status = 503;
+/// bassert_any!(status == 200, status == 204);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// none of 2 assertions held
+///
+/// 1. assertion failed: `status == 200`
+/// status: `503`,
+/// 200: `200`
+/// difference: `303`
+///
+/// 2. assertion failed: `status == 204`
+/// status: `503`,
+/// 204: `204`
+/// difference: `299`
+/// ```
+#[macro_export]
+macro_rules! bassert_any {
+    ($($tokens:tt)+) => {
+        $crate::bassert_internal!(@list [any] [] [] $($tokens)+)
+    };
+}
+
+/// A version of [`bassert!`] which compiles down to a no-op outside of debug builds.
+///
+/// In debug builds (where the `debug_assertions` config attribute it set), it will
+/// perform exactly the same as writing [`bassert!`].
+///
+/// In non-debug builds, it will be a no-op.
+///
+/// Its usage is identical to the [`bassert!`] macro.
+#[macro_export]
+macro_rules! debug_bassert {
+    ($($arg:tt)*) => {
+         if ::core::cfg!(debug_assertions) {
+             $crate::bassert!($($arg)*);
+         }
+     };
+}
+
+/// A version of [`bassert!`] which compiles down to a no-op in debug builds.
+///
+/// This is the inverse of [`debug_bassert!`]: in non-debug builds (where the `debug_assertions` config attribute is not set),
+/// it will perform exactly the same as writing [`bassert!`].
+/// This is useful for checks which are too slow to run in unoptimized builds, or invariants which only hold in release builds.
+///
+/// Its usage is identical to the [`bassert!`] macro.
+#[macro_export]
+macro_rules! release_bassert {
+    ($($arg:tt)*) => {
+         if !::core::cfg!(debug_assertions) {
+             $crate::bassert!($($arg)*);
+         }
+     };
+}
+
+/// A version of [`bassert!`] which is only checked when enabled at runtime.
+///
+/// This is meant for expensive assertions which should normally not slow down the program,
+/// but can be switched on when investigating a problem, without recompiling.
+/// They are enabled by setting the `BASSERT_ENABLE` environment variable to `1` (or `true`, `yes` or `on`),
+/// which is read the first time an `optional_bassert!` is reached, or by calling [`set_enabled`].
+///
+/// When disabled, the operands are not evaluated: the only cost is checking whether the assertions are enabled.
+/// (Without the `std` feature, the environment variable is not read, so only [`set_enabled`] enables them.)
+///
+/// Its usage is identical to the [`bassert!`] macro.
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// # fn expensive_consistency_check() -> bool { true }
+/// bassert::set_enabled(true);
+/// optional_bassert!(expensive_consistency_check());
+/// ```
+#[macro_export]
+macro_rules! optional_bassert {
+    ($($arg:tt)*) => {
+         if $crate::internal::optional_bassert_enabled() {
+             $crate::bassert!($($arg)*);
+         }
+     };
+}
+
+/// A compile-time version of [`bassert!`], for assertions about constants.
+///
+/// The condition is evaluated during compilation, which fails if it does not hold,
+/// with an error containing `` assertion failed: `condition` ``.
+/// Because formatting values using [`Debug`](core::fmt::Debug) is not possible at compile time,
+/// only the stringified condition (and optionally a string literal message) is printed.
+///
+/// It can be used both at the item level and inside function bodies:
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// const BUFFER_SIZE: usize = 256;
+/// const_bassert!(BUFFER_SIZE.is_power_of_two());
+///
+/// fn main() {
+///     const_bassert!(BUFFER_SIZE <= 1024, "the buffer should fit on the stack");
+/// }
+/// ```
+///
+/// Only conditions which can be evaluated in a `const` context are supported,
+/// like comparisons of integers or calls to `const fn`s.
+/// ```compile_fail
+/// # #[macro_use] extern crate bassert;
+/// const BUFFER_SIZE: usize = 100;
+/// const_bassert!(BUFFER_SIZE.is_power_of_two()); // Fails to compile
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! const_bassert {
+    ($cond:expr $(,)?) => {
+        const _: () = {
+            if !$cond {
+                ::core::panic!(
+                    "{}",
+                    ::core::concat!("assertion failed: `", ::core::stringify!($cond), "`")
+                )
+            }
+        };
+    };
+
+    ($cond:expr, $message:literal $(,)?) => {
+        const _: () = {
+            if !$cond {
+                ::core::panic!(
+                    "{}",
+                    ::core::concat!(
+                        "assertion failed: `",
+                        ::core::stringify!($cond),
+                        "`: ",
+                        $message
+                    )
+                )
+            }
+        };
+    };
+}
+
+/// Asserts that a value lies between two bounds (both inclusive), like `low <= x <= high`.
+///
+/// This reads more naturally than `bassert!(x in low..=high)` when the bounds are expressions,
+/// and each of the three operands is evaluated exactly once.
+/// When the assertion fails, all three are printed, together with the bound which was violated:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let (speed, min_speed, max_speed) = (130, 60, 120);
+/// bassert_between!(speed, min_speed, max_speed);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `min_speed <= speed <= max_speed`
+/// upper bound violated: `speed <= max_speed`
+/// min_speed: `60`,
+/// speed: `130`,
+/// max_speed: `120`
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the bounds.
+#[macro_export]
+macro_rules! bassert_between {
+    ($val:expr, $low:expr, $high:expr $(, $($arg:tt)*)?) => {
+        match (&$val, &$low, &$high) {
+            (val, low, high) => {
+                let above_low = low <= val;
+                $crate::bassert_internal!(
+                    @check [panic]
+                    above_low && val <= high,
+                    $crate::internal::bassert_between_failed(
+                        [stringify!($val), stringify!($low), stringify!($high)],
+                        [
+                            $crate::internal::Operand::new(&val),
+                            $crate::internal::Operand::new(&low),
+                            $crate::internal::Operand::new(&high),
+                        ],
+                        above_low,
+                        if above_low {
+                            ::core::cmp::PartialOrd::partial_cmp(val, high).is_none()
+                        } else {
+                            ::core::cmp::PartialOrd::partial_cmp(low, val).is_none()
+                        },
+                        $crate::bassert_internal!(@args $($($arg)*)?),
+                    )
+                )
+            }
+        }
+    };
+}
+
+/// Asserts that the [`Ord`] implementation of a type is consistent with its [`PartialOrd`] and [`PartialEq`] implementations
+/// for two given values, which catches the buggy implementations that make a `BTreeMap` or sort misbehave.
+///
+/// The checked laws are, in order:
+/// - `a.partial_cmp(&b) == Some(a.cmp(&b))`
+/// - `(a == b) == a.cmp(&b).is_eq()`, and likewise for `<`, `<=`, `>` and `>=`
+/// - `b.cmp(&a) == a.cmp(&b).reverse()`
+///
+/// The first law which is broken is printed with the values and what both sides of it returned:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// use std::cmp::Ordering;
+///
+/// #[derive(Debug, PartialEq, Eq, PartialOrd)]
+/// struct Version(u32);
+///
+/// impl Ord for Version {
+///     fn cmp(&self, other: &Self) -> Ordering {
+///         other.0.cmp(&self.0)
+///     }
+/// }
+///
+/// bassert_ord_consistent!(Version(1), Version(2));
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `Version(1).partial_cmp(&Version(2)) == Some(Version(1).cmp(&Version(2)))`
+/// Version(1): `Version(1)`,
+/// Version(2): `Version(2)`
+/// Version(1).partial_cmp(&Version(2)) returned `Some(Less)`, but Version(1).cmp(&Version(2)) returned `Greater`
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the values.
+#[macro_export]
+macro_rules! bassert_ord_consistent {
+    ($lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => $crate::internal::bassert_ord_consistent(
+                [stringify!($lhs), stringify!($rhs)],
+                $crate::internal::Operand::new(&lhs),
+                $crate::internal::Operand::new(&rhs),
+                lhs,
+                rhs,
+                $crate::bassert_internal!(@args $($($arg)*)?),
+            ),
+        }
+    };
+}
+
+/// Asserts that two floating-point numbers (`f32` or `f64`) are at most `ulps` units in the last place apart.
+///
+/// The distance in ULPs is the number of representable floats between them (plus one),
+/// which is a tolerance that scales with the magnitude of the numbers by construction.
+/// `0.0` and `-0.0` are equal (so zero ULPs apart), and `NaN` is never equal to anything.
+/// When they are further apart, the distance is printed:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let sum: f64 = (0..10).map(|_| 0.1).sum();
+/// bassert_ulps_eq!(sum, 1.0, ulps = 0);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `sum ~= 1.0`
+/// sum: `0.9999999999999999`,
+/// 1.0: `1.0`,
+/// ULP distance: `1`,
+/// maximum ULP distance: `0`
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the maximum distance.
+#[macro_export]
+macro_rules! bassert_ulps_eq {
+    ($lhs:expr, $rhs:expr, ulps = $ulps:expr $(, $($arg:tt)*)?) => {
+        match (&$lhs, &$rhs, $ulps) {
+            (lhs, rhs, ulps) => {
+                let distance = $crate::internal::UlpsFloat::ulps_distance(*lhs, *rhs);
+                $crate::bassert_internal!(
+                    @check [panic]
+                    distance.is_some_and(|distance| distance <= ulps),
+                    $crate::internal::bassert_ulps_failed(
+                        stringify!($lhs),
+                        stringify!($rhs),
+                        *lhs,
+                        *rhs,
+                        distance,
+                        ulps,
+                        $crate::bassert_internal!(@args $($($arg)*)?),
+                    )
+                )
+            }
+        }
+    };
+}
+
+/// Asserts that two slices of floating-point numbers (or anything implementing `AsRef<[f32]>` or `AsRef<[f64]>`, like a `Vec`)
+/// have the same length, and that each pair of elements is approximately equal like with `~=`.
+///
+/// An explicit tolerance can be passed as `eps = ...`, which is used for every pair of elements.
+/// Otherwise, the default tolerance of `~=` is used, scaled by the magnitude of each pair.
+/// When they differ, the difference of the first pair exceeding the tolerance is printed with its index:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let computed = vec![1.0, 2.0, 3.5];
+/// bassert_approx_slice_eq!(computed, [1.0, 2.0, 3.0], eps = 1e-9);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `computed ~= [1.0, 2.0, 3.0]`
+/// computed: `[1.0, 2.0, 3.5]`,
+/// [1.0, 2.0, 3.0]: `[1.0, 2.0, 3.0]`,
+/// difference: `0.5`,
+/// tolerance: `1e-9`
+/// first difference at index 2: left = `3.5`, right = `3.0`
+/// ```
+///
+/// When the lengths differ, these are printed instead of the difference and tolerance.
+/// Like with [`bassert!`], a custom message can be passed after the tolerance (or after the slices, without one).
+#[macro_export]
+macro_rules! bassert_approx_slice_eq {
+    ($lhs:expr, $rhs:expr, eps = $eps:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_approx_slice_eq!(@impl $lhs, $rhs, ::core::option::Option::Some($eps) $(, $($arg)*)?)
+    };
+
+    (@impl $lhs:expr, $rhs:expr, $eps:expr $(, $($arg:tt)*)?) => {
+        match (
+            ::core::convert::AsRef::<[_]>::as_ref(&$lhs),
+            ::core::convert::AsRef::<[_]>::as_ref(&$rhs),
+            $eps,
+        ) {
+            (lhs, rhs, eps) => $crate::bassert_internal!(
+                @check [panic]
+                $crate::internal::approx_slice_eq(lhs, rhs, eps),
+                $crate::internal::bassert_approx_slice_failed(
+                    [stringify!($lhs), stringify!($rhs)],
+                    lhs,
+                    rhs,
+                    eps,
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+
+    ($lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_approx_slice_eq!(@impl $lhs, $rhs, ::core::option::Option::None $(, $($arg)*)?)
+    };
+}
+
+/// Asserts that two optional floating-point numbers (`Option<f32>` or `Option<f64>`) are approximately equal.
+///
+/// Two `None`s are equal, a `Some` never equals a `None`, and two `Some`s are compared like with `~=`.
+/// Like with [`bassert_approx_slice_eq!`], an explicit tolerance can be passed as `eps = ...`.
+/// When both are `Some`, the difference of the values inside them is printed:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let measured = Some(3.5);
+/// bassert_approx_opt_eq!(measured, Some(3.0), eps = 0.1);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `measured ~= Some(3.0)`
+/// measured: `Some(3.5)`,
+/// Some(3.0): `Some(3.0)`,
+/// difference: `0.5`,
+/// tolerance: `0.1`
+/// ```
+///
+/// When only one of them is `None`, this is printed instead:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let measured: Option<f64> = None;
+/// bassert_approx_opt_eq!(measured, Some(3.0));
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `measured ~= Some(3.0)`
+/// measured: `None`,
+/// Some(3.0): `Some(3.0)`
+/// measured is `None`, but Some(3.0) is not
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the tolerance (or after the operands, without one).
+#[macro_export]
+macro_rules! bassert_approx_opt_eq {
+    ($lhs:expr, $rhs:expr, eps = $eps:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_approx_opt_eq!(@impl $lhs, $rhs, ::core::option::Option::Some($eps) $(, $($arg)*)?)
+    };
+
+    (@impl $lhs:expr, $rhs:expr, $eps:expr $(, $($arg:tt)*)?) => {
+        match (&$lhs, &$rhs, $eps) {
+            (lhs, rhs, eps) => $crate::bassert_internal!(
+                @check [panic]
+                $crate::internal::approx_opt_eq(*lhs, *rhs, eps),
+                $crate::internal::bassert_approx_opt_failed(
+                    [stringify!($lhs), stringify!($rhs)],
+                    *lhs,
+                    *rhs,
+                    eps,
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+
+    ($lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_approx_opt_eq!(@impl $lhs, $rhs, ::core::option::Option::None $(, $($arg)*)?)
+    };
+}
+
+/// Asserts that two floating-point numbers (`f32` or `f64`) have exactly the same bits.
+///
+/// Unlike `==`, this considers a `NaN` equal to itself, and `0.0` different from `-0.0`,
+/// which is useful to test code which should reproduce floats exactly (like serialization).
+/// When they differ, their bit patterns are printed as well,
+/// with the operands parenthesized in the header so it reads as the check which was made:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let zero = -0.0f64;
+/// bassert_total_eq!(zero, 0.0);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `(zero).to_bits() == (0.0).to_bits()`
+/// zero: `-0.0`,
+/// 0.0: `0.0`
+/// bits: left = `0x8000000000000000`, right = `0x0000000000000000`
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the operands.
+#[macro_export]
+macro_rules! bassert_total_eq {
+    ($lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => $crate::bassert_internal!(
+                @check [panic]
+                $crate::internal::TotalEqFloat::bits_eq(*lhs, *rhs),
+                $crate::internal::bassert_total_eq_failed(
+                    ::core::concat!("(", stringify!($lhs), ").to_bits() == (", stringify!($rhs), ").to_bits()"),
+                    [stringify!($lhs), stringify!($rhs)],
+                    *lhs,
+                    *rhs,
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+}
+
+/// Asserts that a [`Duration`](core::time::Duration) is within a tolerance of an expected one,
+/// like the time some code took to run.
+///
+/// When the difference between them is larger than the tolerance, all of them are printed:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// use std::time::Duration;
+///
+/// let elapsed = Duration::from_millis(130);
+/// bassert_duration_near!(elapsed, Duration::from_millis(100), Duration::from_millis(20));
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `elapsed ~= Duration::from_millis(100)`
+/// elapsed: `130ms`,
+/// Duration::from_millis(100): `100ms`,
+/// difference: `30ms`,
+/// tolerance: `20ms`
+/// ```
+///
+/// The difference is never negative: it does not matter whether the duration is too long or too short.
+/// Like with [`bassert!`], a custom message can be passed after the tolerance.
+#[macro_export]
+macro_rules! bassert_duration_near {
+    ($measured:expr, $expected:expr, $tolerance:expr $(, $($arg:tt)*)?) => {
+        match ($measured, $expected, $tolerance) {
+            (measured, expected, tolerance) => {
+                let (measured, expected, tolerance): (
+                    ::core::time::Duration,
+                    ::core::time::Duration,
+                    ::core::time::Duration,
+                ) = (measured, expected, tolerance);
+                $crate::bassert_internal!(
+                    @check [panic]
+                    measured.abs_diff(expected) <= tolerance,
+                    $crate::internal::bassert_duration_failed(
+                        stringify!($measured),
+                        stringify!($expected),
+                        measured,
+                        expected,
+                        tolerance,
+                        $crate::bassert_internal!(@args $($($arg)*)?),
+                    )
+                )
+            }
+        }
+    };
+}
+
+/// Asserts that two references point to the same object, using [`core::ptr::eq`].
+///
+/// Unlike `==`, this does not compare the values they point to, which is useful to test interning or caching.
+/// To check that two `Rc`s or `Arc`s point to the same allocation (like `Rc::ptr_eq`), pass `&*a` and `&*b`.
+/// When they do not, the values and the addresses they point to are printed:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let (a, b) = (String::from("x"), String::from("x"));
+/// bassert_ptr_eq!(&a, &b);
+/// ```
+/// This will panic with a message like:
+/// ```text
+/// assertion failed: `ptr::eq(&a, &b)`
+/// &a: `"x"`,
+/// &b: `"x"`
+/// addresses: left = `0x7ffc6a3f5d40`, right = `0x7ffc6a3f5d58`
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the references.
+#[macro_export]
+macro_rules! bassert_ptr_eq {
+    ($lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(
+            @ptr true,
+            ::core::concat!("ptr::eq(", stringify!($lhs), ", ", stringify!($rhs), ")"),
+            $lhs,
+            $rhs
+            $(, $($arg)*)?
+        )
+    };
+}
+
+/// Asserts that two references point to different objects, using [`core::ptr::eq`].
+///
+/// This is the opposite of [`bassert_ptr_eq!`], which prints the same values and addresses when it fails.
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// let (a, b) = (String::from("x"), String::from("x"));
+/// bassert_ptr_ne!(&a, &b);
+/// ```
+#[macro_export]
+macro_rules! bassert_ptr_ne {
+    ($lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(
+            @ptr false,
+            ::core::concat!("!ptr::eq(", stringify!($lhs), ", ", stringify!($rhs), ")"),
+            $lhs,
+            $rhs
+            $(, $($arg)*)?
+        )
+    };
+}
+
+/// Asserts that two strings are equal when ignoring ASCII case, using [`str::eq_ignore_ascii_case`].
+///
+/// The operands can be anything implementing `AsRef<str>`, like `&str` or `String`.
+/// When they differ beyond case, both are printed with their original casing,
+/// followed by the first character which differs (or their lengths, in characters):
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let header = "Content-Type";
+/// bassert_ieq!(header, "content-length");
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `header.eq_ignore_ascii_case("content-length")`
+/// header: `"Content-Type"`,
+/// "content-length": `"content-length"`
+/// first difference at index 8: left = `'T'`, right = `'l'`
+/// lengths differ: left = `12`, right = `14`
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the operands.
+#[macro_export]
+macro_rules! bassert_ieq {
+    ($lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => {
+                let (lhs_str, rhs_str): (&str, &str) = (
+                    ::core::convert::AsRef::<str>::as_ref(lhs),
+                    ::core::convert::AsRef::<str>::as_ref(rhs),
+                );
+                $crate::bassert_internal!(
+                    @check [panic]
+                    lhs_str.eq_ignore_ascii_case(rhs_str),
+                    $crate::internal::bassert_ieq_failed(
+                        ::core::concat!(stringify!($lhs), ".eq_ignore_ascii_case(", stringify!($rhs), ")"),
+                        [stringify!($lhs), stringify!($rhs)],
+                        [$crate::internal::Operand::new(&lhs), $crate::internal::Operand::new(&rhs)],
+                        [lhs_str, rhs_str],
+                        $crate::bassert_internal!(@args $($($arg)*)?),
+                    )
+                )
+            }
+        }
+    };
+}
+
+/// Asserts that a string contains another (like a log line containing a message), using [`str::contains`].
+///
+/// Both operands can be anything implementing `AsRef<str>`, like `&str` or `String`.
+/// When the needle is not found, both strings are printed in full
+/// (using their [`Debug`](core::fmt::Debug) representation, so leading or trailing whitespace stays visible):
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let log = "connection refused ";
+/// bassert_contains!(log, "timed out");
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `log.contains("timed out")`
+/// log: `"connection refused "`,
+/// "timed out": `"timed out"`
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the operands.
+#[macro_export]
+macro_rules! bassert_contains {
+    ($haystack:expr, $needle:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(
+            @contains true,
+            ::core::concat!(stringify!($haystack), ".contains(", stringify!($needle), ")"),
+            $haystack,
+            $needle
+            $(, $($arg)*)?
+        )
+    };
+}
+
+/// Asserts that a string does not contain another, using [`str::contains`].
+///
+/// This is the opposite of [`bassert_contains!`].
+/// When the needle is found, both strings are printed, followed by the byte offset of its first occurrence:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let output = "warning: unused variable";
+/// bassert_not_contains!(output, "warning");
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `!output.contains("warning")`
+/// output: `"warning: unused variable"`,
+/// "warning": `"warning"`
+/// found at byte offset `0`
+/// ```
+#[macro_export]
+macro_rules! bassert_not_contains {
+    ($haystack:expr, $needle:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(
+            @contains false,
+            ::core::concat!("!", stringify!($haystack), ".contains(", stringify!($needle), ")"),
+            $haystack,
+            $needle
+            $(, $($arg)*)?
+        )
+    };
+}
+
+/// Asserts that two maps (like [`HashMap`](std::collections::HashMap)s or [`BTreeMap`](alloc::collections::BTreeMap)s) are equal.
+///
+/// When they are not, instead of only printing both maps, the failure message lists
+/// the keys only present in one of them, and the values of the keys present in both whose values differ.
+/// All keys are printed in sorted order, so the message does not depend on the iteration order of the maps.
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// use std::collections::HashMap;
+///
+/// let actual = HashMap::from([("a", 1), ("b", 2), ("c", 3)]);
+/// let expected = HashMap::from([("a", 1), ("b", 20), ("d", 4)]);
+/// bassert_map_eq!(actual, expected);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `actual == expected`
+/// actual: `{"a": 1, "b": 2, "c": 3}`,
+/// expected: `{"a": 1, "b": 20, "d": 4}`
+/// keys only in left: `["c"]`
+/// keys only in right: `["d"]`
+/// different value for key `"b"`: left = `2`, right = `20`
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the maps.
+#[macro_export]
+macro_rules! bassert_map_eq {
+    ($lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => $crate::bassert_internal!(
+                @check [panic]
+                *lhs == *rhs,
+                $crate::internal::bassert_map_failed(
+                    stringify!($lhs),
+                    stringify!($rhs),
+                    lhs,
+                    rhs,
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+}
+
+/// Asserts that two collections contain the same elements, ignoring their order.
+///
+/// Both sides can be anything whose references can be iterated over (like a `Vec`, slice or `HashSet`),
+/// as long as the elements implement [`Debug`](core::fmt::Debug), [`Eq`] and [`Hash`](core::hash::Hash).
+/// They are compared as multisets: an element occurring twice on one side should occur twice on the other side as well.
+/// When they differ, the elements only present in one of them are listed,
+/// sorted by their `Debug` representation so the message does not depend on the iteration order of the collections:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let actual = vec![3, 1, 2, 2];
+/// let expected = vec![1, 2, 3, 4];
+/// bassert_set_eq!(actual, expected);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `actual == expected`
+/// actual: `[3, 1, 2, 2]`,
+/// expected: `[1, 2, 3, 4]`
+/// elements only in left: `[2]`
+/// elements only in right: `[4]`
+/// ```
+///
+/// Only available with the `std` feature, as the elements are counted using a `HashMap`.
+/// Like with [`bassert!`], a custom message can be passed after the collections.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! bassert_set_eq {
+    ($lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => $crate::internal::bassert_set_eq(
+                [stringify!($lhs), stringify!($rhs)],
+                $crate::internal::Operand::new(&lhs),
+                $crate::internal::Operand::new(&rhs),
+                lhs,
+                rhs,
+                $crate::bassert_internal!(@args $($($arg)*)?),
+            ),
+        }
+    };
+}
+
+/// Asserts that every element of the first collection is contained in the second one.
+///
+/// Like with [`bassert_set_eq!`], both sides can be anything whose references can be iterated over,
+/// with elements implementing [`Debug`](core::fmt::Debug), [`Eq`] and [`Hash`](core::hash::Hash).
+/// Extra elements in the second collection are fine, and the number of occurrences of an element does not matter.
+/// When it fails, the elements of the first collection missing from the second one are listed:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let required = ["id", "name", "email"];
+/// let fields = vec!["id", "created_at", "name"];
+/// bassert_subset!(required, fields);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `required.is_subset(fields)`
+/// required: `["id", "name", "email"]`,
+/// fields: `["id", "created_at", "name"]`
+/// elements only in left: `["email"]`
+/// ```
+///
+/// Only available with the `std` feature.
+/// Like with [`bassert!`], a custom message can be passed after the collections.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! bassert_subset {
+    ($lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@subset true, ".is_subset(", $lhs, $rhs $(, $($arg)*)?)
+    };
+}
+
+/// Asserts that every element of the second collection is contained in the first one.
+///
+/// This is [`bassert_subset!`] with its arguments swapped, so the elements missing from the first collection are listed as
+/// `` elements only in right ``.
+/// ```
+/// # #[macro_use] extern crate bassert;
+/// let fields = vec!["id", "created_at", "name"];
+/// bassert_superset!(fields, ["id", "name"]);
+/// ```
+///
+/// Only available with the `std` feature.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! bassert_superset {
+    ($lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@subset false, ".is_superset(", $lhs, $rhs $(, $($arg)*)?)
+    };
+}
+
+/// Asserts that no element of a collection occurs more than once, like the ids handed out by a generator.
+///
+/// Like with [`bassert_set_eq!`], the collection can be anything whose references can be iterated over,
+/// with elements implementing [`Debug`](core::fmt::Debug), [`Eq`] and [`Hash`](core::hash::Hash).
+/// When an element repeats, the first duplicate is printed with the indices of both of its occurrences:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let ids = vec![1, 3, 2, 3];
+/// bassert_unique!(ids);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `ids.is_unique()`
+/// ids: `[1, 3, 2, 3]`
+/// element at index 1 (`3`) == element at index 3 (`3`)
+/// ```
+///
+/// Only available with the `std` feature, as the elements are looked up using a `HashMap`.
+/// Like with [`bassert!`], a custom message can be passed after the collection.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! bassert_unique {
+    ($collection:expr $(, $($arg:tt)*)?) => {
+        match &$collection {
+            collection => $crate::internal::bassert_unique(
+                ::core::concat!(stringify!($collection), ".is_unique()"),
+                stringify!($collection),
+                $crate::internal::Operand::new(&collection),
+                collection,
+                $crate::bassert_internal!(@args $($($arg)*)?),
+            ),
+        }
+    };
+}
+
+/// Asserts that two byte buffers (anything implementing `AsRef<[u8]>`) are equal.
+///
+/// When they are not, instead of printing both as lists of decimal numbers,
+/// the failure message contains a side-by-side hex dump of the rows around the first difference,
+/// with the rows containing differences marked by a `>`:
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// let actual: Vec<u8> = (0..20).collect();
+/// let mut expected = actual.clone();
+/// expected[10] = 0xff;
+/// bassert_bytes_eq!(actual, expected);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `actual == expected`
+/// first difference at offset 10 (0xa):
+///   00000000  00 01 02 03 04 05 06 07  |  00 01 02 03 04 05 06 07
+/// > 00000008  08 09 0a 0b 0c 0d 0e 0f  |  08 09 ff 0b 0c 0d 0e 0f
+///   00000010  10 11 12 13              |  10 11 12 13
+/// ```
+///
+/// Like with [`bassert!`], a custom message can be passed after the buffers.
+#[macro_export]
+macro_rules! bassert_bytes_eq {
+    ($lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        match (
+            ::core::convert::AsRef::<[u8]>::as_ref(&$lhs),
+            ::core::convert::AsRef::<[u8]>::as_ref(&$rhs),
+        ) {
+            (lhs, rhs) => $crate::bassert_internal!(
+                @check [panic]
+                lhs == rhs,
+                $crate::internal::bassert_bytes_failed(
+                    stringify!($lhs),
+                    stringify!($rhs),
+                    lhs,
+                    rhs,
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+}
+
+/// Asserts that two integers (like bitmasks or flags) are equal, printing them in hexadecimal and binary.
+///
+/// Unlike with `==`, the failure message shows the bit structure of both integers,
+/// as well as their XOR (the bits which differ):
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// const MASK: u8 = 0x7f;
+/// let flags: u8 = 0x3f;
+/// bassert_bits_eq!(flags, MASK);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `flags == MASK`
+/// flags: `0x3f`,
+/// MASK: `0x7f`,
+/// differing bits: `0x40`
+/// bits: left = `0b00111111`, right = `0b01111111`
+/// ```
+///
+/// The binary representations are padded to the width of the type, so their bits line up.
+/// Like with [`bassert!`], a custom message can be passed after the integers.
+#[macro_export]
+macro_rules! bassert_bits_eq {
+    ($lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => $crate::bassert_internal!(
+                @check [panic]
+                *lhs == *rhs,
+                $crate::internal::bassert_bits_failed(
+                    [stringify!($lhs), stringify!($rhs)],
+                    *lhs,
+                    *rhs,
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+}
+
+/// A version of [`bassert!`] which prints the value of every sub-expression when the assertion fails.
+///
+/// Only available with the `power` feature, as it is implemented by a procedural macro.
+/// ```should_panic
+/// # #[macro_use] extern crate bassert;
+/// struct User {
+///     age: u32,
+/// }
+/// let user = User { age: 3 };
+/// let bonus = 2;
+/// let limit = 10;
+/// power_bassert!(user.age + bonus > limit);
+/// ```
+/// This will panic with the message:
+/// ```text
+/// assertion failed: `user.age + bonus > limit`
+/// user.age: `3`,
+/// bonus: `2`,
+/// user.age + bonus: `5`,
+/// limit: `10`
+/// ```
+///
+/// The values are printed in the order they were evaluated,
+/// including those of method calls, function calls and indexing, like `` v.len(): `3` ``.
+/// Sub-expressions whose type does not implement [`Debug`](core::fmt::Debug) are skipped, as are literals.
+/// Because the values are formatted while evaluating the expression, this is slower than [`bassert!`] when the assertion holds.
+///
+/// A custom message can be passed like with [`bassert!`].
+/// Assertions using `~=`, `in`, `=` or chained comparisons, and expressions containing
+/// closures, blocks or control flow at the top level, are passed to [`bassert!`] unchanged.
+#[cfg(feature = "power")]
+#[macro_export]
+macro_rules! power_bassert {
+    ($($tokens:tt)+) => {
+        $crate::internal::power_bassert_impl!(($crate) $($tokens)+)
+    };
+}
+
+// This macro is only used internally in another macro
+#[macro_export]
+#[doc(hidden)]
+#[allow(unused_macros)]
+macro_rules! bassert_internal {
+    // The `@parse` rules dispatch on the kind of assertion.
+    // `[panic]` mode panics when the assertion fails, `[try]` mode evaluates to a `Result` instead.
+    // A label before the assertion, like `bassert!("user age bounds"; age < 150)`,
+    // which is added to the failure of the assertion without it.
+    // It is matched as a single token first, as matching a `literal` directly
+    // would fail to compile assertions starting with a negation like `-x < y`.
+    (@parse [$mode:ident] $label:tt; $($tokens:tt)+) => {
+        $crate::bassert_internal!(@label [$mode] $label; $($tokens)+)
+    };
+
+    (@label [panic] $label:literal; $($tokens:tt)+) => {
+        if let ::core::result::Result::Err(failure) = $crate::bassert_internal!(@parse [try] $($tokens)+) {
+            $crate::internal::bassert_panic($crate::internal::bassert_label(failure, $label))
+        }
+    };
+
+    (@label [try] $label:literal; $($tokens:tt)+) => {
+        ::core::result::Result::map_err(
+            $crate::bassert_internal!(@parse [try] $($tokens)+),
+            |failure| $crate::internal::bassert_label(failure, $label),
+        )
+    };
+
+    (@label [val] $label:literal; $($tokens:tt)+) => {
+        ::core::compile_error!("`bassert_val!` does not support labels")
+    };
+
+    (@label [$mode:ident] $label:tt; $($tokens:tt)+) => {
+        ::core::compile_error!("the label of an assertion must be a string literal, like `bassert!(\"label\"; x < y)`")
+    };
+
+    (@parse [$mode:ident] #display $lhs:tt > $rhs:tt $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@compare [$mode] #display Gt > $lhs, $rhs $(, $($arg)*)?)
+    };
+
+    (@parse [$mode:ident] #display $lhs:tt < $rhs:tt $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@compare [$mode] #display Lt < $lhs, $rhs $(, $($arg)*)?)
+    };
+
+    (@parse [$mode:ident] #display $lhs:tt >= $rhs:tt $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@compare [$mode] #display Gte >= $lhs, $rhs $(, $($arg)*)?)
+    };
+
+    (@parse [$mode:ident] #display $lhs:tt <= $rhs:tt $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@compare [$mode] #display Lte <= $lhs, $rhs $(, $($arg)*)?)
+    };
+
+    (@parse [$mode:ident] #display $lhs:tt == $rhs:tt $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@compare [$mode] #display Eq == $lhs, $rhs $(, $($arg)*)?)
+    };
+
+    (@parse [$mode:ident] #display $lhs:tt != $rhs:tt $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@compare [$mode] #display Ne != $lhs, $rhs $(, $($arg)*)?)
+    };
+
+    (@parse [$mode:ident] #display $($rest:tt)*) => {
+        ::core::compile_error!(
+            "`#display` only supports comparisons of single tokens or parenthesized expressions, like `#display a == (b.path())`"
+        )
+    };
+
+    (@parse [$mode:ident] $lhs:tt > $rhs:tt $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@compare [$mode] Gt > $lhs, $rhs $(, $($arg)*)?)
+    };
+
+    (@parse [$mode:ident] $lhs:tt < $rhs:tt $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@compare [$mode] Lt < $lhs, $rhs $(, $($arg)*)?)
+    };
+
+    (@parse [$mode:ident] $lhs:tt >= $rhs:tt $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@compare [$mode] Gte >= $lhs, $rhs $(, $($arg)*)?)
+    };
+
+    (@parse [$mode:ident] $lhs:tt <= $rhs:tt $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@compare [$mode] Lte <= $lhs, $rhs $(, $($arg)*)?)
+    };
+
+    (@parse [$mode:ident] $lhs:tt == $rhs:tt $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@compare [$mode] Eq == $lhs, $rhs $(, $($arg)*)?)
+    };
+
+    (@parse [$mode:ident] $lhs:tt != $rhs:tt $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@compare [$mode] Ne != $lhs, $rhs $(, $($arg)*)?)
+    };
+
+    (@parse [$mode:ident] $lhs:tt ~= $rhs:tt $(,)?) => {
+        $crate::bassert_internal!(@approx [$mode] $lhs, $rhs, ::core::option::Option::None)
+    };
+
+    (@parse [$mode:ident] $lhs:tt ~= $rhs:tt, eps = $eps:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(
+            @approx [$mode] $lhs,
+            $rhs,
+            ::core::option::Option::Some($eps)
+            $(, $($arg)*)?
+        )
+    };
+
+    (@parse [$mode:ident] $lhs:tt ~= $rhs:tt, rel = $rel:expr $(, $($arg:tt)*)?) => {
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => {
+                let rel = $rel;
+                $crate::bassert_internal!(
+                    @check [$mode]
+                    $crate::internal::ApproxFloat::approx_eq(
+                        *lhs,
+                        *rhs,
+                        $crate::internal::ApproxFloat::relative_tolerance(*lhs, *rhs, rel),
+                    ),
+                    $crate::internal::bassert_relative_approx_failed(
+                        stringify!($lhs),
+                        stringify!($rhs),
+                        *lhs,
+                        *rhs,
+                        rel,
+                        $crate::bassert_internal!(@args $($($arg)*)?),
+                    )
+                )
+            }
+        }
+    };
+
+    (@parse [$mode:ident] $lhs:tt ~= $rhs:tt, $($arg:tt)+) => {
+        $crate::bassert_internal!(@approx [$mode] $lhs, $rhs, ::core::option::Option::None, $($arg)+)
+    };
+
+    (@parse [$mode:ident] $val:tt in $range:expr $(, $($arg:tt)*)?) => {
+        match (&$val, &$range) {
+            (val, range) => $crate::bassert_internal!(
+                @check [$mode]
+                ::core::ops::RangeBounds::contains(range, val),
+                $crate::internal::bassert_range_failed(
+                    stringify!($val),
+                    stringify!($range),
+                    $crate::internal::Operand::new(&val),
+                    $crate::internal::Operand::new(&range),
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+
+    // Anything else is either a match (`=`), a conjunction (`&&`),
+    // a comparison of operands which are not a single token tree,
+    // a chained comparison, a negation (`!`) or a plain boolean expression.
+    (@parse [$mode:ident] $($tokens:tt)+) => {
+        $crate::bassert_internal!(@split_start [$mode] $($tokens)+)
+    };
+
+    // Matches, reached when the expression contains a top-level `=` or `if`.
+    // `bassert_matches!` matches the value itself (like `assert_matches!` does),
+    // only borrowing it to describe the failure.
+    (@pattern [matches] $lhs:pat = $rhs:expr $(, $($arg:tt)*)?) => {
+        match $rhs {
+            $lhs => {}
+            #[allow(unreachable_patterns)]
+            ref rhs => $crate::internal::bassert_panic($crate::internal::bassert_match_failed(
+                $crate::Kind::Match,
+                stringify!($lhs),
+                ::core::option::Option::None,
+                stringify!($rhs),
+                $crate::internal::Operand::new(&rhs),
+                $crate::bassert_internal!(@error rhs),
+                $crate::bassert_internal!(@args $($($arg)*)?),
+            )),
+        }
+    };
+
+    (@pattern [$mode:ident] $lhs:pat = $rhs:expr $(, $($arg:tt)*)?) => {
+        match &$rhs {
+            rhs => $crate::bassert_internal!(
+                @check [$mode]
+                ::core::matches!(rhs, $lhs),
+                $crate::internal::bassert_match_failed(
+                    $crate::Kind::Match,
+                    stringify!($lhs),
+                    ::core::option::Option::None,
+                    stringify!($rhs),
+                    $crate::internal::Operand::new(&rhs),
+                    $crate::bassert_internal!(@error rhs),
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+
+    // A guard cannot be matched as an `expr` followed by `=`,
+    // so its tokens are collected up to the top-level `=` instead.
+    // For patterns ending in a tuple variant or struct (like `Some(x)` or `Point { x, y }`),
+    // the bindings are printed when the guard fails.
+    // The tokens of the pattern are forwarded as-is, so it is stringified exactly like it was written.
+    (@pattern [$mode:ident] $path:tt $fields:tt if $($rest:tt)+) => {
+        $crate::bassert_internal!(@guard_bindings [$mode] [$path $fields] $fields $($rest)+)
+    };
+
+    (@pattern [$mode:ident] $a:tt $path:tt $fields:tt if $($rest:tt)+) => {
+        $crate::bassert_internal!(@guard_bindings [$mode] [$a $path $fields] $fields $($rest)+)
+    };
+
+    (@pattern [$mode:ident] $a:tt $b:tt $path:tt $fields:tt if $($rest:tt)+) => {
+        $crate::bassert_internal!(@guard_bindings [$mode] [$a $b $path $fields] $fields $($rest)+)
+    };
+
+    (@pattern [$mode:ident] $a:tt $b:tt $c:tt $path:tt $fields:tt if $($rest:tt)+) => {
+        $crate::bassert_internal!(
+            @guard_bindings [$mode] [$a $b $c $path $fields] $fields $($rest)+
+        )
+    };
+
+    (@pattern [$mode:ident] $a:tt $b:tt $c:tt $d:tt $path:tt $fields:tt if $($rest:tt)+) => {
+        $crate::bassert_internal!(
+            @guard_bindings [$mode] [$a $b $c $d $path $fields] $fields $($rest)+
+        )
+    };
+
+    (@pattern [$mode:ident] $lhs:pat if $($rest:tt)+) => {
+        $crate::bassert_internal!(@guard [$mode] [$lhs] [] [] $($rest)+)
+    };
+
+    // Fails with `$failure` unless `$ok` holds.
+    // The failure is only constructed when the assertion does not hold.
+    (@check [panic] $ok:expr, $failure:expr) => {
+        if !$ok {
+            $crate::internal::bassert_panic($failure)
+        }
+    };
+
+    // `bassert_val!` handles comparisons in its own `@compare` rule, so any other assertion ends up here.
+    (@check [val] $ok:expr, $failure:expr) => {
+        ::core::compile_error!("`bassert_val!` only supports comparisons like `a == b` or `a < b`")
+    };
+
+    (@check [try] $ok:expr, $failure:expr) => {
+        if $ok {
+            ::core::result::Result::<(), $crate::BassertFailure>::Ok(())
+        } else {
+            ::core::result::Result::Err($failure)
+        }
+    };
+
+    // Two assertions checked one after the other.
+    (@both [panic] $first:expr, $second:expr) => {{
+        $first;
+        $second;
+    }};
+
+    (@both [val] $first:expr, $second:expr) => {
+        ::core::compile_error!("`bassert_val!` only supports comparisons like `a == b` or `a < b`")
+    };
+
+    (@both [try] $first:expr, $second:expr) => {
+        match $first {
+            ::core::result::Result::Ok(()) => $second,
+            ::core::result::Result::Err(failure) => ::core::result::Result::Err(failure),
+        }
+    };
+
+    // The (optional) custom message.
+    (@args) => {
+        ::core::option::Option::None
+    };
+
+    // A closure building the message, which is only called if the assertion fails.
+    (@args || $($body:tt)+) => {
+        ::core::option::Option::Some(::core::format_args!("{}", $crate::internal::bassert_message(|| $($body)+)))
+    };
+
+    (@args move || $($body:tt)+) => {
+        ::core::option::Option::Some(::core::format_args!("{}", $crate::internal::bassert_message(move || $($body)+)))
+    };
+
+    (@args $($arg:tt)+) => {
+        ::core::option::Option::Some(::core::format_args!($($arg)+))
+    };
+
+    (@approx [$mode:ident] $lhs:tt, $rhs:tt, $eps:expr $(, $($arg:tt)*)?) => {
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => {
+                let tolerance = $crate::internal::ApproxFloat::tolerance(*lhs, *rhs, $eps);
+                $crate::bassert_internal!(
+                    @check [$mode]
+                    $crate::internal::ApproxFloat::approx_eq(*lhs, *rhs, tolerance),
+                    $crate::internal::bassert_approx_failed(
+                        stringify!($lhs),
+                        stringify!($rhs),
+                        *lhs,
+                        *rhs,
+                        tolerance,
+                        $crate::bassert_internal!(@args $($($arg)*)?),
+                    )
+                )
+            }
+        }
+    };
+
+    // `None` is a unit variant rather than a binding, which would not even have a known type.
+    (@guard_bindings [$mode:ident] [$($lhs:tt)+] (None) $($rest:tt)+) => {
+        $crate::bassert_internal!(@guard [$mode] [$($lhs)+] [] [] $($rest)+)
+    };
+
+    (@guard_bindings [$mode:ident] [$($lhs:tt)+] ($($binding:ident),+ $(,)?) $($rest:tt)+) => {
+        $crate::bassert_internal!(@guard [$mode] [$($lhs)+] [$($binding),+] [] $($rest)+)
+    };
+
+    // Like `Some(n @ 1..=5)`.
+    (@guard_bindings [$mode:ident] [$($lhs:tt)+] ($binding:ident @ $($subpattern:tt)+) $($rest:tt)+) => {
+        $crate::bassert_internal!(@guard [$mode] [$($lhs)+] [$binding] [] $($rest)+)
+    };
+
+    (@guard_bindings [$mode:ident] [$($lhs:tt)+] { $($binding:ident),+ $(, ..)? } $($rest:tt)+) => {
+        $crate::bassert_internal!(@guard [$mode] [$($lhs)+] [$($binding),+] [] $($rest)+)
+    };
+
+    (@guard_bindings [$mode:ident] [$($lhs:tt)+] $fields:tt $($rest:tt)+) => {
+        $crate::bassert_internal!(@guard [$mode] [$($lhs)+] [] [] $($rest)+)
+    };
+
+    // When the guard fails, the borrowed value is matched again (without the guard) to print the bindings.
+    (@guard [matches] [$lhs:pat] [$($binding:ident),*] [$($guard:tt)+] = $rhs:expr $(, $($arg:tt)*)?) => {
+        match $rhs {
+            $lhs if $($guard)+ => {}
+            #[allow(unreachable_patterns)]
+            ref rhs => $crate::internal::bassert_panic(match rhs {
+                #[allow(unused_variables)]
+                $lhs => {
+                    #[allow(unused_mut)]
+                    let mut failure = $crate::bassert_internal!(
+                        @guard_failed [$lhs] [$($guard)+] $rhs, rhs, true $(, $($arg)*)?
+                    );
+                    $(
+                        $crate::internal::bassert_push_binding(
+                            &mut failure,
+                            stringify!($binding),
+                            $crate::internal::Operand::new(&&$binding),
+                        );
+                    )*
+                    failure
+                }
+                #[allow(unreachable_patterns)]
+                _ => $crate::bassert_internal!(
+                    @guard_failed [$lhs] [$($guard)+] $rhs, rhs, false $(, $($arg)*)?
+                ),
+            }),
+        }
+    };
+
+    (@guard [$mode:ident] [$lhs:pat] [$($binding:ident),*] [$($guard:tt)+] = $rhs:expr $(, $($arg:tt)*)?) => {
+        match &$rhs {
+            rhs => match rhs {
+                $lhs => $crate::bassert_internal!(
+                    @check [$mode]
+                    $($guard)+,
+                    {
+                        #[allow(unused_mut)]
+                        let mut failure = $crate::bassert_internal!(
+                            @guard_failed [$lhs] [$($guard)+] $rhs, rhs, true $(, $($arg)*)?
+                        );
+                        $(
+                            $crate::internal::bassert_push_binding(
+                                &mut failure,
+                                stringify!($binding),
+                                $crate::internal::Operand::new(&&$binding),
+                            );
+                        )*
+                        failure
+                    }
+                ),
+                #[allow(unreachable_patterns)]
+                _ => $crate::bassert_internal!(
+                    @check [$mode]
+                    false,
+                    $crate::bassert_internal!(
+                        @guard_failed [$lhs] [$($guard)+] $rhs, rhs, false $(, $($arg)*)?
+                    )
+                ),
+            },
+        }
+    };
+
+    (@guard [$mode:ident] [$lhs:pat] [$($binding:ident),*] [$($guard:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::bassert_internal!(@guard [$mode] [$lhs] [$($binding),*] [$($guard)* $next] $($rest)*)
+    };
+
+    (@guard_failed [$lhs:pat] [$($guard:tt)+] $rhs:tt, $rhs_var:ident, $pattern_matched:expr $(, $($arg:tt)*)?) => {
+        $crate::internal::bassert_match_failed(
+            $crate::Kind::Match,
+            stringify!($lhs),
+            ::core::option::Option::Some($crate::internal::MatchGuard {
+                guarded_pattern: ::core::concat!(stringify!($lhs), " if ", stringify!($($guard)+)),
+                guard: stringify!($($guard)+),
+                pattern_matched: $pattern_matched,
+            }),
+            stringify!($rhs),
+            $crate::internal::Operand::new(&$rhs_var),
+            $crate::bassert_internal!(@error $rhs_var),
+            $crate::bassert_internal!(@args $($($arg)*)?),
+        )
+    };
+
+    // `bassert_ok!`, `bassert_err!`, `bassert_some!` and `bassert_none!`.
+    (@unwrap $pat:pat => $out:expr, $pattern:literal, $value:expr $(, $($arg:tt)*)?) => {
+        match $value {
+            $pat => $out,
+            #[allow(unreachable_patterns)]
+            value => $crate::internal::bassert_panic($crate::internal::bassert_match_failed(
+                $crate::Kind::Match,
+                $pattern,
+                ::core::option::Option::None,
+                stringify!($value),
+                $crate::internal::Operand::new(&&value),
+                $crate::bassert_internal!(@error &value),
+                $crate::bassert_internal!(@args $($($arg)*)?),
+            )),
+        }
+    };
+
+    // `bassert_is_ok!`, `bassert_is_err!`, `bassert_is_some!` and `bassert_is_none!`.
+    (@is $method:ident, $expected:literal, $actual:literal, $value:expr $(, $($arg:tt)*)?) => {
+        match &$value {
+            value => {
+                if !value.$method() {
+                    $crate::internal::bassert_panic($crate::internal::bassert_variant_failed(
+                        ::core::concat!(stringify!($value), ".", stringify!($method), "()"),
+                        stringify!($value),
+                        $crate::internal::Operand::new(&value),
+                        [$expected, $actual],
+                        $crate::bassert_internal!(@args $($($arg)*)?),
+                    ))
+                }
+            }
+        }
+    };
+
+    // `bassert_pred!`, with the predicate stringified for the failure message.
+    (@pred $expr:expr, $value:expr, $pred:expr $(, $($arg:tt)*)?) => {
+        match &$value {
+            value => {
+                if !($pred)(value) {
+                    $crate::internal::bassert_panic($crate::internal::bassert_pred_failed(
+                        $expr,
+                        stringify!($pred),
+                        stringify!($value),
+                        $crate::internal::Operand::new(&value),
+                        $crate::bassert_internal!(@args $($($arg)*)?),
+                    ))
+                }
+            }
+        }
+    };
+
+    // `bassert_matches!`, collecting the tokens of the pattern (and its guard) up to the top-level `,`.
+    (@matches [$value:expr] [$($pat:tt)+] $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(@pattern [matches] $($pat)+ = $value $(, $($arg)*)?)
+    };
+
+    (@matches [$value:expr] [$($pat:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::bassert_internal!(@matches [$value] [$($pat)* $next] $($rest)*)
+    };
+
+    // `bassert_let!`, collecting the tokens of the pattern up to the top-level `=`.
+    (@let [$($pat:tt)+] = $rhs:expr $(, $($arg:tt)*)?) => {
+        match $rhs {
+            $($pat)+ => $crate::bassert_internal!(@let_last $($pat)+),
+            #[allow(unreachable_patterns)]
+            rhs => $crate::internal::bassert_panic($crate::internal::bassert_match_failed(
+                $crate::Kind::Match,
+                stringify!($($pat)+),
+                ::core::option::Option::None,
+                stringify!($rhs),
+                $crate::internal::Operand::new(&&rhs),
+                $crate::bassert_internal!(@error &rhs),
+                $crate::bassert_internal!(@args $($($arg)*)?),
+            )),
+        }
+    };
+
+    (@let [$($pat:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::bassert_internal!(@let [$($pat)* $next] $($rest)*)
+    };
+
+    // The bindings are those of the last token tree of the pattern (like the `(v)` of `Ok(v)`),
+    // which is only looked into when it is not one of multiple comma-separated patterns.
+    (@let_last $(, $($rest:tt)*)?) => {
+        ::core::compile_error!("the bindings of this pattern are not supported by `bassert_let!`")
+    };
+
+    (@let_last $first:tt $($rest:tt)+) => {
+        $crate::bassert_internal!(@let_last $($rest)+)
+    };
+
+    (@let_last $last:tt) => {
+        $crate::bassert_internal!(@let_fields $last)
+    };
+
+    (@let_fields ($binding:ident $(,)?)) => {
+        $binding
+    };
+
+    (@let_fields ($($binding:ident),+ $(,)?)) => {
+        ($($binding),+)
+    };
+
+    (@let_fields { $binding:ident $(, ..)? }) => {
+        $binding
+    };
+
+    (@let_fields { $($binding:ident),+ $(, ..)? }) => {
+        ($($binding),+)
+    };
+
+    (@let_fields ($($inner:tt)+)) => {
+        $crate::bassert_internal!(@let_last $($inner)+)
+    };
+
+    (@let_fields $other:tt) => {
+        ::core::compile_error!("the bindings of this pattern are not supported by `bassert_let!`")
+    };
+
+    // `bassert_ptr_eq!` and `bassert_ptr_ne!`.
+    (@ptr $eq:literal, $expr:expr, $lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        match ($lhs, $rhs) {
+            (lhs, rhs) => $crate::bassert_internal!(
+                @check [panic]
+                ::core::ptr::eq(lhs, rhs) == $eq,
+                $crate::internal::bassert_ptr_failed(
+                    $expr,
+                    [stringify!($lhs), stringify!($rhs)],
+                    $crate::internal::Operand::new(&lhs),
+                    $crate::internal::Operand::new(&rhs),
+                    [$crate::internal::address(lhs), $crate::internal::address(rhs)],
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+
+    // `bassert_all!` and `bassert_any!`: split the assertions on the commas between them,
+    // collecting each one into a bracketed group.
+    (@list [$which:ident] [$($done:tt)*] [$($current:tt)+] , $($rest:tt)*) => {
+        $crate::bassert_internal!(@list [$which] [$($done)* [$($current)+]] [] $($rest)*)
+    };
+
+    (@list [$which:ident] [$($done:tt)*] [$($current:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::bassert_internal!(@list [$which] [$($done)*] [$($current)* $next] $($rest)*)
+    };
+
+    (@list [$which:ident] [$($done:tt)*] [$($current:tt)+]) => {
+        $crate::bassert_internal!(@list [$which] [$($done)* [$($current)+]] [])
+    };
+
+    (@list [all] [$([$($assertion:tt)+])+] []) => {{
+        let mut group = $crate::BassertGroup::new();
+        $(group.check($crate::try_bassert!($($assertion)+));)+
+        group.assert_all();
+    }};
+
+    (@list [any] [$([$($assertion:tt)+])+] []) => {{
+        let mut group = $crate::BassertGroup::new();
+        'bassert_any: {
+            $(
+                let result = $crate::try_bassert!($($assertion)+);
+                let held = result.is_ok();
+                group.check(result);
+                if held {
+                    break 'bassert_any;
+                }
+            )+
+            group.assert_any();
+        }
+    }};
+
+    // `bassert_contains!` and `bassert_not_contains!`.
+    (@contains $contains:literal, $expr:expr, $haystack:expr, $needle:expr $(, $($arg:tt)*)?) => {
+        match (&$haystack, &$needle) {
+            (haystack, needle) => {
+                let (haystack_str, needle_str): (&str, &str) = (
+                    ::core::convert::AsRef::<str>::as_ref(haystack),
+                    ::core::convert::AsRef::<str>::as_ref(needle),
+                );
+                $crate::bassert_internal!(
+                    @check [panic]
+                    haystack_str.contains(needle_str) == $contains,
+                    $crate::internal::bassert_contains_failed(
+                        $expr,
+                        [stringify!($haystack), stringify!($needle)],
+                        [$crate::internal::Operand::new(&haystack), $crate::internal::Operand::new(&needle)],
+                        haystack_str.find(needle_str),
+                        $crate::bassert_internal!(@args $($($arg)*)?),
+                    )
+                )
+            }
+        }
+    };
+
+    // `bassert_subset!` and `bassert_superset!`.
+    (@subset $lhs_is_subset:literal, $method:literal, $lhs:expr, $rhs:expr $(, $($arg:tt)*)?) => {
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => $crate::internal::bassert_subset(
+                ::core::concat!(stringify!($lhs), $method, stringify!($rhs), ")"),
+                [stringify!($lhs), stringify!($rhs)],
+                [$crate::internal::Operand::new(&lhs), $crate::internal::Operand::new(&rhs)],
+                lhs,
+                rhs,
+                $lhs_is_subset,
+                $crate::bassert_internal!(@args $($($arg)*)?),
+            ),
+        }
+    };
+
+    // Chained comparisons evaluate each of the three operands exactly once.
+    (@chain [$mode:ident] $a:tt $op1:tt $b:tt $op2:tt $c:tt $(, $($arg:tt)*)?) => {
+        match (&$a, &$b, &$c) {
+            (a, b, c) => {
+                let first_holds = a $op1 b;
+                $crate::bassert_internal!(
+                    @check [$mode]
+                    first_holds && b $op2 c,
+                    $crate::internal::bassert_chain_failed(
+                        [
+                            $crate::bassert_internal!(@kind $op1),
+                            $crate::bassert_internal!(@kind $op2),
+                        ],
+                        [stringify!($a), stringify!($b), stringify!($c)],
+                        [
+                            $crate::bassert_internal!(@operand a),
+                            $crate::bassert_internal!(@operand b),
+                            $crate::bassert_internal!(@operand c),
+                        ],
+                        if first_holds { 1 } else { 0 },
+                        if first_holds {
+                            ::core::cmp::PartialOrd::partial_cmp(b, c).is_none()
+                        } else {
+                            ::core::cmp::PartialOrd::partial_cmp(a, b).is_none()
+                        },
+                        $crate::bassert_internal!(@args $($($arg)*)?),
+                    )
+                )
+            }
+        }
+    };
+
+    // Whether the operands of a failed ordering comparison could not be compared at all,
+    // like when one of them is `NaN`.
+    (@incomparable Eq $lhs:expr, $rhs:expr) => {
+        false
+    };
+
+    (@incomparable Ne $lhs:expr, $rhs:expr) => {
+        false
+    };
+
+    (@incomparable $kind:ident $lhs:expr, $rhs:expr) => {
+        ::core::cmp::PartialOrd::partial_cmp($lhs, $rhs).is_none()
+    };
+
+    (@kind <) => {
+        $crate::Kind::Lt
+    };
+
+    (@kind <=) => {
+        $crate::Kind::Lte
+    };
+
+    (@kind >) => {
+        $crate::Kind::Gt
+    };
+
+    (@kind >=) => {
+        $crate::Kind::Gte
+    };
+
+    (@conjunction_rhs [$mode:ident] [$($lhs:tt)+] [$($rhs:tt)+] $(,)?) => {
+        $crate::bassert_internal!(
+            @both [$mode]
+            $crate::bassert_internal!(
+                @parse [$mode] $($lhs)+,
+                "left conjunct of `{}` failed",
+                $crate::internal::normalize(stringify!($($lhs)+ && $($rhs)+))
+            ),
+            $crate::bassert_internal!(
+                @parse [$mode] $($rhs)+,
+                "right conjunct of `{}` failed",
+                $crate::internal::normalize(stringify!($($lhs)+ && $($rhs)+))
+            )
+        )
+    };
+
+    (@conjunction_rhs [$mode:ident] [$($lhs:tt)+] [$($rhs:tt)+] , $($arg:tt)+) => {
+        $crate::bassert_internal!(
+            @both [$mode]
+            $crate::bassert_internal!(
+                @parse [$mode] $($lhs)+,
+                "left conjunct of `{}` failed: {}",
+                $crate::internal::normalize(stringify!($($lhs)+ && $($rhs)+)),
+                ::core::option::Option::unwrap($crate::bassert_internal!(@args $($arg)+))
+            ),
+            $crate::bassert_internal!(
+                @parse [$mode] $($rhs)+,
+                "right conjunct of `{}` failed: {}",
+                $crate::internal::normalize(stringify!($($lhs)+ && $($rhs)+)),
+                ::core::option::Option::unwrap($crate::bassert_internal!(@args $($arg)+))
+            )
+        )
+    };
+
+    (@conjunction_rhs [$mode:ident] [$($lhs:tt)+] [$($rhs:tt)*] || $($rest:tt)*) => {
+        $crate::bassert_internal!(@unary [$mode] $($lhs)+ && $($rhs)* || $($rest)*)
+    };
+
+    (@conjunction_rhs [$mode:ident] [$($lhs:tt)+] [$($rhs:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::bassert_internal!(@conjunction_rhs [$mode] [$($lhs)+] [$($rhs)* $next] $($rest)*)
+    };
+
+    // Splits an expression on its top-level comparison operators,
+    // so operands which are not a single token tree (like `a + 1 == b * 2`) can be checked as well.
+    // The brackets hold the operands and operators seen so far (as `[operand] {operator}`),
+    // the tokens of the current operand, a `<` for every unclosed list of generic arguments
+    // (whose `<`, `>` and `,` tokens are part of the operand, like in `Vec::<i32>::new()`),
+    // and whether the previous token was a `::`.
+    // The current token is matched on using a copy in parentheses,
+    // so the original one (with its spacing) ends up in the stringified operand.
+    (@split_start [$mode:ident] $first:tt $($rest:tt)*) => {
+        $crate::bassert_internal!(@split [$mode] [] [] [] [] ($first) $first $($rest)*)
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)*] [$($depth:tt)+] [$($colons:tt)?] (<) $token:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(
+            @split [$mode] [$($done)*] [$($operand)* $token] [< $($depth)+] [] $(($next) $next $($rest)*)?
+        )
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)*] [$open:tt $($depth:tt)*] [$($colons:tt)?] (>) $token:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(
+            @split [$mode] [$($done)*] [$($operand)* $token] [$($depth)*] [] $(($next) $next $($rest)*)?
+        )
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)*] [$open1:tt $open2:tt $($depth:tt)*] [$($colons:tt)?] (>>) $token:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(
+            @split [$mode] [$($done)*] [$($operand)* $token] [$($depth)*] [] $(($next) $next $($rest)*)?
+        )
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)*] [$($depth:tt)+] [$($colons:tt)?] ($copy:tt) $token:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(
+            @split [$mode] [$($done)*] [$($operand)* $token] [$($depth)+] [] $(($next) $next $($rest)*)?
+        )
+    };
+
+    // Turbofish and qualified paths (like `<T as Default>::default()`) open a list of generic arguments.
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)*] [] [::] (<) $token:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(
+            @split [$mode] [$($done)*] [$($operand)* $token] [<] [] $(($next) $next $($rest)*)?
+        )
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [] [] [] (<) $token:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(@split [$mode] [$($done)*] [$token] [<] [] $(($next) $next $($rest)*)?)
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)*] [] [$($colons:tt)?] (::) $token:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(
+            @split [$mode] [$($done)*] [$($operand)* $token] [] [::] $(($next) $next $($rest)*)?
+        )
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)+] [] [] (==) $token:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(@split [$mode] [$($done)* [$($operand)+] {$token}] [] [] [] $(($next) $next $($rest)*)?)
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)+] [] [] (!=) $token:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(@split [$mode] [$($done)* [$($operand)+] {$token}] [] [] [] $(($next) $next $($rest)*)?)
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)+] [] [] (<) $token:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(@split [$mode] [$($done)* [$($operand)+] {$token}] [] [] [] $(($next) $next $($rest)*)?)
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)+] [] [] (<=) $token:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(@split [$mode] [$($done)* [$($operand)+] {$token}] [] [] [] $(($next) $next $($rest)*)?)
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)+] [] [] (>) $token:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(@split [$mode] [$($done)* [$($operand)+] {$token}] [] [] [] $(($next) $next $($rest)*)?)
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)+] [] [] (>=) $token:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(@split [$mode] [$($done)* [$($operand)+] {$token}] [] [] [] $(($next) $next $($rest)*)?)
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)+] [] [] (~) $tilde:tt $eq:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(@split [$mode] [$($done)* [$($operand)+] {$tilde $eq}] [] [] [] $(($next) $next $($rest)*)?)
+    };
+
+    // Each side of a conjunction is checked as an assertion of its own.
+    (@split [$mode:ident] [$([$($done_operand:tt)+] {$($done_op:tt)+})*] [$($operand:tt)+] [] [] (&&) $token:tt $($rest:tt)+) => {
+        $crate::bassert_internal!(
+            @conjunction_rhs [$mode] [$($($done_operand)+ $($done_op)+)* $($operand)+] [] $($rest)+
+        )
+    };
+
+    // Patterns (and their guards) can only be parsed once it is known they are one,
+    // as the parser cannot backtrack from a failed attempt.
+    (@split [$mode:ident] [$([$($done_operand:tt)+] {$($done_op:tt)+})*] [$($operand:tt)+] [] [] (=) $token:tt $($rest:tt)+) => {
+        $crate::bassert_internal!(
+            @pattern [$mode] $($($done_operand)+ $($done_op)+)* $($operand)+ $token $($rest)+
+        )
+    };
+
+    (@split [$mode:ident] [$([$($done_operand:tt)+] {$($done_op:tt)+})*] [$($operand:tt)+] [] [] (if) $token:tt $($rest:tt)+) => {
+        $crate::bassert_internal!(
+            @pattern [$mode] $($($done_operand)+ $($done_op)+)* $($operand)+ $token $($rest)+
+        )
+    };
+
+    (@split [$mode:ident] [] [$($val:tt)+] [] [] (in) $token:tt $($rest:tt)+) => {
+        $crate::bassert_internal!(@split_in [$mode] [$($val)+] $($rest)+)
+    };
+
+    // These bind less tightly than comparisons (and `&&`), so the expression is a plain boolean expression.
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)*] [] [] (||) $token:tt $($rest:tt)*) => {
+        $crate::bassert_internal!(@split_bail [$mode] [$($done)*] $($operand)* $token $($rest)*)
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)*] [] [] (..) $token:tt $($rest:tt)*) => {
+        $crate::bassert_internal!(@split_range [$mode] [$($done)*] [$($operand)* $token] $($rest)*)
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)*] [] [] (..=) $token:tt $($rest:tt)*) => {
+        $crate::bassert_internal!(@split_range [$mode] [$($done)*] [$($operand)* $token] $($rest)*)
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)+] [] [] (,) $token:tt $($rest:tt)*) => {
+        $crate::bassert_internal!(@split_done [$mode] [$($done)*] [$($operand)+] $token $($rest)*)
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)*] [] [$($colons:tt)?] ($copy:tt) $token:tt $($next:tt $($rest:tt)*)?) => {
+        $crate::bassert_internal!(
+            @split [$mode] [$($done)*] [$($operand)* $token] [] [] $(($next) $next $($rest)*)?
+        )
+    };
+
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)+] [] [$($colons:tt)?]) => {
+        $crate::bassert_internal!(@split_done [$mode] [$($done)*] [$($operand)+])
+    };
+
+    // Unbalanced generic arguments are left for the compiler to report.
+    (@split [$mode:ident] [$($done:tt)*] [$($operand:tt)*] [$($depth:tt)+] [$($colons:tt)?]) => {
+        $crate::bassert_internal!(@split_bail [$mode] [$($done)*] $($operand)*)
+    };
+
+    // Without comparison operators, it is a negation or a plain boolean expression.
+    (@split_done [$mode:ident] [] [$($operand:tt)+] $($rest:tt)*) => {
+        $crate::bassert_internal!(@unary [$mode] $($operand)+ $($rest)*)
+    };
+
+    // Once parsed as expressions, the operands are single token trees
+    // which are checked by the `@parse` rules like any others.
+    (@split_done [$mode:ident] [[$lhs:expr] {$($op:tt)+}] [$rhs:expr] $($rest:tt)*) => {
+        $crate::bassert_internal!(@parse [$mode] $lhs $($op)+ $rhs $($rest)*)
+    };
+
+    (@split_done [$mode:ident] [[$a:expr] {<} [$b:expr] {<}] [$c:expr] $($rest:tt)*) => {
+        $crate::bassert_internal!(@chain [$mode] $a < $b < $c $($rest)*)
+    };
+
+    (@split_done [$mode:ident] [[$a:expr] {<} [$b:expr] {<=}] [$c:expr] $($rest:tt)*) => {
+        $crate::bassert_internal!(@chain [$mode] $a < $b <= $c $($rest)*)
+    };
+
+    (@split_done [$mode:ident] [[$a:expr] {<=} [$b:expr] {<}] [$c:expr] $($rest:tt)*) => {
+        $crate::bassert_internal!(@chain [$mode] $a <= $b < $c $($rest)*)
+    };
+
+    (@split_done [$mode:ident] [[$a:expr] {<=} [$b:expr] {<=}] [$c:expr] $($rest:tt)*) => {
+        $crate::bassert_internal!(@chain [$mode] $a <= $b <= $c $($rest)*)
+    };
+
+    (@split_done [$mode:ident] [[$a:expr] {>} [$b:expr] {>}] [$c:expr] $($rest:tt)*) => {
+        $crate::bassert_internal!(@chain [$mode] $a > $b > $c $($rest)*)
+    };
+
+    (@split_done [$mode:ident] [[$a:expr] {>} [$b:expr] {>=}] [$c:expr] $($rest:tt)*) => {
+        $crate::bassert_internal!(@chain [$mode] $a > $b >= $c $($rest)*)
+    };
+
+    (@split_done [$mode:ident] [[$a:expr] {>=} [$b:expr] {>}] [$c:expr] $($rest:tt)*) => {
+        $crate::bassert_internal!(@chain [$mode] $a >= $b > $c $($rest)*)
+    };
+
+    (@split_done [$mode:ident] [[$a:expr] {>=} [$b:expr] {>=}] [$c:expr] $($rest:tt)*) => {
+        $crate::bassert_internal!(@chain [$mode] $a >= $b >= $c $($rest)*)
+    };
+
+    // Other chains (like `a == b == c`) are left for the compiler to reject.
+    (@split_done [$mode:ident] [$($done:tt)+] [$($operand:tt)+] $($rest:tt)*) => {
+        $crate::bassert_internal!(@split_bail [$mode] [$($done)+] $($operand)+ $($rest)*)
+    };
+
+    // A range followed by a top-level `=` (or guard) is a range pattern, like `'a'..='z' = c`.
+    // Anything else containing one is a plain boolean expression.
+    (@split_range [$mode:ident] [$([$($done_operand:tt)+] {$($done_op:tt)+})*] [$($seen:tt)+] = $($rest:tt)+) => {
+        $crate::bassert_internal!(
+            @pattern [$mode] $($($done_operand)+ $($done_op)+)* $($seen)+ = $($rest)+
+        )
+    };
+
+    (@split_range [$mode:ident] [$([$($done_operand:tt)+] {$($done_op:tt)+})*] [$($seen:tt)+] if $($rest:tt)+) => {
+        $crate::bassert_internal!(
+            @pattern [$mode] $($($done_operand)+ $($done_op)+)* $($seen)+ if $($rest)+
+        )
+    };
+
+    (@split_range [$mode:ident] [$($done:tt)*] [$($seen:tt)+] , $($rest:tt)*) => {
+        $crate::bassert_internal!(@split_bail [$mode] [$($done)*] $($seen)+ , $($rest)*)
+    };
+
+    (@split_range [$mode:ident] [$($done:tt)*] [$($seen:tt)+] $token:tt $($rest:tt)*) => {
+        $crate::bassert_internal!(@split_range [$mode] [$($done)*] [$($seen)+ $token] $($rest)*)
+    };
+
+    (@split_range [$mode:ident] [$($done:tt)*] [$($seen:tt)+]) => {
+        $crate::bassert_internal!(@split_bail [$mode] [$($done)*] $($seen)+)
+    };
+
+    (@split_in [$mode:ident] [$val:expr] $($rest:tt)+) => {
+        $crate::bassert_internal!(@parse [$mode] $val in $($rest)+)
+    };
+
+    // Puts the tokens split so far back together, to be checked as a plain boolean expression.
+    (@split_bail [$mode:ident] [$([$($operand:tt)+] {$($op:tt)+})*] $($rest:tt)*) => {
+        $crate::bassert_internal!(@unary [$mode] $($($operand)+ $($op)+)* $($rest)*)
+    };
+
+    // The operand is passed through a function so that parenthesized operands such as
+    // `!(a == b)` don't trigger `unused_parens` as a match scrutinee.
+    (@unary [$mode:ident] ! $inner:expr $(, $($arg:tt)*)?) => {
+        match $crate::internal::identity($inner) {
+            inner => $crate::bassert_internal!(
+                @check [$mode]
+                !inner,
+                $crate::internal::bassert_not_failed(
+                    stringify!($inner),
+                    inner,
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    };
+
+    // Plain boolean expressions are tried last,
+    // so they never shadow the negation rule above.
+    (@unary [$mode:ident] $cond:expr $(, $($arg:tt)*)?) => {
+        $crate::bassert_internal!(
+            @check [$mode]
+            $cond,
+            $crate::internal::bassert_bool_failed(
+                stringify!($cond),
+                $crate::bassert_internal!(@args $($($arg)*)?),
+            )
+        )
+    };
+
+    // Only numeric operands and slices have a meaningful difference;
+    // for all other types this will resolve to the fallback returning `None`.
+    (@difference $lhs:expr, $rhs:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::internal::{NoDifference as _, NumericDifference as _, SliceDifference as _, StrDifference as _};
+        (&&$crate::internal::DifferenceWrap($lhs, $rhs)).bassert_difference()
+    }};
+
+    // The `Display` representation of the error when a `Result` fails to match;
+    // for all other types (and errors not implementing `Display`) this will resolve to the fallback returning `None`.
+    (@error $value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::internal::{ErrorDisplay as _, NoErrorDisplay as _};
+        (&&$crate::internal::ErrorWrap($value)).bassert_error_display()
+    }};
+
+    // An operand as printed in the failure message: using `Debug`, or `Display` after `#display`.
+    // Operands whose type does not implement `Debug` (including those of a generic type without a `Debug` bound)
+    // resolve to the fallback, which prints a placeholder instead.
+    (@operand $value:ident) => {{
+        #[allow(unused_imports)]
+        use $crate::internal::{DebugOperand as _, NoDebugOperand as _};
+        (&&$crate::internal::OperandWrap(&$value)).bassert_operand()
+    }};
+
+    (@operand #display $value:ident) => {
+        $crate::internal::Operand::display(&$crate::internal::Displayed($value))
+    };
+
+    // The left-hand side is bound by value, so it can be returned after the check.
+
+    (@compare [val] $(#$display:ident)? $kind:ident $op:tt $lhs:tt, $rhs:tt $(, $($arg:tt)*)?) => {{
+        $crate::bassert_internal!(@constant $lhs, $rhs);
+        match $lhs {
+            value => {
+                if $crate::internal::ASSERTIONS_ENABLED {
+                    match (&value, &$rhs) {
+                        (lhs, rhs) => $crate::bassert_internal!(
+                            @check [panic]
+                            lhs $op rhs,
+                            $crate::internal::bassert_failed(
+                                $crate::Kind::$kind,
+                                [stringify!($lhs), stringify!($rhs)],
+                                $crate::bassert_internal!(@operand $(#$display)? lhs),
+                                $crate::bassert_internal!(@operand $(#$display)? rhs),
+                                $crate::bassert_internal!(@incomparable $kind lhs, rhs),
+                                $crate::bassert_internal!(@difference &*lhs, &*rhs),
+                                $crate::bassert_internal!(@args $($($arg)*)?),
+                            )
+                        ),
+                    }
+                }
+                value
+            }
+        }
+    }};
+
+    (@compare [$mode:ident] $(#$display:ident)? $kind:ident $op:tt $lhs:tt, $rhs:tt $(, $($arg:tt)*)?) => {{
+        $crate::bassert_internal!(@constant $lhs, $rhs);
+        match (&$lhs, &$rhs) {
+            (lhs, rhs) => $crate::bassert_internal!(
+                @check [$mode]
+                lhs $op rhs,
+                $crate::internal::bassert_failed(
+                    $crate::Kind::$kind,
+                    [stringify!($lhs), stringify!($rhs)],
+                    $crate::bassert_internal!(@operand $(#$display)? lhs),
+                    $crate::bassert_internal!(@operand $(#$display)? rhs),
+                    $crate::bassert_internal!(@incomparable $kind lhs, rhs),
+                    $crate::bassert_internal!(@difference &*lhs, &*rhs),
+                    $crate::bassert_internal!(@args $($($arg)*)?),
+                )
+            ),
+        }
+    }};
+
+    // Comparing two literals (like `5 > 3`) always holds or always fails,
+    // which is reported as a warning by using a deprecated function.
+    (@constant $lhs:literal, $rhs:literal) => {
+        $crate::internal::constant_assertion()
+    };
+
+    (@constant $lhs:tt, $rhs:tt) => {};
+}
+
+extern crate alloc;
+
+mod config;
+#[cfg(feature = "diff")]
+mod diff;
+mod expr;
+mod failure;
+mod group;
+#[cfg(feature = "std")]
+mod hook;
+#[cfg(feature = "std")]
+mod json;
+mod kind;
+mod optional;
+#[cfg(feature = "std")]
+mod panics;
+mod style;
+
+pub use config::{BassertConfig, MessageStyle, DEFAULT_MAX_LEN};
+pub use failure::{expected_message, BassertFailure};
+pub use group::BassertGroup;
+#[cfg(feature = "std")]
+pub use hook::{set_failure_hook, take_failure_hook, FailureHook};
+pub use kind::Kind;
+pub use optional::set_enabled;
+
 #[doc(hidden)]
 pub mod internal {
-    use std::fmt;
+    //! The functions the macros expand to.
+    //!
+    //! The ones building a failure are `#[cold]` and `#[inline(never)]`,
+    //! so the code at each call site is just the check and a call, no matter how large the formatting code is.
+    use crate::failure::{BassertFailure, PanicMessage};
+    use crate::style;
+    use alloc::collections::BTreeMap;
+    use alloc::format;
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+    use core::cmp::Ordering;
+    use core::fmt;
+
+    #[doc(hidden)]
+    pub use crate::expr::normalize;
+    #[doc(hidden)]
+    pub use crate::Kind as BassertKind;
+
+    /// Whether the `assertions` feature is enabled, for macros which still evaluate (part of) the assertion without it.
+    #[doc(hidden)]
+    pub const ASSERTIONS_ENABLED: bool = cfg!(feature = "assertions");
+
+    /// Calls the closure passed as custom message, which is then formatted using its `Display` implementation.
+    #[doc(hidden)]
+    pub fn bassert_message<T: fmt::Display>(message: impl FnOnce() -> T) -> T {
+        message()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn optional_bassert_enabled() -> bool {
+        crate::optional::is_enabled()
+    }
+
+    /// Adds the label passed before an assertion to its failure.
+    #[cold]
+    #[doc(hidden)]
+    pub fn bassert_label(mut failure: BassertFailure, label: &'static str) -> BassertFailure {
+        failure.inner.label = Some(label);
+        failure
+    }
+
+    /// Panics with the message describing `failure`.
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_panic(failure: BassertFailure) -> ! {
+        #[cfg(feature = "std")]
+        {
+            crate::json::print(&failure);
+            crate::hook::run(&failure);
+        }
+        crate::failure::fail(&PanicMessage(&failure))
+    }
+
+    /// An operand of a failed assertion, passed to the functions building the [`BassertFailure`].
+    ///
+    /// Using a trait object (instead of making these functions generic over the operand types)
+    /// means each of these functions is compiled only once, no matter how many types are asserted on.
+    #[doc(hidden)]
+    #[derive(Clone, Copy)]
+    pub struct Operand<'a> {
+        value: &'a dyn fmt::Debug,
+        /// `None` when the type is not known, like for operands passed to [`write_failure`].
+        type_name: Option<style::TypeName>,
+    }
+
+    impl<'a> Operand<'a> {
+        /// Takes a reference to a reference, as only sized types can be turned into a trait object.
+        #[inline]
+        pub fn new<T: fmt::Debug + ?Sized>(value: &'a &T) -> Self {
+            Operand {
+                value,
+                type_name: Some(style::type_name::<T>()),
+            }
+        }
+
+        /// An operand printed using its `Display` implementation, for operands marked with `#display`.
+        #[inline]
+        pub fn display<T: fmt::Display + ?Sized>(value: &'a Displayed<'a, T>) -> Self {
+            Operand {
+                value,
+                type_name: Some(style::type_name::<T>()),
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    pub struct OperandWrap<'a, T: ?Sized>(pub &'a &'a T);
+
+    /// Implemented for (references to) [`OperandWrap`]s of a type implementing `Debug`.
+    ///
+    /// Like for [`NumericDifference`], auto-ref method resolution makes this take precedence over [`NoDebugOperand`].
+    #[doc(hidden)]
+    pub trait DebugOperand<'a> {
+        fn bassert_operand(&self) -> Operand<'a>;
+    }
+
+    #[doc(hidden)]
+    pub trait NoDebugOperand<'a> {
+        fn bassert_operand(&self) -> Operand<'a>;
+    }
+
+    impl<'a, T: fmt::Debug + ?Sized> DebugOperand<'a> for &OperandWrap<'a, T> {
+        fn bassert_operand(&self) -> Operand<'a> {
+            Operand::new(self.0)
+        }
+    }
+
+    impl<'a, T: ?Sized> NoDebugOperand<'a> for OperandWrap<'a, T> {
+        fn bassert_operand(&self) -> Operand<'a> {
+            Operand {
+                value: &NoDebug,
+                type_name: Some(style::type_name::<T>()),
+            }
+        }
+    }
+
+    /// Printed in place of the value of an operand whose type does not implement `Debug`.
+    struct NoDebug;
+
+    impl fmt::Debug for NoDebug {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("<does not implement Debug>")
+        }
+    }
+
+    /// Formats the wrapped value using its `Display` implementation, even when formatted using `Debug`.
+    #[doc(hidden)]
+    pub struct Displayed<'a, T: ?Sized>(pub &'a T);
+
+    impl<T: fmt::Display + ?Sized> fmt::Debug for Displayed<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(self.0, f)
+        }
+    }
+
+    /// Called by comparisons of two literals, to warn that these do not test anything.
+    #[deprecated(
+        note = "both sides of this comparison are literals, so it always holds or always fails"
+    )]
+    #[doc(hidden)]
+    #[inline(always)]
+    pub fn constant_assertion() {}
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_failed(
+        kind: BassertKind,
+        exprs: [&'static str; 2],
+        lhs: Operand<'_>,
+        rhs: Operand<'_>,
+        incomparable: bool,
+        difference: Option<Difference>,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let [lhs_expr, rhs_expr] = exprs;
+        let expr = format!("{} {} {}", lhs_expr, kind.op_str(), rhs_expr);
+        let mut failure = report(
+            Some(kind),
+            expr,
+            (lhs_expr, style::format_value(lhs.value), lhs.type_name),
+            Some((rhs_expr, style::format_value(rhs.value), rhs.type_name)),
+            difference,
+            args,
+        );
+        failure.inner.incomparable = incomparable;
+        #[cfg(feature = "diff")]
+        {
+            failure.inner.diff = pretty_diff(&kind, lhs.value, rhs.value);
+        }
+        failure
+    }
+
+    /// An operand of a failed assertion: its stringified expression, formatted value and type.
+    type Reported = (&'static str, String, Option<style::TypeName>);
+
+    /// Builds the failure of an assertion on one or two operands which are formatted already.
+    ///
+    /// Generic assertions only format their operands and compute the difference themselves,
+    /// so the rest is compiled once instead of for every type they are used with.
+    #[track_caller]
+    fn report(
+        kind: Option<BassertKind>,
+        expr: String,
+        lhs: Reported,
+        rhs: Option<Reported>,
+        difference: Option<Difference>,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let (lhs_expr, lhs_str, lhs_type) = lhs;
+        let rhs_expr = rhs.as_ref().map(|(rhs_expr, _, _)| *rhs_expr);
+        let mut failure = BassertFailure::new(kind, expr, lhs_expr, rhs_expr, args);
+        failure.push_value(lhs_expr, lhs_str.clone(), lhs_type);
+        failure.inner.lhs = Some(lhs_str);
+        if let Some((rhs_expr, rhs_str, rhs_type)) = rhs {
+            failure.push_value(rhs_expr, rhs_str.clone(), rhs_type);
+            failure.inner.rhs = Some(rhs_str);
+        }
+        failure.inner.difference = difference;
+        failure
+    }
+
+    /// Writes the message a failed comparison of `lhs` and `rhs` panics with to `out`, instead of panicking.
+    ///
+    /// This is the message the [`Display`](fmt::Display) implementation of the [`BassertFailure`] prints,
+    /// except that no backtrace, source snippet or thread is included (so the output is the same every time).
+    /// As the operands are trait objects, their types are not printed (even with the `type-names` feature),
+    /// and the difference between numbers, slices or strings is not explained.
+    ///
+    /// ```
+    /// use bassert::internal::{write_failure, BassertKind};
+    ///
+    /// let mut message = String::new();
+    /// write_failure(&mut message, BassertKind::Lt, "x", "y", &3, &2, Some(format_args!("too big"))).unwrap();
+    /// # #[cfg(not(feature = "type-names"))]
+    /// assert_eq!(message, "assertion failed: `x < y`\nx: `3`,\ny: `2`: too big");
+    /// ```
+    pub fn write_failure(
+        out: &mut impl fmt::Write,
+        kind: BassertKind,
+        lhs_expr: &'static str,
+        rhs_expr: &'static str,
+        lhs: &dyn fmt::Debug,
+        rhs: &dyn fmt::Debug,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> fmt::Result {
+        let (lhs, rhs) = (
+            Operand {
+                value: lhs,
+                type_name: None,
+            },
+            Operand {
+                value: rhs,
+                type_name: None,
+            },
+        );
+        let mut failure = bassert_failed(kind, [lhs_expr, rhs_expr], lhs, rhs, false, None, args);
+        failure.inner.backtrace = None;
+        failure.inner.snippet = None;
+        failure.inner.thread = None;
+        failure.write_message(out, style::Painter::plain())
+    }
+
+    #[cfg(feature = "diff")]
+    fn pretty_diff(
+        kind: &BassertKind,
+        lhs: &dyn fmt::Debug,
+        rhs: &dyn fmt::Debug,
+    ) -> Option<String> {
+        // When a `!=` fails, both sides are equal so there is nothing to diff.
+        if !matches!(kind, BassertKind::Eq) {
+            return None;
+        }
+
+        let (lhs, rhs) = (format!("{:#?}", lhs), format!("{:#?}", rhs));
+        // A diff of two single-line values would not tell anything the values themselves don't.
+        if !lhs.contains('\n') && !rhs.contains('\n') {
+            return None;
+        }
+
+        Some(crate::diff::line_diff(&lhs, &rhs))
+    }
+
+    /// What sets the operands of a failed comparison apart, printed after their values.
+    #[doc(hidden)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Difference {
+        /// `lhs - rhs`, for numbers.
+        Numeric(String),
+        /// The index and values of the first differing elements of two slices (if any),
+        /// and the lengths of the slices if these differ.
+        Slice {
+            first: Option<(usize, String, String)>,
+            lengths: Option<(usize, usize)>,
+        },
+        /// The keys only present in the left-hand or right-hand map (if any),
+        /// and the keys present in both whose values differ, with both values.
+        Map {
+            only_lhs: Option<String>,
+            only_rhs: Option<String>,
+            different_values: Vec<(String, String, String)>,
+        },
+        /// The (formatted lists of) elements only present in the left-hand or right-hand collection (if any).
+        Set {
+            only_lhs: Option<String>,
+            only_rhs: Option<String>,
+        },
+        /// The offset of the first differing byte of two buffers,
+        /// their lengths if these differ, and a hex dump of the rows around the offset.
+        Bytes {
+            offset: usize,
+            lengths: Option<(usize, usize)>,
+            dump: String,
+        },
+        /// The index and (formatted) values of the first two neighbouring elements of a slice which are out of order,
+        /// whether these could not be compared at all,
+        /// and whether the elements should have been strictly increasing (so equal elements are out of order as well).
+        Unsorted {
+            index: usize,
+            lhs: String,
+            rhs: String,
+            incomparable: bool,
+            strict: bool,
+        },
+        /// The predicate of `bassert_pred!` and the (formatted) value it returned `false` for.
+        Predicate {
+            pred_expr: String,
+            value_expr: String,
+            value: String,
+        },
+        /// The indices and (formatted) value of the first element of a collection which occurred twice.
+        Duplicate { indices: [usize; 2], value: String },
+        /// The variant a `Result` or `Option` was expected to be, and the one it turned out to be.
+        Expected {
+            expected: &'static str,
+            actual: &'static str,
+        },
+        /// The operand of an optional comparison which was `None`, and the one which was not.
+        NoneOperand {
+            none_expr: String,
+            some_expr: String,
+        },
+        /// The (formatted) bit patterns of two floats or integers.
+        Bits { lhs: String, rhs: String },
+        /// The (formatted) addresses two references point to.
+        Pointers { lhs: String, rhs: String },
+        /// One iterator ended after `len` elements (all equal to those of the other),
+        /// while the other continued with `next`.
+        Ended {
+            len: usize,
+            lhs_ended: bool,
+            next: String,
+        },
+        /// The `Display` representation of the error of a `Result` which did not match.
+        Error(String),
+        /// The name of the enum variant a value which did not match a pattern turned out to be.
+        Variant(String),
+        /// Two expressions which should have agreed, and what they returned.
+        Inconsistent {
+            lhs_expr: String,
+            lhs: String,
+            rhs_expr: String,
+            rhs: String,
+        },
+        /// The byte offset at which a string was found which should not have been.
+        Found(usize),
+        /// The length a collection was expected to have (or `None` for any non-zero length), and its actual length.
+        Len {
+            expected: Option<usize>,
+            actual: usize,
+        },
+        /// The outcome of the code passed to `bassert_panics!` or `bassert_no_panic!`, which was not the expected one:
+        /// the substring the panic message should have contained (if any),
+        /// and the message it panicked with (or `None` when it did not panic).
+        Panic {
+            expected: Option<String>,
+            message: Option<String>,
+        },
+        /// The position of the first differing character of two strings (counting from 1),
+        /// and the (escaped) characters around it: the common ones before it, and those of each string after it.
+        Str {
+            line: usize,
+            column: usize,
+            before: String,
+            lhs_after: String,
+            rhs_after: String,
+        },
+    }
+
+    #[doc(hidden)]
+    pub struct ErrorWrap<'a, T: ?Sized>(pub &'a T);
+
+    /// Implemented for (references to) [`ErrorWrap`]s of a `Result` (or a reference to one) whose error implements `Display`.
+    ///
+    /// Like for [`NumericDifference`], auto-ref method resolution makes this take precedence over [`NoErrorDisplay`].
+    #[doc(hidden)]
+    pub trait ErrorDisplay {
+        fn bassert_error_display(&self) -> Option<String>;
+    }
+
+    #[doc(hidden)]
+    pub trait NoErrorDisplay {
+        fn bassert_error_display(&self) -> Option<String>;
+    }
+
+    impl<T, E: fmt::Display> ErrorDisplay for &ErrorWrap<'_, Result<T, E>> {
+        fn bassert_error_display(&self) -> Option<String> {
+            self.0.as_ref().err().map(ToString::to_string)
+        }
+    }
+
+    impl<T, E: fmt::Display> ErrorDisplay for &ErrorWrap<'_, &Result<T, E>> {
+        fn bassert_error_display(&self) -> Option<String> {
+            self.0.as_ref().err().map(ToString::to_string)
+        }
+    }
+
+    impl<T: ?Sized> NoErrorDisplay for ErrorWrap<'_, T> {
+        fn bassert_error_display(&self) -> Option<String> {
+            None
+        }
+    }
+
+    #[doc(hidden)]
+    pub struct DifferenceWrap<'a, Lhs: ?Sized, Rhs: ?Sized>(pub &'a Lhs, pub &'a Rhs);
+
+    /// Implemented for (references to) [`DifferenceWrap`]s of two primitive numbers of the same type (or references to them).
+    ///
+    /// Because of auto-ref method resolution, this impl takes precedence over [`NoDifference`] whenever it applies.
+    #[doc(hidden)]
+    pub trait NumericDifference {
+        fn bassert_difference(&self) -> Option<Difference>;
+    }
+
+    /// Like [`NumericDifference`], for slices, arrays and `Vec`s with the same type of elements.
+    #[doc(hidden)]
+    pub trait SliceDifference {
+        fn bassert_difference(&self) -> Option<Difference>;
+    }
+
+    /// Like [`NumericDifference`], for strings.
+    #[doc(hidden)]
+    pub trait StrDifference {
+        fn bassert_difference(&self) -> Option<Difference>;
+    }
+
+    #[doc(hidden)]
+    pub trait NoDifference {
+        fn bassert_difference(&self) -> Option<Difference>;
+    }
+
+    impl<Lhs: ?Sized, Rhs: ?Sized> NoDifference for DifferenceWrap<'_, Lhs, Rhs> {
+        fn bassert_difference(&self) -> Option<Difference> {
+            None
+        }
+    }
+
+    /// Types which can be compared element by element.
+    #[doc(hidden)]
+    pub trait AsSlice {
+        type Elem;
+        fn bassert_as_slice(&self) -> &[Self::Elem];
+    }
+
+    impl<T> AsSlice for [T] {
+        type Elem = T;
+        fn bassert_as_slice(&self) -> &[T] {
+            self
+        }
+    }
+
+    impl<T, const N: usize> AsSlice for [T; N] {
+        type Elem = T;
+        fn bassert_as_slice(&self) -> &[T] {
+            self
+        }
+    }
+
+    impl<T> AsSlice for Vec<T> {
+        type Elem = T;
+        fn bassert_as_slice(&self) -> &[T] {
+            self
+        }
+    }
+
+    impl<S: AsSlice + ?Sized> AsSlice for &S {
+        type Elem = S::Elem;
+        fn bassert_as_slice(&self) -> &[S::Elem] {
+            (**self).bassert_as_slice()
+        }
+    }
+
+    /// Types which can be compared as text.
+    #[doc(hidden)]
+    pub trait AsStr {
+        fn bassert_as_str(&self) -> &str;
+    }
+
+    impl AsStr for str {
+        fn bassert_as_str(&self) -> &str {
+            self
+        }
+    }
+
+    impl AsStr for String {
+        fn bassert_as_str(&self) -> &str {
+            self
+        }
+    }
+
+    impl<S: AsStr + ?Sized> AsStr for &S {
+        fn bassert_as_str(&self) -> &str {
+            (**self).bassert_as_str()
+        }
+    }
+
+    /// Implements the `As...` traits for the smart pointers of `alloc`,
+    /// so the difference is also printed for operands like `Rc<T>`, `Arc<T>`, `Box<T>` and `Cow<T>`.
+    macro_rules! impl_smart_pointers {
+        ($trait:ident $(<$assoc:ident>)?, $method:ident -> $output:ty) => {
+            impl<S: $trait + ?Sized> $trait for alloc::boxed::Box<S> {
+                $(type $assoc = S::$assoc;)?
+                fn $method(&self) -> $output {
+                    (**self).$method()
+                }
+            }
+
+            impl<S: $trait + ?Sized> $trait for alloc::rc::Rc<S> {
+                $(type $assoc = S::$assoc;)?
+                fn $method(&self) -> $output {
+                    (**self).$method()
+                }
+            }
+
+            impl<S: $trait + ?Sized> $trait for alloc::sync::Arc<S> {
+                $(type $assoc = S::$assoc;)?
+                fn $method(&self) -> $output {
+                    (**self).$method()
+                }
+            }
+
+            impl<S: $trait + alloc::borrow::ToOwned + ?Sized> $trait for alloc::borrow::Cow<'_, S> {
+                $(type $assoc = S::$assoc;)?
+                fn $method(&self) -> $output {
+                    (**self).$method()
+                }
+            }
+        };
+    }
+
+    impl_smart_pointers!(AsStr, bassert_as_str -> &str);
+    impl_smart_pointers!(AsSlice<Elem>, bassert_as_slice -> &[S::Elem]);
+    impl_smart_pointers!(AsNumber<Number>, bassert_as_number -> S::Number);
+
+    impl<Lhs: AsStr + ?Sized, Rhs: AsStr + ?Sized> StrDifference for &DifferenceWrap<'_, Lhs, Rhs> {
+        fn bassert_difference(&self) -> Option<Difference> {
+            str_difference(self.0.bassert_as_str(), self.1.bassert_as_str())
+        }
+    }
+
+    /// The number of characters printed around the first differing character of two strings.
+    const STR_CONTEXT: usize = 20;
+
+    fn str_difference(lhs: &str, rhs: &str) -> Option<Difference> {
+        // Both strings are the same up to this (byte) index.
+        let mismatch = lhs
+            .char_indices()
+            .zip(rhs.chars())
+            .find(|((_, lhs_char), rhs_char)| lhs_char != rhs_char)
+            .map(|((idx, _), _)| idx)
+            .or_else(|| (lhs.len() != rhs.len()).then_some(lhs.len().min(rhs.len())))?;
+
+        let line_start = lhs[..mismatch].rfind('\n').map_or(0, |idx| idx + 1);
+        let line = lhs[..mismatch].matches('\n').count() + 1;
+        let column = lhs[line_start..mismatch].chars().count() + 1;
+
+        let skipped = (column - 1).saturating_sub(STR_CONTEXT);
+        let mut before = String::from(if skipped > 0 { "..." } else { "" });
+        before.extend(
+            lhs[line_start..mismatch]
+                .chars()
+                .skip(skipped)
+                .flat_map(char::escape_debug),
+        );
+        let after = |text: &str| {
+            let mut chars = text[mismatch..].chars().take_while(|&c| c != '\n');
+            let mut after: String = chars
+                .by_ref()
+                .take(STR_CONTEXT)
+                .flat_map(char::escape_debug)
+                .collect();
+            if chars.next().is_some() {
+                after.push_str("...");
+            }
+            after
+        };
+        Some(Difference::Str {
+            line,
+            column,
+            before,
+            lhs_after: after(lhs),
+            rhs_after: after(rhs),
+        })
+    }
+
+    impl<Lhs, Rhs> SliceDifference for &DifferenceWrap<'_, Lhs, Rhs>
+    where
+        Lhs: AsSlice + ?Sized,
+        Rhs: AsSlice<Elem = Lhs::Elem> + ?Sized,
+        Lhs::Elem: fmt::Debug + PartialEq,
+    {
+        fn bassert_difference(&self) -> Option<Difference> {
+            let (lhs, rhs) = (self.0.bassert_as_slice(), self.1.bassert_as_slice());
+            let first = lhs.iter().zip(rhs).position(|(l, r)| l != r).map(|idx| {
+                let (lhs_elem, rhs_elem) = (&lhs[idx], &rhs[idx]);
+                (
+                    idx,
+                    style::format_value(lhs_elem),
+                    style::format_value(rhs_elem),
+                )
+            });
+            let lengths = (lhs.len() != rhs.len()).then_some((lhs.len(), rhs.len()));
+            if first.is_none() && lengths.is_none() {
+                return None;
+            }
+            Some(Difference::Slice { first, lengths })
+        }
+    }
+
+    /// Primitive numbers, whose difference is printed.
+    #[doc(hidden)]
+    pub trait Number: Copy {
+        fn bassert_difference(self, other: Self) -> String;
+    }
+
+    /// Primitive numbers and (possibly nested) references to them,
+    /// so the difference is printed for operands like `&x` as well.
+    #[doc(hidden)]
+    pub trait AsNumber {
+        type Number: Number;
+        fn bassert_as_number(&self) -> Self::Number;
+    }
+
+    impl<S: AsNumber + ?Sized> AsNumber for &S {
+        type Number = S::Number;
+        fn bassert_as_number(&self) -> S::Number {
+            (**self).bassert_as_number()
+        }
+    }
+
+    impl<Lhs, Rhs> NumericDifference for &DifferenceWrap<'_, Lhs, Rhs>
+    where
+        Lhs: AsNumber + ?Sized,
+        Rhs: AsNumber<Number = Lhs::Number> + ?Sized,
+    {
+        fn bassert_difference(&self) -> Option<Difference> {
+            let (lhs, rhs) = (self.0.bassert_as_number(), self.1.bassert_as_number());
+            Some(Difference::Numeric(lhs.bassert_difference(rhs)))
+        }
+    }
+
+    macro_rules! impl_integer_difference {
+        ($($int:ty),*) => {
+            $(
+                impl Number for $int {
+                    fn bassert_difference(self, other: Self) -> String {
+                        // `abs_diff` cannot overflow, unlike `self - other`.
+                        let sign = if self < other { "-" } else { "" };
+                        format!("{}{}", sign, self.abs_diff(other))
+                    }
+                }
+
+                impl AsNumber for $int {
+                    type Number = $int;
+                    fn bassert_as_number(&self) -> $int {
+                        *self
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_integer_difference!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+    macro_rules! impl_float_difference {
+        ($($float:ty),*) => {
+            $(
+                impl Number for $float {
+                    fn bassert_difference(self, other: Self) -> String {
+                        format!("{:?}", self - other)
+                    }
+                }
+
+                impl AsNumber for $float {
+                    type Number = $float;
+                    fn bassert_as_number(&self) -> $float {
+                        *self
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_float_difference!(f32, f64);
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_map_failed<'m, M, K, V>(
+        lhs_expr: &'static str,
+        rhs_expr: &'static str,
+        lhs: &'m M,
+        rhs: &'m M,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure
+    where
+        &'m M: IntoIterator<Item = (&'m K, &'m V)>,
+        K: fmt::Debug + Ord + 'm,
+        V: fmt::Debug + PartialEq + 'm,
+    {
+        let kind = BassertKind::Eq;
+        let expr = format!("{} {} {}", lhs_expr, kind.op_str(), rhs_expr);
+        // Sorted, to not depend on the iteration order of the maps.
+        let lhs: BTreeMap<&K, &V> = lhs.into_iter().collect();
+        let rhs: BTreeMap<&K, &V> = rhs.into_iter().collect();
+
+        let only_in = |map: &BTreeMap<&K, &V>, other: &BTreeMap<&K, &V>| {
+            let keys: Vec<&K> = map
+                .keys()
+                .filter(|key| !other.contains_key(*key))
+                .copied()
+                .collect();
+            (!keys.is_empty()).then(|| style::format_value(&keys))
+        };
+        let different_values = lhs
+            .iter()
+            .filter_map(|(key, lhs_value)| match rhs.get(key) {
+                Some(rhs_value) if lhs_value != rhs_value => Some((
+                    style::format_value(key),
+                    style::format_value(lhs_value),
+                    style::format_value(rhs_value),
+                )),
+                _ => None,
+            })
+            .collect();
+        let difference = Difference::Map {
+            only_lhs: only_in(&lhs, &rhs),
+            only_rhs: only_in(&rhs, &lhs),
+            different_values,
+        };
+        let type_name = Some(style::type_name::<M>());
+        report(
+            Some(kind),
+            expr,
+            (lhs_expr, style::format_value(&lhs), type_name),
+            Some((rhs_expr, style::format_value(&rhs), type_name)),
+            Some(difference),
+            args,
+        )
+    }
+
+    /// The number of bytes in each row of the hex dump of [`bassert_bytes_failed`].
+    const BYTES_PER_ROW: usize = 8;
+
+    /// The number of rows printed before and after the row containing the first difference.
+    const CONTEXT_ROWS: usize = 1;
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_bytes_failed(
+        lhs_expr: &'static str,
+        rhs_expr: &'static str,
+        lhs: &[u8],
+        rhs: &[u8],
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let kind = BassertKind::Eq;
+        let expr = format!("{} {} {}", lhs_expr, kind.op_str(), rhs_expr);
+        let mut failure = BassertFailure::new(Some(kind), expr, lhs_expr, Some(rhs_expr), args);
+        let hex = |bytes: &[u8]| {
+            let hex: Vec<String> = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+            hex.join(" ")
+        };
+        failure.inner.lhs = Some(hex(lhs));
+        failure.inner.rhs = Some(hex(rhs));
+
+        let offset = lhs
+            .iter()
+            .zip(rhs)
+            .position(|(lhs_byte, rhs_byte)| lhs_byte != rhs_byte)
+            .unwrap_or(lhs.len().min(rhs.len()));
+        let rows = lhs.len().max(rhs.len()).div_ceil(BYTES_PER_ROW);
+        let first_row = (offset / BYTES_PER_ROW).saturating_sub(CONTEXT_ROWS);
+        let last_row = (offset / BYTES_PER_ROW + CONTEXT_ROWS + 1).min(rows);
+
+        let mut dump = String::new();
+        if first_row > 0 {
+            dump.push_str("  ...\n");
+        }
+        for row in first_row..last_row {
+            let range = row * BYTES_PER_ROW..(row + 1) * BYTES_PER_ROW;
+            let clamp = |len: usize| range.start.min(len)..range.end.min(len);
+            let (lhs_row, rhs_row) = (&lhs[clamp(lhs.len())], &rhs[clamp(rhs.len())]);
+            let marker = if lhs_row == rhs_row { ' ' } else { '>' };
+            let width = BYTES_PER_ROW * 3 - 1;
+            let line = format!(
+                "{} {:08x}  {:width$}  |  {}",
+                marker,
+                range.start,
+                hex(lhs_row),
+                hex(rhs_row)
+            );
+            dump.push_str(line.trim_end());
+            dump.push('\n');
+        }
+        if last_row < rows {
+            dump.push_str("  ...\n");
+        }
+        dump.pop();
+
+        let lengths = (lhs.len() != rhs.len()).then_some((lhs.len(), rhs.len()));
+        failure.inner.difference = Some(Difference::Bytes {
+            offset,
+            lengths,
+            dump,
+        });
+        failure
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_chain_failed(
+        kinds: [BassertKind; 2],
+        exprs: [&'static str; 3],
+        operands: [Operand<'_>; 3],
+        broken_link: usize,
+        incomparable: bool,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let [a_expr, b_expr, c_expr] = exprs;
+        let [op1, op2] = [kinds[0].op_str(), kinds[1].op_str()];
+        let expr = format!("{} {} {} {} {}", a_expr, op1, b_expr, op2, c_expr);
+        let values = operands.map(|operand| style::format_value(operand.value));
+
+        // The kind, operands and values of the failure are those of the broken link.
+        let (kind, lhs_expr, rhs_expr, [lhs, rhs]) = match broken_link {
+            0 => (kinds[0], a_expr, b_expr, [&values[0], &values[1]]),
+            _ => (kinds[1], b_expr, c_expr, [&values[1], &values[2]]),
+        };
+        let mut failure = BassertFailure::new(Some(kind), expr, lhs_expr, Some(rhs_expr), args);
+        let broken = format!("{} {} {}", lhs_expr, kind.op_str(), rhs_expr);
+        failure.push_note("broken link", broken);
+        failure.inner.incomparable = incomparable;
+        failure.inner.lhs = Some(lhs.clone());
+        failure.inner.rhs = Some(rhs.clone());
+
+        for ((expr, value), operand) in exprs.into_iter().zip(values).zip(operands) {
+            failure.push_value(expr, value, operand.type_name);
+        }
+        failure
+    }
+
+    /// A law relating [`Ord::cmp`] to the other comparisons, as checked by `bassert_ord_consistent!`.
+    #[derive(Debug, Clone, Copy)]
+    #[doc(hidden)]
+    pub enum OrdLaw {
+        PartialCmp,
+        Eq,
+        Lt,
+        Le,
+        Gt,
+        Ge,
+        Reverse,
+    }
+
+    /// Panics unless `lhs.cmp(rhs)` is consistent with the other comparisons of `lhs` and `rhs`.
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_ord_consistent<T: fmt::Debug + Ord + ?Sized>(
+        exprs: [&'static str; 2],
+        lhs_operand: Operand<'_>,
+        rhs_operand: Operand<'_>,
+        lhs: &T,
+        rhs: &T,
+        args: Option<fmt::Arguments<'_>>,
+    ) {
+        let cmp = lhs.cmp(rhs);
+        let laws = [
+            (OrdLaw::PartialCmp, lhs.partial_cmp(rhs) == Some(cmp)),
+            (OrdLaw::Eq, (lhs == rhs) == cmp.is_eq()),
+            (OrdLaw::Lt, (lhs < rhs) == cmp.is_lt()),
+            (OrdLaw::Le, (lhs <= rhs) == cmp.is_le()),
+            (OrdLaw::Gt, (lhs > rhs) == cmp.is_gt()),
+            (OrdLaw::Ge, (lhs >= rhs) == cmp.is_ge()),
+            (OrdLaw::Reverse, rhs.cmp(lhs) == cmp.reverse()),
+        ];
+        if let Some(&(law, _)) = laws.iter().find(|(_, holds)| !holds) {
+            bassert_panic(bassert_ord_failed(
+                exprs,
+                [lhs_operand, rhs_operand],
+                lhs,
+                rhs,
+                law,
+                args,
+            ))
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_ord_failed<T: fmt::Debug + Ord + ?Sized>(
+        exprs: [&'static str; 2],
+        operands: [Operand<'_>; 2],
+        lhs: &T,
+        rhs: &T,
+        law: OrdLaw,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let [a, b] = exprs.map(crate::expr::normalize);
+        let cmp_expr = format!("{}.cmp(&{})", a, b);
+        let cmp = format!("{:?}", lhs.cmp(rhs));
+        // What the side of the law other than `a.cmp(&b)` returned, and the law itself.
+        let (other_expr, other, expr) = match law {
+            OrdLaw::PartialCmp => {
+                let other_expr = format!("{}.partial_cmp(&{})", a, b);
+                let expr = format!("{} == Some({})", other_expr, cmp_expr);
+                (other_expr, format!("{:?}", lhs.partial_cmp(rhs)), expr)
+            }
+            OrdLaw::Reverse => {
+                let other_expr = format!("{}.cmp(&{})", b, a);
+                let expr = format!("{} == {}.reverse()", other_expr, cmp_expr);
+                (other_expr, format!("{:?}", rhs.cmp(lhs)), expr)
+            }
+            _ => {
+                let (op, method, holds) = match law {
+                    OrdLaw::Eq => ("==", "is_eq", lhs == rhs),
+                    OrdLaw::Lt => ("<", "is_lt", lhs < rhs),
+                    OrdLaw::Le => ("<=", "is_le", lhs <= rhs),
+                    OrdLaw::Gt => (">", "is_gt", lhs > rhs),
+                    _ => (">=", "is_ge", lhs >= rhs),
+                };
+                let other_expr = format!("{} {} {}", a, op, b);
+                let expr = format!("({}) == {}.{}()", other_expr, cmp_expr, method);
+                (other_expr, holds.to_string(), expr)
+            }
+        };
+        let [lhs, rhs] = operands;
+        report(
+            None,
+            expr,
+            (exprs[0], style::format_value(lhs.value), lhs.type_name),
+            Some((exprs[1], style::format_value(rhs.value), rhs.type_name)),
+            Some(Difference::Inconsistent {
+                lhs_expr: other_expr,
+                lhs: other,
+                rhs_expr: cmp_expr,
+                rhs: cmp,
+            }),
+            args,
+        )
+    }
+
+    /// Describes a `Result` or `Option` which turned out to be the other variant than `expected`.
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_variant_failed(
+        expr: &'static str,
+        value_expr: &'static str,
+        value: Operand<'_>,
+        [expected, actual]: [&'static str; 2],
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        report(
+            None,
+            expr.to_string(),
+            (
+                value_expr,
+                style::format_value(value.value),
+                value.type_name,
+            ),
+            None,
+            Some(Difference::Expected { expected, actual }),
+            args,
+        )
+    }
+
+    /// Describes a value for which the predicate of `bassert_pred!` returned `false`.
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_pred_failed(
+        expr: &'static str,
+        pred_expr: &'static str,
+        value_expr: &'static str,
+        value: Operand<'_>,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let mut failure = BassertFailure::new(None, expr.to_string(), value_expr, None, args);
+        let value_str = style::format_value(value.value);
+        failure.inner.lhs = Some(value_str.clone());
+        failure.inner.difference = Some(Difference::Predicate {
+            pred_expr: crate::expr::normalize(pred_expr).into_owned(),
+            value_expr: crate::expr::normalize(value_expr).into_owned(),
+            value: value_str,
+        });
+        failure
+    }
+
+    /// Describes a failed `bassert_between!` as the chained comparison `low <= val <= high`,
+    /// naming the violated bound instead of the broken link.
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_between_failed(
+        exprs: [&'static str; 3],
+        operands: [Operand<'_>; 3],
+        above_low: bool,
+        incomparable: bool,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let [val_expr, low_expr, high_expr] = exprs;
+        let [val, low, high] = operands;
+        let mut failure = bassert_chain_failed(
+            [BassertKind::Lte, BassertKind::Lte],
+            [low_expr, val_expr, high_expr],
+            [low, val, high],
+            if above_low { 1 } else { 0 },
+            incomparable,
+            args,
+        );
+        let label = if above_low {
+            "upper bound violated"
+        } else {
+            "lower bound violated"
+        };
+        for note in &mut failure.inner.notes {
+            note.0 = label;
+        }
+        failure
+    }
+
+    /// Floating-point types which can be compared using `~=`.
+    #[doc(hidden)]
+    pub trait ApproxFloat: Copy + fmt::Debug {
+        /// The tolerance used when no explicit `eps` is passed,
+        /// scaled by the magnitude of the compared values.
+        fn tolerance(lhs: Self, rhs: Self, eps: Option<Self>) -> Self;
+
+        /// The tolerance for a relative tolerance `rel` (as passed using `rel = ...`),
+        /// scaled by the largest magnitude of the compared values.
+        fn relative_tolerance(lhs: Self, rhs: Self, rel: Self) -> Self;
+
+        fn approx_eq(lhs: Self, rhs: Self, tolerance: Self) -> bool;
+
+        fn difference(lhs: Self, rhs: Self) -> Self;
+
+        /// The difference divided by the largest magnitude of the compared values.
+        fn relative_difference(lhs: Self, rhs: Self) -> Self;
+    }
+
+    macro_rules! impl_approx_float {
+        ($($float:ty),*) => {
+            $(
+                impl ApproxFloat for $float {
+                    fn tolerance(lhs: Self, rhs: Self, eps: Option<Self>) -> Self {
+                        match eps {
+                            Some(eps) => eps,
+                            None => 4.0 * <$float>::EPSILON * lhs.abs().max(rhs.abs()).max(1.0),
+                        }
+                    }
+
+                    fn relative_tolerance(lhs: Self, rhs: Self, rel: Self) -> Self {
+                        rel * lhs.abs().max(rhs.abs())
+                    }
+
+                    fn approx_eq(lhs: Self, rhs: Self, tolerance: Self) -> bool {
+                        // Checking for equality first makes equal infinities compare as approximately equal.
+                        lhs == rhs || Self::difference(lhs, rhs) <= tolerance
+                    }
+
+                    fn difference(lhs: Self, rhs: Self) -> Self {
+                        (lhs - rhs).abs()
+                    }
+
+                    fn relative_difference(lhs: Self, rhs: Self) -> Self {
+                        Self::difference(lhs, rhs) / lhs.abs().max(rhs.abs())
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_approx_float!(f32, f64);
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_approx_failed<F: ApproxFloat>(
+        lhs_expr: &'static str,
+        rhs_expr: &'static str,
+        lhs: F,
+        rhs: F,
+        tolerance: F,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let mut failure = approx_failed(lhs_expr, rhs_expr, lhs, rhs, args);
+        failure.push_value("tolerance", style::format_value(&tolerance), None);
+        failure
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_relative_approx_failed<F: ApproxFloat>(
+        lhs_expr: &'static str,
+        rhs_expr: &'static str,
+        lhs: F,
+        rhs: F,
+        rel: F,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let mut failure = approx_failed(lhs_expr, rhs_expr, lhs, rhs, args);
+        let relative_difference = F::relative_difference(lhs, rhs);
+        failure.push_value(
+            "relative difference",
+            style::format_value(&relative_difference),
+            None,
+        );
+        failure.push_value("relative tolerance", style::format_value(&rel), None);
+        failure
+    }
+
+    /// The index of the first pair of elements of `lhs` and `rhs` which are not approximately equal.
+    fn first_approx_mismatch<F: ApproxFloat>(
+        lhs: &[F],
+        rhs: &[F],
+        eps: Option<F>,
+    ) -> Option<usize> {
+        lhs.iter()
+            .zip(rhs)
+            .position(|(&lhs, &rhs)| !F::approx_eq(lhs, rhs, F::tolerance(lhs, rhs, eps)))
+    }
+
+    #[doc(hidden)]
+    pub fn approx_slice_eq<F: ApproxFloat>(lhs: &[F], rhs: &[F], eps: Option<F>) -> bool {
+        lhs.len() == rhs.len() && first_approx_mismatch(lhs, rhs, eps).is_none()
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_approx_slice_failed<F: ApproxFloat>(
+        exprs: [&'static str; 2],
+        lhs: &[F],
+        rhs: &[F],
+        eps: Option<F>,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let [lhs_expr, rhs_expr] = exprs;
+        let kind = BassertKind::Approx;
+        let expr = format!("{} {} {}", lhs_expr, kind.op_str(), rhs_expr);
+        let mut failure = BassertFailure::new(Some(kind), expr, lhs_expr, Some(rhs_expr), args);
+        let (lhs_str, rhs_str) = (style::format_value(&lhs), style::format_value(&rhs));
+        failure.push_value(lhs_expr, lhs_str.clone(), Some(style::type_name::<[F]>()));
+        failure.push_value(rhs_expr, rhs_str.clone(), Some(style::type_name::<[F]>()));
+        let lengths = (lhs.len() != rhs.len()).then_some((lhs.len(), rhs.len()));
+        let first = first_approx_mismatch(lhs, rhs, eps).map(|idx| {
+            let (lhs, rhs) = (lhs[idx], rhs[idx]);
+            // The difference and tolerance are those of the first pair which differs.
+            if lengths.is_none() {
+                let tolerance = F::tolerance(lhs, rhs, eps);
+                failure.push_value(
+                    "difference",
+                    style::format_value(&F::difference(lhs, rhs)),
+                    None,
+                );
+                failure.push_value("tolerance", style::format_value(&tolerance), None);
+            }
+            (idx, style::format_value(&lhs), style::format_value(&rhs))
+        });
+        failure.inner.lhs = Some(lhs_str);
+        failure.inner.rhs = Some(rhs_str);
+        failure.inner.difference = Some(Difference::Slice { first, lengths });
+        failure
+    }
+
+    #[doc(hidden)]
+    pub fn approx_opt_eq<F: ApproxFloat>(lhs: Option<F>, rhs: Option<F>, eps: Option<F>) -> bool {
+        match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) => F::approx_eq(lhs, rhs, F::tolerance(lhs, rhs, eps)),
+            (lhs, rhs) => lhs.is_none() && rhs.is_none(),
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_approx_opt_failed<F: ApproxFloat>(
+        exprs: [&'static str; 2],
+        lhs: Option<F>,
+        rhs: Option<F>,
+        eps: Option<F>,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let [lhs_expr, rhs_expr] = exprs;
+        let kind = BassertKind::Approx;
+        let expr = format!("{} {} {}", lhs_expr, kind.op_str(), rhs_expr);
+        let type_name = Some(style::type_name::<Option<F>>());
+        let difference = match (lhs, rhs) {
+            (Some(_), Some(_)) => None,
+            (None, _) => Some((lhs_expr, rhs_expr)),
+            (_, None) => Some((rhs_expr, lhs_expr)),
+        };
+        let mut failure = report(
+            Some(kind),
+            expr,
+            (lhs_expr, style::format_value(&lhs), type_name),
+            Some((rhs_expr, style::format_value(&rhs), type_name)),
+            difference.map(|(none_expr, some_expr)| Difference::NoneOperand {
+                none_expr: crate::expr::normalize(none_expr).into_owned(),
+                some_expr: crate::expr::normalize(some_expr).into_owned(),
+            }),
+            args,
+        );
+        if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+            let tolerance = F::tolerance(lhs, rhs, eps);
+            failure.push_value(
+                "difference",
+                style::format_value(&F::difference(lhs, rhs)),
+                None,
+            );
+            failure.push_value("tolerance", style::format_value(&tolerance), None);
+        }
+        failure
+    }
+
+    /// The failure of a `~=`, listing the operands and their difference.
+    #[track_caller]
+    fn approx_failed<F: ApproxFloat>(
+        lhs_expr: &'static str,
+        rhs_expr: &'static str,
+        lhs: F,
+        rhs: F,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let kind = BassertKind::Approx;
+        let expr = format!("{} {} {}", lhs_expr, kind.op_str(), rhs_expr);
+        let mut failure = BassertFailure::new(Some(kind), expr, lhs_expr, Some(rhs_expr), args);
+        let (lhs_str, rhs_str) = (style::format_value(&lhs), style::format_value(&rhs));
+        failure.push_value(lhs_expr, lhs_str.clone(), Some(style::type_name::<F>()));
+        failure.push_value(rhs_expr, rhs_str.clone(), Some(style::type_name::<F>()));
+        let difference = F::difference(lhs, rhs);
+        failure.push_value("difference", style::format_value(&difference), None);
+        failure.inner.lhs = Some(lhs_str);
+        failure.inner.rhs = Some(rhs_str);
+        failure
+    }
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for f32 {}
+        impl Sealed for f64 {}
+    }
+
+    /// Floating-point types which can be compared using [`bassert_ulps_eq!`](crate::bassert_ulps_eq!).
+    #[doc(hidden)]
+    pub trait UlpsFloat: sealed::Sealed + Copy + fmt::Debug {
+        /// The number of units in the last place between `self` and `other`,
+        /// or `None` if either of them is `NaN`.
+        fn ulps_distance(self, other: Self) -> Option<u64>;
+    }
+
+    macro_rules! impl_ulps_float {
+        ($($float:ty),*) => {
+            $(
+                impl UlpsFloat for $float {
+                    fn ulps_distance(self, other: Self) -> Option<u64> {
+                        if self.is_nan() || other.is_nan() {
+                            return None;
+                        }
+                        // Maps the floats onto integers in the same order, with `0.0` and `-0.0` both at zero.
+                        let ordered = |float: $float| {
+                            let magnitude = i128::from((float.abs()).to_bits());
+                            if float.is_sign_negative() {
+                                -magnitude
+                            } else {
+                                magnitude
+                            }
+                        };
+                        let distance = ordered(self).abs_diff(ordered(other));
+                        Some(distance as u64)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_ulps_float!(f32, f64);
+
+    /// Floating-point types which can be compared using [`bassert_total_eq!`](crate::bassert_total_eq!).
+    #[doc(hidden)]
+    pub trait TotalEqFloat: sealed::Sealed + Copy + fmt::Debug {
+        fn bits_eq(self, other: Self) -> bool;
+
+        /// The bits as a zero-padded hexadecimal number, like `0x3ff0000000000000`.
+        fn format_bits(self) -> String;
+    }
+
+    macro_rules! impl_total_eq_float {
+        ($($float:ty),*) => {
+            $(
+                impl TotalEqFloat for $float {
+                    fn bits_eq(self, other: Self) -> bool {
+                        self.to_bits() == other.to_bits()
+                    }
+
+                    fn format_bits(self) -> String {
+                        let width = 2 + 2 * core::mem::size_of::<$float>();
+                        format!("{:#0width$x}", self.to_bits(), width = width)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_total_eq_float!(f32, f64);
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_bits_failed<T>(
+        exprs: [&'static str; 2],
+        lhs: T,
+        rhs: T,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure
+    where
+        T: Copy + fmt::LowerHex + fmt::Binary + core::ops::BitXor<Output = T>,
+    {
+        let [lhs_expr, rhs_expr] = exprs;
+        let kind = BassertKind::Eq;
+        let expr = format!("{} {} {}", lhs_expr, kind.op_str(), rhs_expr);
+        let type_name = Some(style::type_name::<T>());
+        let width = core::mem::size_of::<T>() * 8;
+        let mut failure = report(
+            Some(kind),
+            expr,
+            (lhs_expr, format!("{:#x}", lhs), type_name),
+            Some((rhs_expr, format!("{:#x}", rhs), type_name)),
+            Some(Difference::Bits {
+                lhs: format!("{:#0width$b}", lhs, width = width + 2),
+                rhs: format!("{:#0width$b}", rhs, width = width + 2),
+            }),
+            args,
+        );
+        failure.push_value("differing bits", format!("{:#x}", lhs ^ rhs), None);
+        failure
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_total_eq_failed<F: TotalEqFloat>(
+        expr: &'static str,
+        exprs: [&'static str; 2],
+        lhs: F,
+        rhs: F,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let [lhs_expr, rhs_expr] = exprs;
+        let mut failure =
+            BassertFailure::new(None, expr.to_string(), lhs_expr, Some(rhs_expr), args);
+        let (lhs_str, rhs_str) = (style::format_value(&lhs), style::format_value(&rhs));
+        failure.push_value(lhs_expr, lhs_str.clone(), Some(style::type_name::<F>()));
+        failure.push_value(rhs_expr, rhs_str.clone(), Some(style::type_name::<F>()));
+        failure.inner.lhs = Some(lhs_str);
+        failure.inner.rhs = Some(rhs_str);
+        failure.inner.difference = Some(Difference::Bits {
+            lhs: lhs.format_bits(),
+            rhs: rhs.format_bits(),
+        });
+        failure
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_ulps_failed<F: UlpsFloat>(
+        lhs_expr: &'static str,
+        rhs_expr: &'static str,
+        lhs: F,
+        rhs: F,
+        distance: Option<u64>,
+        ulps: u64,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let kind = BassertKind::Approx;
+        let expr = format!("{} {} {}", lhs_expr, kind.op_str(), rhs_expr);
+        let mut failure = BassertFailure::new(Some(kind), expr, lhs_expr, Some(rhs_expr), args);
+        let (lhs_str, rhs_str) = (style::format_value(&lhs), style::format_value(&rhs));
+        failure.push_value(lhs_expr, lhs_str.clone(), Some(style::type_name::<F>()));
+        failure.push_value(rhs_expr, rhs_str.clone(), Some(style::type_name::<F>()));
+        let distance = match distance {
+            Some(distance) => distance.to_string(),
+            None => "undefined (NaN)".to_string(),
+        };
+        failure.push_value("ULP distance", distance, None);
+        failure.push_value("maximum ULP distance", ulps.to_string(), None);
+        failure.inner.lhs = Some(lhs_str);
+        failure.inner.rhs = Some(rhs_str);
+        failure
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_duration_failed(
+        lhs_expr: &'static str,
+        rhs_expr: &'static str,
+        lhs: core::time::Duration,
+        rhs: core::time::Duration,
+        tolerance: core::time::Duration,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let kind = BassertKind::Approx;
+        let expr = format!("{} {} {}", lhs_expr, kind.op_str(), rhs_expr);
+        let mut failure = BassertFailure::new(Some(kind), expr, lhs_expr, Some(rhs_expr), args);
+        let type_name = Some(style::type_name::<core::time::Duration>());
+        let (lhs_str, rhs_str) = (style::format_value(&lhs), style::format_value(&rhs));
+        failure.push_value(lhs_expr, lhs_str.clone(), type_name);
+        failure.push_value(rhs_expr, rhs_str.clone(), type_name);
+        failure.push_value("difference", style::format_value(&lhs.abs_diff(rhs)), None);
+        failure.push_value("tolerance", style::format_value(&tolerance), None);
+        failure.inner.lhs = Some(lhs_str);
+        failure.inner.rhs = Some(rhs_str);
+        failure
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_range_failed(
+        val_expr: &'static str,
+        range_expr: &'static str,
+        val: Operand<'_>,
+        range: Operand<'_>,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let expr = format!("{} in {}", val_expr, range_expr);
+        let mut failure = BassertFailure::new(None, expr, val_expr, Some(range_expr), args);
+        let (val_str, range_str) = (
+            style::format_value(val.value),
+            style::format_value(range.value),
+        );
+        failure.push_value(val_expr, val_str.clone(), val.type_name);
+        failure.push_value(range_expr, range_str.clone(), range.type_name);
+        failure.inner.lhs = Some(val_str);
+        failure.inner.rhs = Some(range_str);
+        failure
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_match_failed(
+        kind: BassertKind,
+        pattern: &'static str,
+        guard: Option<MatchGuard>,
+        rhs_expr: &'static str,
+        rhs: Operand<'_>,
+        error: Option<String>,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let lhs_expr = match &guard {
+            Some(guard) => guard.guarded_pattern,
+            None => pattern,
+        };
+        let expr = format!("{} {} {}", lhs_expr, kind.op_str(), rhs_expr);
+        let mut failure = BassertFailure::new(Some(kind), expr, lhs_expr, Some(rhs_expr), args);
+        let pattern_matched = guard.as_ref().is_some_and(|guard| guard.pattern_matched);
+        match guard {
+            Some(guard) if guard.pattern_matched => {
+                failure.push_note("guard failed", guard.guard.to_string());
+            }
+            Some(_) => failure.push_note("pattern did not match", pattern.to_string()),
+            None => {}
+        }
+        let rhs_str = style::format_value(rhs.value);
+        let variant = match pattern_matched {
+            true => None,
+            false => actual_variant(pattern, &rhs_str),
+        };
+        failure.push_value(rhs_expr, rhs_str.clone(), rhs.type_name);
+        failure.inner.rhs = Some(rhs_str);
+        failure.inner.difference = error
+            .map(Difference::Error)
+            .or_else(|| variant.map(Difference::Variant));
+        failure
+    }
+
+    /// Guesses the enum variant of a value from its `Debug` representation,
+    /// which for derived implementations starts with the name of the variant, like `Some(3)`.
+    ///
+    /// Returns `None` when the value does not look like a tuple or struct variant
+    /// (as unit variants are printed as just their name already),
+    /// or when the pattern mentions the variant (so another part of it did not match).
+    fn actual_variant(pattern: &str, value: &str) -> Option<String> {
+        let end = value
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(value.len());
+        let (name, rest) = value.split_at(end);
+        // A value truncated right after the name (like `Some(… 10 more chars)`) might have had a longer name.
+        if !name.starts_with(|c: char| c.is_ascii_uppercase())
+            || !(rest.starts_with('(') || rest.starts_with(" {"))
+            || rest.starts_with("(…")
+        {
+            return None;
+        }
+        let mentioned = pattern
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .any(|word| word == name);
+        (!mentioned).then(|| name.to_string())
+    }
+
+    /// Adds the value of a binding of a pattern whose guard failed to `failure`.
+    #[cold]
+    #[inline(never)]
+    #[doc(hidden)]
+    pub fn bassert_push_binding(
+        failure: &mut BassertFailure,
+        binding: &'static str,
+        value: Operand<'_>,
+    ) {
+        let value_str = style::format_value(value.value);
+        failure.push_value(binding, value_str, value.type_name);
+    }
+
+    /// The guard of a `=` assertion like `Some(x) if *x > 5 = val`.
+    #[doc(hidden)]
+    pub struct MatchGuard {
+        /// The pattern followed by its guard, like `Some(x) if *x > 5`.
+        pub guarded_pattern: &'static str,
+        pub guard: &'static str,
+        /// Whether the pattern matched, in which case the guard itself failed.
+        pub pattern_matched: bool,
+    }
+
+    #[inline(always)]
+    #[doc(hidden)]
+    pub fn identity<T>(value: T) -> T {
+        value
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_not_failed(
+        inner_expr: &'static str,
+        inner: bool,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let expr = format!("!{}", inner_expr);
+        let mut failure = BassertFailure::new(None, expr, inner_expr, None, args);
+        let inner_str = style::format_value(&inner);
+        failure.push_value(inner_expr, inner_str.clone(), None);
+        failure.inner.lhs = Some(inner_str);
+        failure
+    }
+
+    /// The address `reference` points to, without the metadata of slices or trait objects.
+    #[doc(hidden)]
+    #[inline]
+    pub fn address<T: ?Sized>(reference: &T) -> *const () {
+        (reference as *const T).cast()
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_ptr_failed(
+        expr: &'static str,
+        exprs: [&'static str; 2],
+        lhs: Operand<'_>,
+        rhs: Operand<'_>,
+        addresses: [*const (); 2],
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let [lhs_expr, rhs_expr] = exprs;
+        let mut failure =
+            BassertFailure::new(None, expr.to_string(), lhs_expr, Some(rhs_expr), args);
+        let (lhs_str, rhs_str) = (
+            style::format_value(lhs.value),
+            style::format_value(rhs.value),
+        );
+        failure.push_value(lhs_expr, lhs_str.clone(), lhs.type_name);
+        failure.push_value(rhs_expr, rhs_str.clone(), rhs.type_name);
+        failure.inner.lhs = Some(lhs_str);
+        failure.inner.rhs = Some(rhs_str);
+        failure.inner.difference = Some(Difference::Pointers {
+            lhs: format!("{:p}", addresses[0]),
+            rhs: format!("{:p}", addresses[1]),
+        });
+        failure
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_contains_failed(
+        expr: &'static str,
+        exprs: [&'static str; 2],
+        operands: [Operand<'_>; 2],
+        found_at: Option<usize>,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let ([haystack_expr, needle_expr], [haystack, needle]) = (exprs, operands);
+        let mut failure = BassertFailure::new(
+            None,
+            expr.to_string(),
+            haystack_expr,
+            Some(needle_expr),
+            args,
+        );
+        let (haystack_value, needle_value) = (
+            style::format_value(haystack.value),
+            style::format_value(needle.value),
+        );
+        failure.push_value(haystack_expr, haystack_value.clone(), haystack.type_name);
+        failure.push_value(needle_expr, needle_value.clone(), needle.type_name);
+        failure.inner.lhs = Some(haystack_value);
+        failure.inner.rhs = Some(needle_value);
+        failure.inner.difference = found_at.map(Difference::Found);
+        failure
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_ieq_failed(
+        expr: &'static str,
+        exprs: [&'static str; 2],
+        operands: [Operand<'_>; 2],
+        strs: [&str; 2],
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let ([lhs_expr, rhs_expr], [lhs, rhs], [lhs_str, rhs_str]) = (exprs, operands, strs);
+        let mut failure =
+            BassertFailure::new(None, expr.to_string(), lhs_expr, Some(rhs_expr), args);
+        let (lhs_value, rhs_value) = (
+            style::format_value(lhs.value),
+            style::format_value(rhs.value),
+        );
+        failure.push_value(lhs_expr, lhs_value.clone(), lhs.type_name);
+        failure.push_value(rhs_expr, rhs_value.clone(), rhs.type_name);
+        failure.inner.lhs = Some(lhs_value);
+        failure.inner.rhs = Some(rhs_value);
+
+        let first = lhs_str
+            .chars()
+            .zip(rhs_str.chars())
+            .enumerate()
+            .find(|(_, (l, r))| !l.eq_ignore_ascii_case(r))
+            .map(|(idx, (l, r))| (idx, style::format_value(&l), style::format_value(&r)));
+        let (lhs_len, rhs_len) = (lhs_str.chars().count(), rhs_str.chars().count());
+        let lengths = (lhs_len != rhs_len).then_some((lhs_len, rhs_len));
+        failure.inner.difference = Some(Difference::Slice { first, lengths });
+        failure
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_len_failed(
+        expr: &'static str,
+        collection_expr: &'static str,
+        collection: Operand<'_>,
+        expected: Option<usize>,
+        actual: usize,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let mut failure = BassertFailure::new(None, expr.to_string(), collection_expr, None, args);
+        let collection_str = style::format_value(collection.value);
+        failure.push_value(
+            collection_expr,
+            collection_str.clone(),
+            collection.type_name,
+        );
+        failure.inner.lhs = Some(collection_str);
+        failure.inner.difference = Some(Difference::Len { expected, actual });
+        failure
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_sorted_failed<T: fmt::Debug + PartialOrd>(
+        expr: &'static str,
+        slice_expr: &'static str,
+        slice: &[T],
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        bassert_monotonic_failed(expr, slice_expr, slice, false, args)
+    }
+
+    /// Describes the first two neighbouring elements of `slice` which are not increasing
+    /// (when `strict`) or non-decreasing.
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_monotonic_failed<T: fmt::Debug + PartialOrd>(
+        expr: &'static str,
+        slice_expr: &'static str,
+        slice: &[T],
+        strict: bool,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let index = slice
+            .windows(2)
+            .position(|pair| match pair[0].partial_cmp(&pair[1]) {
+                Some(Ordering::Less) => false,
+                Some(Ordering::Equal) => strict,
+                _ => true,
+            });
+        let unsorted = index.map(|index| {
+            let (lhs, rhs) = (&slice[index], &slice[index + 1]);
+            Difference::Unsorted {
+                index,
+                lhs: style::format_value(lhs),
+                rhs: style::format_value(rhs),
+                incomparable: lhs.partial_cmp(rhs).is_none(),
+                strict,
+            }
+        });
+        unsorted_failed(expr, slice_expr, Operand::new(&slice), unsorted, args)
+    }
+
+    /// Panics unless `cmp` does not return `Greater` for any two neighbouring elements of `slice`.
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_sorted_by<T: fmt::Debug>(
+        expr: &'static str,
+        slice_expr: &'static str,
+        slice: &[T],
+        mut cmp: impl FnMut(&T, &T) -> Ordering,
+        args: Option<fmt::Arguments<'_>>,
+    ) {
+        let index = slice
+            .windows(2)
+            .position(|pair| cmp(&pair[0], &pair[1]) == Ordering::Greater);
+        if let Some(index) = index {
+            let unsorted = Difference::Unsorted {
+                index,
+                lhs: style::format_value(&slice[index]),
+                rhs: style::format_value(&slice[index + 1]),
+                incomparable: false,
+                strict: false,
+            };
+            bassert_panic(unsorted_failed(
+                expr,
+                slice_expr,
+                Operand::new(&slice),
+                Some(unsorted),
+                args,
+            ))
+        }
+    }
+
+    /// Panics unless `lhs` and `rhs` yield the same number of elements, which are pairwise equal.
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_iter_eq<Lhs, Rhs>(
+        expr: &'static str,
+        lhs: Lhs,
+        rhs: Rhs,
+        args: Option<fmt::Arguments<'_>>,
+    ) where
+        Lhs: IntoIterator,
+        Rhs: IntoIterator,
+        Lhs::Item: PartialEq<Rhs::Item> + fmt::Debug,
+        Rhs::Item: fmt::Debug,
+    {
+        let (mut lhs, mut rhs) = (lhs.into_iter(), rhs.into_iter());
+        let mut index = 0;
+        let difference = loop {
+            match (lhs.next(), rhs.next()) {
+                (None, None) => return,
+                (Some(l), Some(r)) if l == r => index += 1,
+                (Some(l), Some(r)) => {
+                    break Difference::Slice {
+                        first: Some((index, style::format_value(&l), style::format_value(&r))),
+                        lengths: None,
+                    }
+                }
+                (Some(l), None) => {
+                    break Difference::Ended {
+                        len: index,
+                        lhs_ended: false,
+                        next: style::format_value(&l),
+                    }
+                }
+                (None, Some(r)) => {
+                    break Difference::Ended {
+                        len: index,
+                        lhs_ended: true,
+                        next: style::format_value(&r),
+                    }
+                }
+            }
+        };
+        bassert_panic(bassert_iter_failed(expr, difference, args))
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_iter_failed(
+        expr: &'static str,
+        difference: Difference,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let mut failure = BassertFailure::new(None, expr.to_string(), expr, None, args);
+        failure.inner.difference = Some(difference);
+        failure
+    }
+
+    /// The slice is passed as a trait object, so this part is compiled only once.
+    #[track_caller]
+    fn unsorted_failed(
+        expr: &'static str,
+        slice_expr: &'static str,
+        slice: Operand<'_>,
+        unsorted: Option<Difference>,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        report(
+            None,
+            expr.to_string(),
+            (
+                slice_expr,
+                style::format_value(slice.value),
+                slice.type_name,
+            ),
+            None,
+            unsorted,
+            args,
+        )
+    }
+
+    /// Panics if any element of `items` occurs more than once.
+    #[cfg(feature = "std")]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_unique<'a, T: fmt::Debug + Eq + core::hash::Hash + 'a>(
+        expr: &'static str,
+        collection_expr: &'static str,
+        collection: Operand<'_>,
+        items: impl IntoIterator<Item = &'a T>,
+        args: Option<fmt::Arguments<'_>>,
+    ) {
+        let mut seen = std::collections::HashMap::new();
+        for (index, item) in items.into_iter().enumerate() {
+            if let Some(&first) = seen.get(item) {
+                bassert_panic(bassert_unique_failed(
+                    expr,
+                    collection_expr,
+                    collection,
+                    [first, index],
+                    item,
+                    args,
+                ))
+            }
+            seen.insert(item, index);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_unique_failed(
+        expr: &'static str,
+        collection_expr: &'static str,
+        collection: Operand<'_>,
+        indices: [usize; 2],
+        duplicate: &dyn fmt::Debug,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let difference = Difference::Duplicate {
+            indices,
+            value: style::format_value(duplicate),
+        };
+        report(
+            None,
+            expr.to_string(),
+            (
+                collection_expr,
+                style::format_value(collection.value),
+                collection.type_name,
+            ),
+            None,
+            Some(difference),
+            args,
+        )
+    }
+
+    /// Panics unless `lhs_items` and `rhs_items` contain the same elements, ignoring their order.
+    #[cfg(feature = "std")]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_set_eq<'a, T: fmt::Debug + Eq + core::hash::Hash + 'a>(
+        exprs: [&'static str; 2],
+        lhs: Operand<'_>,
+        rhs: Operand<'_>,
+        lhs_items: impl IntoIterator<Item = &'a T>,
+        rhs_items: impl IntoIterator<Item = &'a T>,
+        args: Option<fmt::Arguments<'_>>,
+    ) {
+        let lhs_items: Vec<&T> = lhs_items.into_iter().collect();
+        let rhs_items: Vec<&T> = rhs_items.into_iter().collect();
+        let (only_lhs, only_rhs) = (
+            only_in(&lhs_items, &rhs_items),
+            only_in(&rhs_items, &lhs_items),
+        );
+        if only_lhs.is_empty() && only_rhs.is_empty() {
+            return;
+        }
+
+        let difference = Difference::Set {
+            only_lhs: format_elements(only_lhs),
+            only_rhs: format_elements(only_rhs),
+        };
+        let expr = format!("{} == {}", exprs[0], exprs[1]);
+        bassert_panic(bassert_set_failed(
+            Some(BassertKind::Eq),
+            expr,
+            exprs,
+            [lhs, rhs],
+            difference,
+            args,
+        ))
+    }
+
+    /// Panics unless every element of `lhs_items` is contained in `rhs_items` (when `lhs_is_subset`),
+    /// or the other way around.
+    #[cfg(feature = "std")]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_subset<'a, T: fmt::Debug + Eq + core::hash::Hash + 'a>(
+        expr: &'static str,
+        exprs: [&'static str; 2],
+        operands: [Operand<'_>; 2],
+        lhs_items: impl IntoIterator<Item = &'a T>,
+        rhs_items: impl IntoIterator<Item = &'a T>,
+        lhs_is_subset: bool,
+        args: Option<fmt::Arguments<'_>>,
+    ) {
+        let (lhs_items, rhs_items): (Vec<&T>, Vec<&T>) = (
+            lhs_items.into_iter().collect(),
+            rhs_items.into_iter().collect(),
+        );
+        let (subset, superset) = if lhs_is_subset {
+            (lhs_items, rhs_items)
+        } else {
+            (rhs_items, lhs_items)
+        };
+        let superset: std::collections::HashSet<&T> = superset.into_iter().collect();
+        let missing: Vec<&T> = subset
+            .into_iter()
+            .filter(|item| !superset.contains(item))
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+
+        let missing = format_elements(missing);
+        let difference = if lhs_is_subset {
+            Difference::Set {
+                only_lhs: missing,
+                only_rhs: None,
+            }
+        } else {
+            Difference::Set {
+                only_lhs: None,
+                only_rhs: missing,
+            }
+        };
+        bassert_panic(bassert_set_failed(
+            None,
+            expr.to_string(),
+            exprs,
+            operands,
+            difference,
+            args,
+        ))
+    }
+
+    /// Formats the (non-empty) list of elements only present on one side.
+    ///
+    /// They are sorted by their `Debug` representation, so the message is the same every time,
+    /// even for collections (like a `HashSet`) which iterate in a random order.
+    #[cfg(feature = "std")]
+    fn format_elements<T: fmt::Debug>(mut elements: Vec<&T>) -> Option<String> {
+        if elements.is_empty() {
+            return None;
+        }
+        elements.sort_by_cached_key(|element| format!("{:?}", element));
+        Some(style::format_value(&elements))
+    }
+
+    /// The elements of `items` which are left over after removing (one occurrence of) each element of `other`,
+    /// in the order they occur in.
+    #[cfg(feature = "std")]
+    fn only_in<'a, T: Eq + core::hash::Hash>(items: &[&'a T], other: &[&'a T]) -> Vec<&'a T> {
+        let mut counts = std::collections::HashMap::<&T, usize>::new();
+        for item in other {
+            *counts.entry(*item).or_default() += 1;
+        }
+        items
+            .iter()
+            .filter(|item| match counts.get_mut(**item) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            })
+            .copied()
+            .collect()
+    }
+
+    #[cfg(feature = "std")]
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    fn bassert_set_failed(
+        kind: Option<BassertKind>,
+        expr: String,
+        exprs: [&'static str; 2],
+        [lhs, rhs]: [Operand<'_>; 2],
+        difference: Difference,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let [lhs_expr, rhs_expr] = exprs;
+        let mut failure = BassertFailure::new(kind, expr, lhs_expr, Some(rhs_expr), args);
+        let (lhs_str, rhs_str) = (
+            style::format_value(lhs.value),
+            style::format_value(rhs.value),
+        );
+        failure.push_value(lhs_expr, lhs_str.clone(), lhs.type_name);
+        failure.push_value(rhs_expr, rhs_str.clone(), rhs.type_name);
+        failure.inner.lhs = Some(lhs_str);
+        failure.inner.rhs = Some(rhs_str);
+        failure.inner.difference = Some(difference);
+        failure
+    }
+
+    /// Runs `code`, panicking unless it panics with a message containing `expected` (if given).
+    #[cfg(feature = "std")]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_panics(expr: &'static str, expected: Option<&str>, code: impl FnOnce()) {
+        match crate::panics::catch(code) {
+            Err(message) if expected.is_none_or(|expected| message.contains(expected)) => {}
+            Err(message) => bassert_panic(bassert_panics_failed(expr, expected, Some(message))),
+            Ok(()) => bassert_panic(bassert_panics_failed(expr, expected, None)),
+        }
+    }
+
+    /// Runs `code`, panicking (with a message of its own) if it panics.
+    #[cfg(feature = "std")]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_no_panic(expr: &'static str, code: impl FnOnce()) {
+        if let Err(message) = crate::panics::catch(code) {
+            bassert_panic(bassert_panics_failed(expr, None, Some(message)))
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    fn bassert_panics_failed(
+        expr: &'static str,
+        expected: Option<&str>,
+        message: Option<String>,
+    ) -> BassertFailure {
+        let mut failure = BassertFailure::new(None, expr.to_string(), expr, None, None);
+        failure.inner.difference = Some(Difference::Panic {
+            expected: expected.map(String::from),
+            message,
+        });
+        failure
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_bool_failed(
+        expr: &'static str,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        BassertFailure::new(None, expr.to_string(), expr, None, args)
+    }
+
+    #[cfg(feature = "power")]
+    #[doc(hidden)]
+    pub use bassert_macros::power_bassert_impl;
+
+    /// The values of the sub-expressions of a [`power_bassert!`](crate::power_bassert), in evaluation order.
+    #[cfg(feature = "power")]
+    #[doc(hidden)]
+    #[derive(Default)]
+    pub struct PowerValues {
+        values: Vec<(&'static str, String, style::TypeName)>,
+    }
+
+    #[cfg(feature = "power")]
+    impl PowerValues {
+        pub fn new() -> Self {
+            Self::default()
+        }
+    }
+
+    #[cfg(feature = "power")]
+    #[doc(hidden)]
+    pub struct PowerValue<'a, T: ?Sized>(pub &'a T);
+
+    /// Implemented for references to [`PowerValue`]s of types implementing `Debug`.
+    ///
+    /// Because of auto-ref method resolution, this impl takes precedence over [`PowerNoDebug`] whenever it applies.
+    #[cfg(feature = "power")]
+    #[doc(hidden)]
+    pub trait PowerDebug {
+        fn bassert_power_record(&self, values: &mut PowerValues, expr: &'static str);
+    }
+
+    /// Values which do not implement `Debug` are not recorded.
+    #[cfg(feature = "power")]
+    #[doc(hidden)]
+    pub trait PowerNoDebug {
+        fn bassert_power_record(&self, values: &mut PowerValues, expr: &'static str);
+    }
+
+    #[cfg(feature = "power")]
+    impl<T: fmt::Debug + ?Sized> PowerDebug for &PowerValue<'_, T> {
+        fn bassert_power_record(&self, values: &mut PowerValues, expr: &'static str) {
+            let value = style::format_value(&self.0);
+            values.values.push((expr, value, style::type_name::<T>()));
+        }
+    }
+
+    #[cfg(feature = "power")]
+    impl<T: ?Sized> PowerNoDebug for PowerValue<'_, T> {
+        fn bassert_power_record(&self, _values: &mut PowerValues, _expr: &'static str) {}
+    }
+
+    #[cfg(feature = "power")]
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn bassert_power_failed(
+        expr: &'static str,
+        values: PowerValues,
+        args: Option<fmt::Arguments<'_>>,
+    ) -> BassertFailure {
+        let mut failure = BassertFailure::new(None, expr.to_string(), expr, None, args);
+        for (expr, value, type_name) in values.values {
+            failure.push_value(expr, value, Some(type_name));
+        }
+        failure
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn gt_success_passes() {
+        let larger = 3;
+        let smaller = 2;
+        bassert!(larger > smaller);
+        bassert!(larger > smaller, "foo bar {}", "some message");
+    }
+
+    #[test]
+    fn gt_complex_success_passes() {
+        let x = 33;
+        bassert!(x < (x + 10));
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `smaller > larger`\nsmaller: `2`,\nlarger: `3`")]
+    fn gt_failure_prints_correct_message() {
+        let larger = 3;
+        let smaller = 2;
+        bassert!(smaller > larger);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `smaller > larger`\nsmaller: `2`,\nlarger: `3`: it is broken, because foo"
+    )]
+    fn gt_failure_with_custom_formatted_message() {
+        let larger = 3;
+        let smaller = 2;
+        bassert!(smaller > larger, "it is broken, because {}", "foo");
+    }
+
+    #[test]
+    fn lt_success_passes() {
+        let larger = 3;
+        let smaller = 2;
+        bassert!(smaller < larger);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `larger < smaller`\nlarger: `3`,\nsmaller: `2`")]
+    fn lt_failure_prints_correct_message() {
+        let larger = 3;
+        let smaller = 2;
+        bassert!(larger < smaller);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `larger < smaller`\nlarger: `3`,\nsmaller: `2`: it is broken, because foo"
+    )]
+    fn lt_failure_with_custom_formatted_message() {
+        let larger = 3;
+        let smaller = 2;
+        bassert!(larger < smaller, "it is broken, because {}", "foo");
+    }
+
+    #[test]
+    fn gte_success_passes() {
+        let larger = 3;
+        let smaller = 2;
+        bassert!(larger >= smaller);
+        bassert!(larger >= larger);
+        bassert!(smaller >= smaller);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `smaller >= larger`\nsmaller: `2`,\nlarger: `3`")]
+    fn gte_failure_prints_correct_message() {
+        let larger = 3;
+        let smaller = 2;
+        bassert!(smaller >= larger);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `smaller >= larger`\nsmaller: `2`,\nlarger: `3`: it was not larger at all"
+    )]
+    fn gte_failure_with_custom_message_prints_correct_message() {
+        let larger = 3;
+        let smaller = 2;
+        bassert!(smaller >= larger, "it was not larger {}", "at all");
+    }
+
+    #[test]
+    fn lte_success_passes() {
+        let larger = 3;
+        let smaller = 2;
+        bassert!(smaller <= larger);
+        bassert!(smaller <= smaller);
+        bassert!(larger <= larger);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `larger <= smaller`\nlarger: `3`,\nsmaller: `2`")]
+    fn lte_failure_prints_correct_message() {
+        let larger = 3;
+        let smaller = 2;
+        bassert!(larger <= smaller);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `larger <= smaller`\nlarger: `3`,\nsmaller: `2`: it was not smaller at all"
+    )]
+    fn lte_failure_with_custom_message_prints_correct_message() {
+        let larger = 3;
+        let smaller = 2;
+        bassert!(larger <= smaller, "it was not smaller {}", "at all");
+    }
+
+    #[test]
+    fn eq_success_passes() {
+        let foo = 42;
+        let bar = 42;
+        bassert!(foo == bar);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `larger == smaller`\nlarger: `3`,\nsmaller: `2`")]
+    fn eq_failure_prints_correct_message() {
+        let larger = 3;
+        let smaller = 2;
+        bassert!(larger == smaller);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `larger == smaller`\nlarger: `3`,\nsmaller: `2`: Huge explosions!"
+    )]
+    fn eq_failure_with_custom_message_prints_correct_message() {
+        let larger = 3;
+        let smaller = 2;
+        bassert!(larger == smaller, "{} explosions!", "Huge");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `larger == smaller`\nlarger: `3`,\nsmaller: `2`: Huge explosions!"
+    )]
+    fn eq_failure_with_closure_message_prints_correct_message() {
+        let larger = 3;
+        let smaller = 2;
+        let size = alloc::string::String::from("Huge");
+        bassert!(larger == smaller, move || alloc::format!(
+            "{} explosions!",
+            size
+        ));
+    }
+
+    #[test]
+    fn label_is_printed_in_the_first_line() {
+        let (age, id) = (200, 7);
+        bassert!("user age bounds"; age > 0);
+        bassert!(
+            (panic_message(|| bassert!("user age bounds"; age < 150, "for user {}", id)))
+                .starts_with("assertion failed [user age bounds]: `age < 150`\nage: `200`,\n150: `150`: for user 7")
+        );
+
+        let failure = try_bassert!("adult"; age > 18 && 0 <= id < 5).unwrap_err();
+        bassert!((failure.label()) == (Some("adult")));
+        bassert!((failure.to_string()).starts_with("assertion failed [adult]: `0 <= id < 5`"));
+        bassert!((try_bassert!(age < 18).unwrap_err().label()) == None);
+        let name = "user";
+        bassert!("user" == name);
+    }
+
+    #[test]
+    fn closure_message_is_only_called_on_failure() {
+        let mut calls = 0;
+        let mut context = || {
+            calls += 1;
+            "context"
+        };
+        let one = 1;
+        bassert!(one == 1, || context());
+        let failure = try_bassert!(one == 2, || context()).unwrap_err();
+        bassert!((failure.message()) == (Some("context")));
+        bassert!(calls == 1);
+    }
+
+    #[test]
+    fn neq_success_passes() {
+        let smaller = 2;
+        let larger = 3;
+        bassert!(smaller != larger);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `foo != bar`\nfoo: `42`,\nbar: `42`")]
+    fn neq_failure_prints_correct_message() {
+        let foo = 42;
+        let bar = 42;
+        bassert!(foo != bar);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `foo != bar`\nfoo: `42`,\nbar: `42`: It be broken"
+    )]
+    fn neq_failure_with_custom_message_prints_correct_message() {
+        let foo = 42;
+        let bar = 42;
+        bassert!(foo != bar, "It be {}", "broken");
+    }
+
+    #[test]
+    fn match_success_passes() {
+        let val: Option<i64> = Some(100);
+        bassert!(Some(_) = val);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `None = val`\nval: `Some(100)`")]
+    fn match_failure_prints_correct_message() {
+        let val: Option<i64> = Some(100);
+        bassert!(None = val);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `None = val`\nval: `Some(100)`: That was unexpected! xyzzy plugh"
+    )]
+    fn match_failure_with_custom_message_prints_correct_message() {
+        let val: Option<i64> = Some(100);
+        bassert!(None = val, "That was unexpected! {} {}", "xyzzy", "plugh");
+    }
+
+    #[derive(Debug)]
+    enum Shape {
+        Circle { radius: u32 },
+        Square(u32),
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `Shape::Circle { .. } = shape`\nshape: `Square(2)`\nactual variant: `Square`"
+    )]
+    fn match_failure_prints_actual_variant() {
+        let shape = Shape::Square(2);
+        bassert!(Shape::Circle { .. } = shape);
+    }
+
+    #[test]
+    fn match_failure_prints_actual_variant_only_if_not_in_pattern() {
+        bassert!(Shape::Square(1..) = (Shape::Square(2)));
+        let shape = Shape::Circle { radius: 3 };
+        let failure = try_bassert!(Shape::Circle { radius: 1 | 2 } = shape).unwrap_err();
+        bassert!(
+            (message_of(failure))
+                == "assertion failed: `Shape::Circle { radius: 1 | 2 } = shape`\nshape: `Circle { radius: 3 }`"
+        );
+
+        // Unit variants and values which are not enums are printed as is.
+        let ordering = core::cmp::Ordering::Less;
+        let failure = try_bassert!(core::cmp::Ordering::Greater = ordering).unwrap_err();
+        bassert!(!message_of(failure).contains("variant"));
+        let v: &[i32] = &[1, 2];
+        let failure = try_bassert!([_] = v).unwrap_err();
+        bassert!(!message_of(failure).contains("variant"));
+        let val = Some(100);
+        let failure = try_bassert!(None = val).unwrap_err();
+        bassert!((message_of(failure).ends_with("\nactual variant: `Some`")));
+    }
+
+    #[test]
+    fn or_pattern_match_success_passes() {
+        let val: Option<i64> = Some(2);
+        bassert!(Some(1) | Some(2) = val);
+        bassert!(Some(1) | Some(2) = val, "some {}", "message");
+        let res: Result<i64, ()> = Ok(1);
+        bassert!(Ok(1 | 2) | Err(_) = res);
+        bassert!(Some(1) | Some(2) if val.is_some() = val);
+        bassert!(|Some(1)| Some(2) = val);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `Some(1) | Some(2) | None = val`\nval: `Some(3)`")]
+    fn or_pattern_match_failure_prints_whole_pattern() {
+        let val: Option<i64> = Some(3);
+        bassert!(Some(1) | Some(2) | None = val);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `Ok(_) | Err(std::cmp::Ordering::Less) = res`\nres: `Err(Greater)`: some message"
+    )]
+    fn or_pattern_match_failure_with_custom_message_prints_correct_message() {
+        let res: Result<i64, std::cmp::Ordering> = Err(std::cmp::Ordering::Greater);
+        bassert!(
+            Ok(_) | Err(std::cmp::Ordering::Less) = res,
+            "some {}",
+            "message"
+        );
+    }
+
+    #[test]
+    fn try_or_pattern_match_failure() {
+        let val: Option<i64> = Some(3);
+        let failure = try_bassert!(Some(1) | Some(2) = val).unwrap_err();
+        bassert!((failure.lhs_expr()) == "Some(1) | Some(2)");
+    }
+
+    #[test]
+    fn complex_operands_success_passes() {
+        let (a, b) = (3, 2);
+        let v = [1, 2, 3];
+        bassert!(a + 1 == b * 2);
+        bassert!((v.len()) == a.min(10));
+        bassert!(a * 2 > b + 1, "some {}", "message");
+        bassert!(v.len() == a);
+        bassert!(v.len() != Vec::<i32>::new().len());
+        bassert!(std::collections::HashMap::<u8, u8>::new().len() == 0);
+        let c: i32 = 2;
+        bassert!(<i32 as Default>::default() < c);
+        bassert!(-c < 0 - 1);
+        bassert!(*v.first().unwrap() as f64 / 10.0 ~= 0.1);
+        bassert!(v[1] as f64 ~= 1.0 + 1.0, eps = 1e-9);
+        bassert!(a + 1 in 0..10);
+        bassert!(Some(1) = v.first().copied());
+        bassert!(Some(&x) if x > 2 = v.last());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `a + 1 == b * 2`\na + 1: `4`,\nb * 2: `6`: some message\ndifference: `-2`"
+    )]
+    fn complex_operands_failure_prints_correct_message() {
+        let (a, b) = (3, 3);
+        bassert!(a + 1 == b * 2, "some {}", "message");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `v.len() >= Vec::<i32>::with_capacity(10).capacity()`\nv.len(): `3`,\nVec::<i32>::with_capacity(10).capacity(): `10`\ndifference: `-7`"
+    )]
+    fn complex_operands_with_generics_failure_prints_correct_message() {
+        let v = [1, 2, 3];
+        bassert!(v.len() >= Vec::<i32>::with_capacity(10).capacity());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `v.iter().map(|x| x * 2).collect::<Vec<_>>() == vec![2, 4]`\nv.iter().map(|x| x * 2).collect::<Vec<_>>(): `[2, 4, 6]`,\nvec![2, 4]: `[2, 4]`"
+    )]
+    fn complex_operands_with_nested_generics_failure_prints_correct_message() {
+        let v = [1, 2, 3];
+        bassert!(v.iter().map(|x| x * 2).collect::<Vec<_>>() == vec![2, 4]);
+    }
+
+    #[test]
+    fn long_complex_operands_success_passes() {
+        let v = [1, 2, 3];
+        bassert!(
+            v.iter()
+                .map(|x| x * 2)
+                .filter(|x| *x > 2)
+                .map(|x| x + 1)
+                .collect::<Vec<_>>()
+                .len()
+                + v.len() * 2
+                - 1
+                == v.iter()
+                    .copied()
+                    .chain(v.iter().copied())
+                    .chain(Some(4))
+                    .count()
+                    + v.len()
+                    - 1
+                    - 2
+        );
+    }
+
+    #[test]
+    fn complex_operands_are_evaluated_once() {
+        let mut calls = 0;
+        let mut next = || {
+            calls += 1;
+            calls
+        };
+        bassert!(next() - 1 == 0);
+        let failure = try_bassert!(next() * 2 == next() * 10).unwrap_err();
+        bassert!((failure.lhs()) == Some("4"));
+        bassert!((failure.rhs()) == Some("30"));
+        bassert!(0 < next() - 3 <= 1);
+        bassert!(calls == 4);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `0 <= idx + 1 < v.len()`\nbroken link: `idx + 1 < v.len()`\n0: `0`,\nidx + 1: `3`,\nv.len(): `3`"
+    )]
+    fn chain_with_complex_operands_failure_prints_broken_link() {
+        let v = [1, 2, 3];
+        let idx = 2;
+        bassert!(0 <= idx + 1 < v.len());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `y * 2 == 4`\ny * 2: `6`,\n4: `4`: right conjunct of `0 <= x < 10 && y * 2 == 4` failed"
+    )]
+    fn chain_in_conjunction_failure_prints_correct_message() {
+        let (x, y) = (5, 3);
+        bassert!(0 <= x < 10 && y * 2 == 4);
+    }
+
+    #[test]
+    fn lower_precedence_operators_are_plain_boolean_expressions() {
+        let (a, b) = (1, 2);
+        bassert!(a == 2 || b == 2);
+        bassert!(a == 1 && b == 1 || b == 2);
+        bassert!(a == 0 || b == 1 || a + 1 == b);
+        let failure = try_bassert!(a == 2 || b == 1).unwrap_err();
+        bassert!((failure.kind()) == None);
+        bassert!((message_of(failure)) == "assertion failed: `a == 2 || b == 1`");
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `!a == b`\n!a: `false`,\nb: `true`")]
+    fn negated_operand_is_compared() {
+        let (a, b) = (true, true);
+        bassert!(!a == b);
+    }
+
+    #[test]
+    fn bool_success_passes() {
+        let list = [1, 2, 3];
+        bassert!(true);
+        bassert!(list.contains(&2));
+        bassert!(list.contains(&2), "foo bar {}", "some message");
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `list.contains(&4)`")]
+    fn bool_failure_prints_correct_message() {
+        let list = [1, 2, 3];
+        bassert!(list.contains(&4));
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `list.contains(&4)`: Not found: 4")]
+    fn bool_failure_with_custom_message_prints_correct_message() {
+        let list = [1, 2, 3];
+        bassert!(list.contains(&4), "Not found: {}", 4);
+    }
+
+    #[test]
+    fn not_success_passes() {
+        let list = [1, 2, 3];
+        bassert!(!list.is_empty());
+        bassert!(!list.contains(&4), "foo bar {}", "some message");
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `!list.is_empty()`\nlist.is_empty(): `true`")]
+    fn not_failure_prints_correct_message() {
+        let list: [i32; 0] = [];
+        bassert!(!list.is_empty());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `!list.contains(&2)`\nlist.contains(&2): `true`: Found: 2"
+    )]
+    fn not_failure_with_custom_message_prints_correct_message() {
+        let list = [1, 2, 3];
+        bassert!(!list.contains(&2), "Found: {}", 2);
+    }
+
+    #[test]
+    fn not_evaluates_inner_expression_once() {
+        let mut calls = 0;
+        let mut check = || {
+            calls += 1;
+            false
+        };
+        bassert!(!check());
+        bassert!(calls == 1);
+    }
+
+    #[test]
+    fn and_success_passes() {
+        let a = 3;
+        let b = 5;
+        bassert!(a > 0 && b < 10);
+        bassert!(a > 0 && b < 10 && a != b, "foo bar {}", "some message");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `a > 0`\na: `-3`,\n0: `0`: left conjunct of `a > 0 && b < 10` failed"
+    )]
+    fn and_left_failure_prints_correct_message() {
+        let a = -3;
+        let b = 5;
+        bassert!(a > 0 && b < 10);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `b < 10`\nb: `15`,\n10: `10`: right conjunct of `a > 0 && b < 10` failed"
+    )]
+    fn and_right_failure_prints_correct_message() {
+        let a = 3;
+        let b = 15;
+        bassert!(a > 0 && b < 10);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `b < 10`\nb: `15`,\n10: `10`: right conjunct of `a > 0 && b < 10` failed: b is too big"
+    )]
+    fn and_failure_with_custom_message_prints_correct_message() {
+        let a = 3;
+        let b = 15;
+        bassert!(a > 0 && b < 10, "b is {}", "too big");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `a < 0`\na: `3`,\n0: `0`: left conjunct of `a < 0 && b < 10` failed: a is 3"
+    )]
+    fn and_failure_with_closure_message_prints_correct_message() {
+        let a = 3;
+        let b = 15;
+        bassert!(a < 0 && b < 10, || format!("a is {}", a));
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `!list.is_empty()`\nlist.is_empty(): `true`")]
+    fn and_with_negated_conjunct_prints_correct_message() {
+        let list: [i32; 0] = [];
+        bassert!(!list.is_empty() && list.len() < 10);
+    }
+
+    #[test]
+    fn and_short_circuits() {
+        let mut calls = 0;
+        let mut check = || {
+            calls += 1;
+            true
+        };
+        let a = 3;
+        bassert!(a > 0 && check());
+        bassert!(calls == 1);
+    }
+
+    #[test]
+    fn chain_success_passes() {
+        let idx = 2;
+        let len = 3;
+        bassert!(0 <= idx < len);
+        bassert!(0 < idx <= len);
+        bassert!(len > idx >= 0, "foo bar {}", "some message");
+        bassert!(len >= len > idx);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `0 <= idx < len`\nbroken link: `idx < len`\n0: `0`,\nidx: `5`,\nlen: `3`"
+    )]
+    fn chain_failure_prints_correct_message() {
+        let idx = 5;
+        let len = 3;
+        bassert!(0 <= idx < len);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `len > idx >= 0`\nbroken link: `len > idx`\nlen: `3`,\nidx: `5`,\n0: `0`: out of bounds"
+    )]
+    fn chain_failure_with_custom_message_prints_correct_message() {
+        let idx = 5;
+        let len = 3;
+        bassert!(len > idx >= 0, "out of {}", "bounds");
+    }
+
+    #[test]
+    fn chain_evaluates_middle_operand_once() {
+        let mut calls = 0;
+        let mut next = || {
+            calls += 1;
+            1
+        };
+        bassert!(0 <= (next()) < 2);
+        bassert!(calls == 1);
+    }
+
+    #[test]
+    fn range_success_passes() {
+        let x = 10;
+        let (low, high) = (0, 100);
+        bassert!(x in 0..11);
+        bassert!(x in 0..=10);
+        bassert!(x in ..);
+        bassert!(x in low..high, "foo bar {}", "some message");
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `x in 0..10`\nx: `10`,\n0..10: `0..10`")]
+    fn range_failure_prints_correct_message() {
+        let x = 10;
+        bassert!(x in 0..10);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `x in low..=high`\nx: `-1`,\nlow..=high: `0..=10`: too small"
+    )]
+    fn range_failure_with_custom_message_prints_correct_message() {
+        let x = -1;
+        let (low, high) = (0, 10);
+        bassert!(x in low..=high, "too {}", "small");
+    }
+
+    #[test]
+    fn range_evaluates_value_once() {
+        let mut calls = 0;
+        let mut next = || {
+            calls += 1;
+            5
+        };
+        bassert!((next()) in 0..10);
+        bassert!(calls == 1);
+    }
+
+    #[test]
+    fn approx_success_passes() {
+        let computed = 0.1 + 0.2;
+        let expected = 0.3;
+        bassert!(computed ~= expected);
+        bassert!(1e9 ~= (1e9 + 1e-7));
+        bassert!(computed ~= 0.31, eps = 0.1);
+        bassert!(computed ~= 0.31, eps = 0.1, "foo bar {}", "some message");
+        bassert!((f32::INFINITY) ~= (f32::INFINITY));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `computed ~= expected`\ncomputed: `0.5`,\nexpected: `0.25`,\ndifference: `0.25`,\ntolerance: `8.881784197001252e-16`"
+    )]
+    fn approx_failure_prints_correct_message() {
+        let computed = 0.5;
+        let expected = 0.25;
+        bassert!(computed ~= expected);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `computed ~= expected`\ncomputed: `0.5`,\nexpected: `0.25`,\ndifference: `0.25`,\ntolerance: `0.125`: too far off"
+    )]
+    fn approx_failure_with_eps_and_custom_message_prints_correct_message() {
+        let computed = 0.5;
+        let expected = 0.25;
+        bassert!(computed ~= expected, eps = 0.125, "too far {}", "off");
+    }
+
+    #[test]
+    fn approx_relative_success_passes() {
+        bassert!(1e9 ~= (1e9 + 1.0), rel = 1e-6);
+        bassert!(0.0 ~= 0.0, rel = 1e-6, "equal {}", "zeros");
+        bassert!((1e-9f32) ~= (1.0000001e-9f32), rel = 1e-6);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `small ~= 2e-9`\nsmall: `1e-9`,\n2e-9: `2e-9`,\ndifference: `1e-9`,\nrelative difference: `0.5`,\nrelative tolerance: `1e-6`: too far off"
+    )]
+    fn approx_relative_failure_prints_relative_difference() {
+        let small = 1e-9;
+        bassert!(small ~= 2e-9, rel = 1e-6, "too far {}", "off");
+    }
+
+    #[test]
+    fn bassert_approx_slice_eq_success_passes() {
+        let sums: Vec<f64> = (1..4).map(|n| (0..n * 10).map(|_| 0.1).sum()).collect();
+        bassert_approx_slice_eq!(sums, [1.0, 2.0, 3.0]);
+        bassert_approx_slice_eq!(sums, vec![1.0, 2.0, 3.1], eps = 0.2, "close {}", "enough");
+        bassert_approx_slice_eq!([f32::INFINITY], [f32::INFINITY], "infinities are equal");
+        let empty: [f64; 0] = [];
+        bassert_approx_slice_eq!(empty, Vec::new());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `computed ~= expected`\ncomputed: `[1.0, 2.0, 3.5]`,\nexpected: `[1.0, 2.1, 3.0]`,\ndifference: `0.5`,\ntolerance: `0.25`: oops\nfirst difference at index 2: left = `3.5`, right = `3.0`"
+    )]
+    fn bassert_approx_slice_eq_failure_prints_first_difference() {
+        let (computed, expected) = (vec![1.0, 2.0, 3.5], [1.0, 2.1, 3.0]);
+        bassert_approx_slice_eq!(computed, expected, eps = 0.25, "oops");
+    }
+
+    #[test]
+    fn bassert_approx_slice_eq_failure_prints_lengths_and_nan() {
+        let (computed, expected) = ([1.0, 2.0], [1.0, 2.5, 3.0]);
+        let message = panic_message(|| bassert_approx_slice_eq!(computed, expected));
+        bassert!(message.ends_with(
+            "expected: `[1.0, 2.5, 3.0]`\nfirst difference at index 1: left = `2.0`, right = `2.5`\nlengths differ: left = `2`, right = `3`"
+        ));
+
+        let nan = [f64::NAN];
+        let message = panic_message(|| bassert_approx_slice_eq!(nan, nan, eps = 1.0));
+        bassert!(message.ends_with(
+            "difference: `NaN`,\ntolerance: `1.0`\nfirst difference at index 0: left = `NaN`, right = `NaN`"
+        ));
+    }
+
+    #[test]
+    fn bassert_approx_opt_eq_success_passes() {
+        let sum: Option<f64> = Some((0..10).map(|_| 0.1).sum());
+        bassert_approx_opt_eq!(sum, Some(1.0));
+        bassert_approx_opt_eq!(Some(1.0f32), Some(1.05), eps = 0.1, "close {}", "enough");
+        bassert_approx_opt_eq!(None::<f64>, None);
+    }
+
+    #[test]
+    fn bassert_approx_opt_eq_failure_distinguishes_none_from_difference() {
+        let (measured, expected) = (Some(3.5), Some(3.0));
+        let message = panic_message(|| bassert_approx_opt_eq!(measured, expected, eps = 0.25));
+        bassert!(message.starts_with(
+            "assertion failed: `measured ~= expected`\nmeasured: `Some(3.5)`,\nexpected: `Some(3.0)`,\ndifference: `0.5`,\ntolerance: `0.25`"
+        ));
+
+        let none: Option<f64> = None;
+        let message = panic_message(|| bassert_approx_opt_eq!(expected, none, "oops"));
+        bassert!(message.starts_with(
+            "assertion failed: `expected ~= none`\nexpected: `Some(3.0)`,\nnone: `None`: oops\nnone is `None`, but expected is not"
+        ));
+    }
+
+    #[test]
+    fn bassert_bits_eq_prints_hex_binary_and_xor() {
+        let flags: u16 = 0b1010;
+        bassert_bits_eq!(flags, 0xa);
+        bassert_bits_eq!(-1i8, i8::MIN | i8::MAX, "all {}", "set");
+        let message = panic_message(|| bassert_bits_eq!(flags, 0x0f_u16, "flags"));
+        bassert!(message.starts_with(
+            "assertion failed: `flags == 0x0f_u16`\nflags: `0xa`,\n0x0f_u16: `0xf`,\ndiffering bits: `0x5`: flags\nbits: left = `0b0000000000001010`, right = `0b0000000000001111`"
+        ));
+        let message = panic_message(|| bassert_bits_eq!(-2i8, 1));
+        bassert!(message.contains("bits: left = `0b11111110`, right = `0b00000001`"));
+    }
+
+    #[test]
+    fn bassert_ulps_eq_success_passes() {
+        let sum: f64 = (0..10).map(|_| 0.1).sum();
+        bassert_ulps_eq!(sum, 1.0, ulps = 1);
+        bassert_ulps_eq!(0.0f32, -0.0f32, ulps = 0, "zeros are {}", "equal");
+        bassert_ulps_eq!(f64::from_bits(1), -f64::from_bits(1), ulps = 2);
+        bassert_ulps_eq!(f32::MAX, f32::INFINITY, ulps = 1);
+        bassert_ulps_eq!(f64::NEG_INFINITY, f64::NEG_INFINITY, ulps = 0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `sum ~= 1.0`\nsum: `0.9999999999999999`,\n1.0: `1.0`,\nULP distance: `1`,\nmaximum ULP distance: `0`: oops"
+    )]
+    fn bassert_ulps_eq_failure_prints_distance() {
+        let sum: f64 = (0..10).map(|_| 0.1).sum();
+        bassert_ulps_eq!(sum, 1.0, ulps = 0, "oops");
+    }
+
+    #[test]
+    fn bassert_ulps_eq_failure_on_opposite_signs_and_nan() {
+        let message = panic_message(|| bassert_ulps_eq!(1.0f32, -1.0f32, ulps = 4));
+        bassert!(message.ends_with("\nULP distance: `2130706432`,\nmaximum ULP distance: `4`"));
+
+        let nan = f64::NAN;
+        let message = panic_message(|| bassert_ulps_eq!(nan, nan, ulps = 4));
+        bassert!(
+            message
+                == "assertion failed: `nan ~= nan`\nnan: `NaN`,\nnan: `NaN`,\nULP distance: `undefined (NaN)`,\nmaximum ULP distance: `4`"
+        );
+    }
+
+    #[test]
+    fn bassert_total_eq_success_passes() {
+        let nan = f64::NAN;
+        bassert_total_eq!(nan, nan);
+        bassert_total_eq!(-0.0f32, -0.0f32, "same {}", "bits");
+        bassert_total_eq!(0.1 + 0.2, 0.30000000000000004);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `(zero).to_bits() == (0.0).to_bits()`\nzero: `-0.0`,\n0.0: `0.0`: oops\nbits: left = `0x8000000000000000`, right = `0x0000000000000000`"
+    )]
+    fn bassert_total_eq_failure_prints_bits() {
+        let zero = -0.0f64;
+        bassert_total_eq!(zero, 0.0, "oops");
+    }
+
+    #[test]
+    fn bassert_total_eq_distinguishes_nan_payloads() {
+        let (lhs, rhs) = (f32::NAN, f32::from_bits(f32::NAN.to_bits() | 1));
+        let message = panic_message(|| bassert_total_eq!(lhs, rhs));
+        bassert!(message.ends_with("\nbits: left = `0x7fc00000`, right = `0x7fc00001`"));
+    }
+
+    #[test]
+    fn bassert_total_eq_parenthesizes_negative_operands() {
+        let message = panic_message(|| bassert_total_eq!(0.0f64, -0.0f64));
+        bassert!(
+            message.starts_with("assertion failed: `(0.0f64).to_bits() == (-0.0f64).to_bits()`\n")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `nan ~= nan`")]
+    fn approx_nan_failure() {
+        let nan = f64::NAN;
+        bassert!(nan ~= nan);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `measured == expected`\nmeasured: `10`,\nexpected: `12`\ndifference: `-2`"
+    )]
+    fn eq_failure_prints_numeric_difference() {
+        let measured = 10;
+        let expected = 12;
+        bassert!(measured == expected);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `small > big`\nsmall: `1`,\nbig: `255`: not bigger\ndifference: `-254`"
+    )]
+    fn gt_failure_prints_unsigned_difference_without_overflow() {
+        let small: u8 = 1;
+        let big: u8 = 255;
+        bassert!(small > big, "not {}", "bigger");
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `x < y`\nx: `1.5`,\ny: `1.0`\ndifference: `0.5`")]
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
+    fn lt_failure_prints_float_difference() {
+        let x = 1.5;
+        let y = 1.0;
+        bassert!(x < y);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `x < y`\nvalues are not comparable (partial_cmp returned None)\nx: `NaN`,\ny: `1.0`"
+    )]
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
+    fn lt_failure_with_nan_prints_not_comparable() {
+        let x = f64::NAN;
+        let y = 1.0;
+        let message = panic_message(|| bassert!(x < y));
+        bassert!(!(message.contains("difference")));
+        bassert!(x < y);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `x >= y`\nx: `1.0`,\ny: `1.5`\ndifference")]
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
+    fn ge_failure_with_comparable_values_prints_no_note() {
+        let x = 1.0;
+        let y = 1.5;
+        bassert!(x >= y);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `0.0 <= x <= 1.0`\nbroken link: `0.0 <= x`\nvalues are not comparable (partial_cmp returned None)\n0.0: `0.0`,\nx: `NaN`,\n1.0: `1.0`"
+    )]
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
+    fn chain_failure_with_nan_prints_not_comparable() {
+        let x = f64::NAN;
+        bassert!(0.0 <= x <= 1.0);
+    }
+
+    #[test]
+    fn difference_is_omitted_for_non_numeric_operands() {
+        let result = std::panic::catch_unwind(|| {
+            let foo = 'f';
+            let bar = 'b';
+            bassert!(foo == bar);
+        });
+        let payload = result.unwrap_err();
+        let message = payload.downcast_ref::<String>().unwrap();
+        bassert!(message == "assertion failed: `foo == bar`\nfoo: `'f'`,\nbar: `'b'`");
+    }
+
+    #[test]
+    fn str_eq_failure_prints_first_mismatch() {
+        let result = std::panic::catch_unwind(|| {
+            let actual = String::from("first line\nthe quick brown fox jumps over the lazy dog");
+            let expected = "first line\nthe quick brown fox jumped over the lazy dog";
+            bassert!(actual == expected);
+        });
+        let payload = result.unwrap_err();
+        let message = payload.downcast_ref::<String>().unwrap();
+        let expected_message = [
+            "assertion failed: `actual == expected`",
+            "actual: `\"first line\\nthe quick brown fox jumps over the lazy dog\"`,",
+            "expected: `\"first line\\nthe quick brown fox jumped over the lazy dog\"`",
+            "mismatch at line 2, column 25:",
+            "left:  `...quick brown fox jumps over the lazy dog`",
+            "right: `...quick brown fox jumped over the lazy dog`",
+            "                               ^",
+        ];
+        bassert!(*message == expected_message.join("\n"));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `actual == expected`\nactual: `\"ab\"`,\nexpected: `\"ab\\tc\"`\nmismatch at line 1, column 3:\nleft:  `ab`\nright: `ab\\tc`\n          ^"
+    )]
+    fn str_eq_failure_escapes_context() {
+        let actual = "ab";
+        let expected = "ab\tc";
+        bassert!(actual == expected);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `actual == expected`\nactual: `[1, 2, 3]`,\nexpected: `[1, 5, 3]`\nfirst difference at index 1: left = `2`, right = `5`"
+    )]
+    fn slice_eq_failure_prints_first_difference() {
+        let actual = alloc::vec![1, 2, 3];
+        let expected = [1, 5, 3];
+        bassert!(actual == expected);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `actual == expected`\nactual: `[1, 2]`,\nexpected: `[1, 2, 3, 4]`\nlengths differ: left = `2`, right = `4`"
+    )]
+    fn slice_eq_failure_prints_length_mismatch() {
+        let actual: &[u8] = &[1, 2];
+        let expected = alloc::vec![1, 2, 3, 4];
+        bassert!(actual == expected);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "assertion failed: `x == 2`\nx: `1`,\n2: `2`")]
+    fn debug_bassert_checks_in_debug_builds() {
+        let x = 1;
+        debug_bassert!(x == 2);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn release_bassert_is_no_op_in_debug_builds() {
+        let x = 1;
+        release_bassert!(x == 2);
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    #[should_panic(expected = "assertion failed: `x == 2`\nx: `1`,\n2: `2`")]
+    fn release_bassert_checks_in_release_builds() {
+        let x = 1;
+        release_bassert!(x == 2);
+    }
+
+    #[test]
+    fn bassert_val_returns_lhs() {
+        let v = alloc::vec![1, 2, 3];
+        let len = bassert_val!(v.len() == 3);
+        bassert!(len == 3);
+        let name = bassert_val!(String::from("abc") != "", "name should not be empty");
+        bassert!(name == "abc");
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `v.len() > 3`\nv.len(): `3`,\n3: `3`: too short")]
+    fn bassert_val_failure_panics() {
+        let v = [1, 2, 3];
+        let _ = bassert_val!(v.len() > 3, "too short");
+    }
+
+    #[test]
+    fn bassert_val_evaluates_once() {
+        let mut calls = 0;
+        let mut next = || {
+            calls += 1;
+            calls
+        };
+        let value = bassert_val!(next() < 10);
+        bassert!(value == 1);
+        bassert!(calls == 1);
+    }
+
+    #[test]
+    fn bassert_let_returns_bindings() {
+        let result: Result<String, i32> = Ok(String::from("owned"));
+        let value = bassert_let!(Ok(value) = result);
+        bassert!(value == "owned");
+        let (a, b) = bassert_let!(Some((a, b)) = Some((1, 2)));
+        bassert!((a, b) == (1, 2));
+        let nested = bassert_let!(
+            Some(Ok(nested)) = Some(Ok::<_, ()>(3)),
+            "expected some value"
+        );
+        bassert!(nested == 3);
+    }
+
+    #[test]
+    fn bassert_let_failure_describes_the_same_message_as_bassert() {
+        let value: Option<i32> = None;
+        bassert!(
+            (panic_message(|| {
+                let _ = bassert_let!(Some(x) = value, "because {}", 42);
+            })) == (panic_message(|| bassert!(Some(_x) = value, "because {}", 42))
+                .replace("_x", "x"))
+        );
+    }
+
+    #[test]
+    fn bassert_matches_takes_the_value_first() {
+        let value: Result<i32, String> = Ok(42);
+        bassert_matches!(value, Ok(_));
+        bassert_matches!(value, Ok(x) if x > 5 && x < 50, "expected {}", "a big value");
+        bassert_matches!(Some((1, 2)), Some((1, _)) | None,);
+        bassert!(
+            (panic_message(|| bassert_matches!(value, Ok(x) if x < 5, "because {}", 42)))
+                == "assertion failed: `Ok(x) if x < 5 = value`\nguard failed: `x < 5`\nvalue: `Ok(42)`,\nx: `42`: because 42"
+        );
+        bassert!((panic_message(|| bassert_matches!(value, Err(_))))
+            .starts_with("assertion failed: `Err(_) = value`\nvalue: `Ok(42)`"));
+    }
+
+    #[test]
+    fn bassert_matches_matches_the_value_itself() {
+        let name = Some(String::from("bassert"));
+        bassert_matches!(name, Some(s) if s.len() > 3);
+        bassert!(
+            (panic_message(|| bassert_matches!(Some(3), Some(x) if x > 10)))
+                == "assertion failed: `Some(x) if x > 10 = Some(3)`\nguard failed: `x > 10`\nSome(3): `Some(3)`,\nx: `3`"
+        );
+        bassert!((panic_message(|| bassert_matches!(None::<i32>, Some(x) if x > 10)))
+            .starts_with("assertion failed: `Some(x) if x > 10 = None::<i32>`\npattern did not match: `Some(x)`"));
+    }
+
+    #[test]
+    fn bassert_is_variant_borrows_the_value() {
+        let result: Result<String, i32> = Ok(String::from("owned"));
+        bassert_is_ok!(result);
+        bassert_is_some!(result.as_ref().ok(), "because {}", 42);
+        bassert!(result == (Ok(String::from("owned"))));
+        bassert_is_err!("x".parse::<u8>());
+        bassert_is_none!(None::<i32>);
+        bassert!(
+            (panic_message(|| bassert_is_err!(result, "because {}", 42))).starts_with(
+                "assertion failed: `result.is_err()`\nresult: `Ok(\"owned\")`: because 42\nexpected `Err(_)`, got `Ok(_)`"
+            )
+        );
+        bassert!(
+            (panic_message(|| bassert_is_some!(None::<i32>)))
+                .starts_with("assertion failed: `None::<i32>.is_some()`\nNone::<i32>: `None`\nexpected `Some(_)`, got `None`")
+        );
+    }
+
+    #[test]
+    fn bassert_pred_names_the_predicate() {
+        fn is_even(n: &i32) -> bool {
+            n % 2 == 0
+        }
+        bassert_pred!(4, is_even);
+        bassert_pred!("abc", |s: &&str| s.len() == 3);
+        bassert!(
+            (panic_message(|| bassert_pred!(1 + 2, is_even))).starts_with(
+                "assertion failed: `is_even(1 + 2)`\npredicate `is_even` failed for 1 + 2: `3`"
+            )
+        );
+        bassert!(
+            (panic_message(|| bassert_pred!(3, |n: &i32| *n < 3, "not {}", "small")))
+                .starts_with("assertion failed: `(|n: &i32| *n < 3)(3)`: not small\npredicate `|n: &i32| *n < 3` failed for 3: `3`")
+        );
+    }
+
+    #[test]
+    fn bassert_len_success_passes() {
+        let v = alloc::vec![1, 2, 3];
+        bassert_len!(v, 3);
+        bassert_len!("abc", 1 + 2, "strings have a length too");
+        bassert_len!(std::collections::HashMap::from([(1, 2)]), 1);
+        bassert_empty!(Vec::<i32>::new());
+        bassert_empty!("");
+        bassert_non_empty!(v);
+        bassert_non_empty!(&v[..1]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `v.len() == 3`\nv: `[1, 2]`: too short\nexpected length `3`, actual length `2`"
+    )]
+    fn bassert_len_failure_prints_lengths_and_collection() {
+        let v = [1, 2];
+        bassert_len!(v, 3, "too short");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `v.is_empty()`\nv: `[1]`\nexpected length `0`, actual length `1`"
+    )]
+    fn bassert_empty_failure_prints_length() {
+        let v = alloc::vec![1];
+        bassert_empty!(v);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `!v.is_empty()`\nv: `[]`\nexpected a non-zero length, actual length `0`"
+    )]
+    fn bassert_non_empty_failure_prints_length() {
+        let v: Vec<i32> = Vec::new();
+        bassert_non_empty!(v);
+    }
+
+    #[test]
+    fn bassert_sorted_success_passes() {
+        bassert_sorted!([1, 2, 2, 3]);
+        bassert_sorted!(alloc::vec!["a", "b"]);
+        bassert_sorted!(Vec::<f64>::new());
+        let v = [3, 2, 1];
+        bassert_sorted_by!(v, |a, b| b.cmp(a));
+        bassert_sorted_by!(&v[..], |a, b| b.cmp(a), "descending {}", "order");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `v.is_sorted()`\nv: `[1, 2, 5, 3, 4]`: oops\nelement at index 2 (`5`) > element at index 3 (`3`)"
+    )]
+    fn bassert_sorted_failure_prints_first_inversion() {
+        let v = alloc::vec![1, 2, 5, 3, 4];
+        bassert_sorted!(v, "oops");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "element at index 0 (`1.0`) cannot be compared to element at index 1 (`NaN`)"
+    )]
+    fn bassert_sorted_failure_explains_incomparable_elements() {
+        bassert_sorted!([1.0, f64::NAN, 2.0]);
+    }
+
+    #[test]
+    fn bassert_monotonic_success_passes() {
+        bassert_monotonic!([1, 2, 5]);
+        bassert_monotonic!(Vec::<f64>::new());
+        bassert_monotonic_non_decreasing!([1, 2, 2, 5]);
+        bassert_monotonic_non_decreasing!(alloc::vec![0.5, 0.5], "constant {}", "values");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `ids.is_sorted_by(|a, b| a < b)`\nids: `[1, 3, 3, 4]`: ids repeat\nelement at index 1 (`3`) >= element at index 2 (`3`)"
+    )]
+    fn bassert_monotonic_failure_prints_first_repeated_pair() {
+        let ids = [1, 3, 3, 4];
+        bassert_monotonic!(ids, "ids repeat");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `v.is_sorted_by(|a, b| a <= b)`\nv: `[1, 3, 3, 2]`\nelement at index 2 (`3`) > element at index 3 (`2`)"
+    )]
+    fn bassert_monotonic_non_decreasing_failure_prints_first_decrease() {
+        let v = [1, 3, 3, 2];
+        bassert_monotonic_non_decreasing!(v);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "element at index 0 (`0.0`) cannot be compared to element at index 1 (`NaN`)"
+    )]
+    fn bassert_monotonic_failure_explains_incomparable_elements() {
+        bassert_monotonic!([0.0, f64::NAN, 1.0]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `v.is_sorted_by(|a, b| b.cmp(a))`\nv: `[3, 1, 2]`\nelement at index 1 (`1`) > element at index 2 (`2`)"
+    )]
+    fn bassert_sorted_by_failure_prints_first_inversion() {
+        let v = [3, 1, 2];
+        bassert_sorted_by!(v, |a, b| b.cmp(a));
+    }
+
+    #[test]
+    fn bassert_ord_consistent_success_passes() {
+        bassert_ord_consistent!(1, 2);
+        bassert_ord_consistent!("b", "a", "strings are {}", "ordered");
+        bassert_ord_consistent!(alloc::vec![1, 2], alloc::vec![1, 2]);
+    }
+
+    #[test]
+    fn bassert_ord_consistent_failure_prints_broken_law() {
+        use core::cmp::Ordering;
+
+        #[derive(Debug, PartialEq, Eq, PartialOrd)]
+        struct Backwards(u32);
+        // Deliberately inconsistent with the derived `PartialOrd`.
+        #[allow(clippy::derive_ord_xor_partial_ord)]
+        impl Ord for Backwards {
+            fn cmp(&self, other: &Self) -> Ordering {
+                other.0.cmp(&self.0)
+            }
+        }
+
+        #[derive(Debug, Eq)]
+        struct AllEqual(u32);
+        impl PartialEq for AllEqual {
+            fn eq(&self, _other: &Self) -> bool {
+                true
+            }
+        }
+        impl PartialOrd for AllEqual {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for AllEqual {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        #[derive(Debug, PartialEq, Eq)]
+        struct AlwaysLess(u32);
+        impl PartialOrd for AlwaysLess {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for AlwaysLess {
+            fn cmp(&self, _other: &Self) -> Ordering {
+                Ordering::Less
+            }
+        }
+
+        let (a, b) = (Backwards(1), Backwards(2));
+        bassert!(
+            (panic_message(|| bassert_ord_consistent!(a, b, "oops")))
+                == "assertion failed: `a.partial_cmp(&b) == Some(a.cmp(&b))`\na: `Backwards(1)`,\nb: `Backwards(2)`: oops\na.partial_cmp(&b) returned `Some(Less)`, but a.cmp(&b) returned `Greater`"
+        );
+        let (a, b) = (AllEqual(1), AllEqual(2));
+        bassert!(
+            (panic_message(|| bassert_ord_consistent!(a, b)))
+                == "assertion failed: `(a == b) == a.cmp(&b).is_eq()`\na: `AllEqual(1)`,\nb: `AllEqual(2)`\na == b returned `true`, but a.cmp(&b) returned `Less`"
+        );
+        let (a, b) = (AlwaysLess(1), AlwaysLess(2));
+        bassert!(
+            (panic_message(|| bassert_ord_consistent!(a, b)))
+                == "assertion failed: `b.cmp(&a) == a.cmp(&b).reverse()`\na: `AlwaysLess(1)`,\nb: `AlwaysLess(2)`\nb.cmp(&a) returned `Less`, but a.cmp(&b) returned `Less`"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn bassert_unique_success_passes() {
+        bassert_unique!(alloc::vec![3, 1, 2]);
+        bassert_unique!(["a", "b"], "no {}", "duplicates");
+        bassert_unique!(Vec::<i32>::new());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[should_panic(
+        expected = "assertion failed: `ids.is_unique()`\nids: `[1, 3, 2, 3, 1]`: oops\nelement at index 1 (`3`) == element at index 3 (`3`)"
+    )]
+    fn bassert_unique_failure_prints_first_duplicate() {
+        let ids = alloc::vec![1, 3, 2, 3, 1];
+        bassert_unique!(ids, "oops");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn bassert_set_eq_success_passes() {
+        bassert_set_eq!(alloc::vec![3, 1, 2], [1, 2, 3]);
+        bassert_set_eq!(
+            alloc::vec!["a", "b", "a"],
+            ["a", "a", "b"],
+            "same {}",
+            "elements"
+        );
+        bassert_set_eq!(std::collections::HashSet::from([1, 2]), alloc::vec![2, 1]);
+        bassert_set_eq!(Vec::<i32>::new(), []);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[should_panic(
+        expected = "assertion failed: `actual == expected`\nactual: `[3, 1, 2, 2]`,\nexpected: `[1, 2, 3, 4]`: oops\nelements only in left: `[2]`\nelements only in right: `[4]`"
+    )]
+    fn bassert_set_eq_failure_lists_missing_elements() {
+        let actual = alloc::vec![3, 1, 2, 2];
+        let expected = [1, 2, 3, 4];
+        bassert_set_eq!(actual, expected, "oops");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn bassert_set_eq_lists_missing_elements_in_a_deterministic_order() {
+        use std::collections::HashSet;
+        for _ in 0..10 {
+            // Every `HashSet` gets its own random iteration order.
+            let actual: HashSet<&str> = HashSet::from(["d", "a", "c", "e"]);
+            let expected: HashSet<&str> = HashSet::from(["b", "f", "c", "g"]);
+            let message = panic_message(|| bassert_set_eq!(actual, expected));
+            bassert!(message.ends_with(
+                "\nelements only in left: `[\"a\", \"d\", \"e\"]`\nelements only in right: `[\"b\", \"f\", \"g\"]`"
+            ));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn bassert_subset_lists_missing_elements_in_a_deterministic_order() {
+        use std::collections::HashSet;
+        for _ in 0..10 {
+            let required: HashSet<u8> = (1..8).collect();
+            let message = panic_message(|| bassert_subset!(required, [2, 4]));
+            bassert!(message.ends_with("\nelements only in left: `[1, 3, 5, 6, 7]`"));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn bassert_map_eq_lists_keys_in_a_deterministic_order() {
+        use std::collections::HashMap;
+        for _ in 0..10 {
+            let lhs: HashMap<u8, u8> = (0..6).map(|n| (n, n)).collect();
+            let rhs: HashMap<u8, u8> = (3..9).map(|n| (n, n * 2)).collect();
+            let message = panic_message(|| bassert_map_eq!(lhs, rhs));
+            bassert!(message.ends_with(
+                "\nkeys only in left: `[0, 1, 2]`\nkeys only in right: `[6, 7, 8]`\ndifferent value for key `3`: left = `3`, right = `6`\ndifferent value for key `4`: left = `4`, right = `8`\ndifferent value for key `5`: left = `5`, right = `10`"
+            ));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[should_panic(expected = "expected: `[1, 1]`\nelements only in right: `[1]`")]
+    fn bassert_set_eq_counts_duplicates() {
+        let (actual, expected) = ([1], [1, 1]);
+        bassert_set_eq!(actual, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn bassert_subset_success_passes() {
+        let fields = alloc::vec!["id", "created_at", "name"];
+        bassert_subset!(["name", "id"], fields);
+        bassert_subset!(["id", "id"], fields, "duplicates are {}", "fine");
+        bassert_subset!(Vec::<&str>::new(), fields);
+        bassert_superset!(fields, ["name"]);
+        bassert_superset!(std::collections::HashSet::from([1, 2, 3]), [3, 1]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[should_panic(
+        expected = "assertion failed: `required.is_subset(fields)`\nrequired: `[\"id\", \"name\", \"email\"]`,\nfields: `[\"id\", \"name\"]`: oops\nelements only in left: `[\"email\"]`"
+    )]
+    fn bassert_subset_failure_lists_missing_elements() {
+        let required = ["id", "name", "email"];
+        let fields = alloc::vec!["id", "name"];
+        bassert_subset!(required, fields, "oops");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[should_panic(
+        expected = "assertion failed: `fields.is_superset([1, 4, 5])`\nfields: `[1, 2]`,\n[1, 4, 5]: `[1, 4, 5]`\nelements only in right: `[4, 5]`"
+    )]
+    fn bassert_superset_failure_lists_missing_elements() {
+        let fields = [1, 2];
+        bassert_superset!(fields, [1, 4, 5]);
+    }
+
+    #[test]
+    fn bassert_duration_near_success_passes() {
+        use core::time::Duration;
+        let elapsed = Duration::from_millis(95);
+        bassert_duration_near!(
+            elapsed,
+            Duration::from_millis(100),
+            Duration::from_millis(5)
+        );
+        bassert_duration_near!(
+            Duration::from_millis(105),
+            Duration::from_millis(100),
+            Duration::from_millis(5),
+            "within {}",
+            "tolerance"
+        );
+        bassert_duration_near!(elapsed, elapsed, Duration::ZERO);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `elapsed ~= expected`\nelapsed: `70ms`,\nexpected: `100ms`,\ndifference: `30ms`,\ntolerance: `20ms`: too fast"
+    )]
+    fn bassert_duration_near_failure_prints_difference() {
+        use core::time::Duration;
+        let (elapsed, expected) = (Duration::from_millis(70), Duration::from_millis(100));
+        bassert_duration_near!(elapsed, expected, Duration::from_millis(20), "too fast");
+    }
+
+    /// Returns `value`, counting how often it is called.
+    fn counted<T>(calls: &core::cell::Cell<usize>, value: T) -> T {
+        calls.set(calls.get() + 1);
+        value
+    }
+
+    /// Checks that `assertion` evaluates `counted` exactly `expected` times.
+    fn bassert_evaluations<R>(
+        expected: usize,
+        assertion: impl FnOnce(&core::cell::Cell<usize>) -> R + std::panic::UnwindSafe,
+    ) {
+        let calls = core::cell::Cell::new(0);
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| assertion(&calls)));
+        bassert!((calls.get()) == expected);
+    }
+
+    #[test]
+    fn operands_are_evaluated_once() {
+        // Both when the assertion holds and when it fails.
+        for x in [1_i32, 2] {
+            bassert_evaluations(2, |c| bassert!((counted(c, x)) == (counted(c, 1))));
+            bassert_evaluations(2, |c| bassert!((counted(c, x)) != (counted(c, 2)), "msg"));
+            bassert_evaluations(2, |c| bassert!((counted(c, x)) < (counted(c, 2))));
+            bassert_evaluations(2, |c| bassert!((counted(c, x)) <= (counted(c, 1)), "{}", x));
+            bassert_evaluations(2, |c| bassert!((counted(c, x)) > (counted(c, 1))));
+            bassert_evaluations(2, |c| bassert!((counted(c, x)) >= (counted(c, 2)), "msg"));
+            bassert_evaluations(2, |c| bassert!(counted(c, x) + 1 == counted(c, 2)));
+            bassert_evaluations(2, |c| bassert!(counted(c, x) + 1 == counted(c, 2), "msg"));
+            bassert_evaluations(2, |c| bassert!((counted(c, x as f64)) ~= (counted(c, 1.0))));
+            bassert_evaluations(
+                2,
+                |c| bassert!((counted(c, x as f64)) ~= (counted(c, 1.0)), eps = 0.1, "msg"),
+            );
+            bassert_evaluations(2, |c| bassert!((counted(c, x)) in (counted(c, 0..2))));
+            bassert_evaluations(
+                2,
+                |c| bassert!((counted(c, x)) in (counted(c, 0..2)), "msg"),
+            );
+            bassert_evaluations(
+                3,
+                |c| bassert!(counted(c, 0) < counted(c, x) < counted(c, 2)),
+            );
+            bassert_evaluations(
+                3,
+                |c| bassert!(counted(c, 0) <= counted(c, x) <= counted(c, 1), "msg"),
+            );
+            bassert_evaluations(1, |c| bassert!(Some(1) = (counted(c, Some(x)))));
+            bassert_evaluations(1, |c| bassert!(Some(1) = (counted(c, Some(x))), "msg"));
+            bassert_evaluations(1, |c| bassert!(Some(y) if *y == 1 = (counted(c, Some(x)))));
+            bassert_evaluations(1, |c| bassert!(!counted(c, x == 2)));
+            bassert_evaluations(1, |c| bassert!(counted(c, x == 1), "msg"));
+            bassert_evaluations(1, |c| bassert!(counted(c, x).is_positive() && x == 1));
+            bassert_evaluations(1, |c| bassert_val!(counted(c, x) == 1));
+            bassert_evaluations(1, |c| try_bassert!(counted(c, x) == 1, "msg"));
+        }
+    }
+
+    /// Counts how often it is formatted using `Debug`.
+    #[derive(PartialEq, Eq, PartialOrd, Ord)]
+    struct Formatted<'a>(i32, &'a core::cell::Cell<usize>);
+
+    impl core::fmt::Debug for Formatted<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            self.1.set(self.1.get() + 1);
+            write!(f, "{}", self.0)
+        }
+    }
+
+    #[test]
+    fn operands_are_formatted_once_and_only_on_failure() {
+        let calls = core::cell::Cell::new(0);
+        let (one, two) = (Formatted(1, &calls), Formatted(2, &calls));
+        bassert!(one < two);
+        bassert!(one < two, "with a {}", "message");
+        bassert!((calls.get()) == 0);
+
+        let failure = try_bassert!(two < one).unwrap_err();
+        bassert!((calls.get()) == 2);
+        let failure_with_message = try_bassert!(two < one, "with a {}", "message").unwrap_err();
+        bassert!((calls.get()) == 4);
+        // Displaying the failure reuses the formatted values.
+        let _ = (failure.to_string(), failure_with_message.to_string());
+        bassert!((calls.get()) == 4);
+
+        let message = panic_message(std::panic::AssertUnwindSafe(|| bassert!(two < one, "msg")));
+        bassert!(message.starts_with("assertion failed: `two < one`\ntwo: `2`,\none: `1`: msg"));
+        bassert!((calls.get()) == 6);
+    }
+
+    #[test]
+    fn reference_operands_success_passes() {
+        let (x, y) = (10, 20);
+        bassert!(&x < &y);
+        bassert!(&&x == &&x);
+        let other = alloc::vec![1, 2, 3];
+        let slice: &[i32] = &other;
+        bassert!(slice == &other[..]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `&x > &y`\n&x: `10`,\n&y: `20`\ndifference: `-10`"
+    )]
+    fn reference_operands_failure_prints_values() {
+        let (x, y) = (10, 20);
+        bassert!(&x > &y);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `slice == &other[..]`\nslice: `[1, 2, 3]`,\n&other[..]: `[1, 2, 4]`\nfirst difference at index 2: left = `3`, right = `4`"
+    )]
+    fn reference_slice_operands_failure_prints_difference() {
+        let (v, other) = (alloc::vec![1, 2, 3], alloc::vec![1, 2, 4]);
+        let slice: &[i32] = &v;
+        bassert!(slice == &other[..]);
+    }
+
+    #[test]
+    fn smart_pointer_operands_success_passes() {
+        use alloc::borrow::Cow;
+        use alloc::boxed::Box;
+        use alloc::rc::Rc;
+        use alloc::sync::Arc;
+
+        let rc = Rc::new(5);
+        bassert!(rc == (Rc::new(5)));
+        bassert!(*rc == 5);
+        let arc = Arc::new(String::from("x"));
+        bassert!(*arc == "x");
+        let boxed = Box::new([1, 2, 3]);
+        bassert!(boxed == (Box::new([1, 2, 3])));
+        bassert!(*boxed == [1, 2, 3]);
+        let cow: Cow<str> = Cow::Borrowed("x");
+        bassert!(cow == "x");
+        bassert!("x" == cow);
+        bassert!(cow == *arc);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `rc < other`\nrc: `5`,\nother: `3`\ndifference: `2`"
+    )]
+    fn rc_operands_failure_prints_difference() {
+        let (rc, other) = (alloc::rc::Rc::new(5), alloc::rc::Rc::new(3));
+        bassert!(rc < other);
+    }
+
+    #[test]
+    #[should_panic(expected = "first difference at index 2: left = `3`, right = `4`")]
+    fn box_operands_failure_prints_difference() {
+        let (boxed, other) = (
+            alloc::boxed::Box::new([1, 2, 3]),
+            alloc::boxed::Box::new([1, 2, 4]),
+        );
+        bassert!(boxed == other);
+    }
+
+    #[test]
+    #[should_panic(expected = "mismatch at line 1, column 2:\nleft:  `ab`\nright: `ac`")]
+    fn cow_and_arc_operands_failure_prints_difference() {
+        let cow: alloc::borrow::Cow<str> = alloc::borrow::Cow::Borrowed("ab");
+        let arc: alloc::sync::Arc<str> = alloc::sync::Arc::from("ac");
+        bassert!(cow == *arc);
+    }
+
+    #[test]
+    fn result_match_failure_prints_displayed_error() {
+        let result: Result<u8, core::num::ParseIntError> = "x1".parse();
+        let message = panic_message(|| bassert!(Ok(_) = result));
+        bassert!(
+            message
+                == "assertion failed: `Ok(_) = result`\nresult: `Err(ParseIntError { kind: InvalidDigit })`\ndisplayed error: `invalid digit found in string`"
+        );
+
+        let reference = &result;
+        let message = panic_message(|| bassert!(Ok(n) if *n > 1 = reference, "oops"));
+        bassert!(message.ends_with(": oops\ndisplayed error: `invalid digit found in string`"));
+
+        let message = panic_message(|| {
+            bassert_let!(Ok(_n) = result);
+        });
+        bassert!(message.ends_with("\ndisplayed error: `invalid digit found in string`"));
+    }
+
+    #[test]
+    fn result_match_failure_without_display_error_prints_debug_only() {
+        let ok: Result<u8, u8> = Ok(1);
+        let message = panic_message(|| bassert!(Err(_) = ok));
+        bassert!(message == "assertion failed: `Err(_) = ok`\nok: `Ok(1)`\nactual variant: `Ok`");
+
+        #[derive(Debug)]
+        struct NoDisplay;
+        let err: Result<u8, NoDisplay> = Err(NoDisplay);
+        let message = panic_message(|| bassert!(Ok(_) = err));
+        bassert!(
+            message
+                == "assertion failed: `Ok(_) = err`\nerr: `Err(NoDisplay)`\nactual variant: `Err`"
+        );
+    }
+
+    #[test]
+    fn bassert_ok_and_friends_return_the_inner_value() {
+        let port: u16 = bassert_ok!("80".parse());
+        bassert!(port == 80);
+        let error = bassert_err!("80a".parse::<u16>(), "{} is not a port", "80a");
+        bassert!((error.to_string()) == "invalid digit found in string");
+        let first = bassert_some!([1, 2].first());
+        bassert!(*first == 1);
+        bassert_none!([0u8; 0].first());
+    }
+
+    #[test]
+    fn bassert_ok_failure_prints_debug_and_display_of_the_error() {
+        let message = panic_message(|| {
+            let _port: u16 = bassert_ok!("80a".parse(), "bad port");
+        });
+        bassert!(
+            message
+                == "assertion failed: `Ok(_) = \"80a\".parse()`\n\"80a\".parse(): `Err(ParseIntError { kind: InvalidDigit })`: bad port\ndisplayed error: `invalid digit found in string`"
+        );
+    }
+
+    #[test]
+    fn bassert_err_some_and_none_failures_print_the_value() {
+        let ok: Result<u8, u8> = Ok(1);
+        let message = panic_message(|| {
+            bassert_err!(ok);
+        });
+        bassert!(message == "assertion failed: `Err(_) = ok`\nok: `Ok(1)`\nactual variant: `Ok`");
+
+        let empty: Option<u8> = None;
+        let message = panic_message(|| {
+            bassert_some!(empty);
+        });
+        bassert!(message == "assertion failed: `Some(_) = empty`\nempty: `None`");
+
+        let message = panic_message(|| bassert_none!(Some(1)));
+        bassert!(
+            message
+                == "assertion failed: `None = Some(1)`\nSome(1): `Some(1)`\nactual variant: `Some`"
+        );
+    }
+
+    #[test]
+    fn bassert_between_success_passes() {
+        let (x, low) = (5, 1);
+        bassert_between!(x, low, x + 1);
+        bassert_between!(x, x, x, "bounds are {}", "inclusive");
+        bassert_between!(0.5, 0.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `low <= x <= high`\nlower bound violated: `low <= x`\nlow: `3`,\nx: `2`,\nhigh: `10`: too small"
+    )]
+    fn bassert_between_failure_names_the_lower_bound() {
+        let (x, low, high) = (2, 3, 10);
+        bassert_between!(x, low, high, "too small");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `0 <= len + 1 <= max`\nupper bound violated: `len + 1 <= max`\n0: `0`,\nlen + 1: `5`,\nmax: `4`"
+    )]
+    fn bassert_between_failure_names_the_upper_bound() {
+        let (len, max) = (4, 4);
+        bassert_between!(len + 1, 0, max);
+    }
+
+    #[test]
+    fn bassert_between_evaluates_operands_once() {
+        let mut calls = 0;
+        let mut next = || {
+            calls += 1;
+            calls
+        };
+        bassert_between!(next(), 0, 10);
+        bassert!(calls == 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "values are not comparable (partial_cmp returned None)")]
+    fn bassert_between_failure_explains_incomparable_values() {
+        bassert_between!(f64::NAN, 0.0, 1.0);
+    }
+
+    #[test]
+    fn bassert_iter_eq_success_passes() {
+        bassert_iter_eq!((1..4).map(|n| n * 2), [2, 4, 6]);
+        bassert_iter_eq!(core::iter::empty::<&u8>(), &[] as &[u8], "both {}", "empty");
+        let v = vec![1, 2];
+        bassert_iter_eq!(&v, [&1, &2]);
+    }
+
+    #[test]
+    fn bassert_iter_eq_failure_prints_first_difference() {
+        let message = panic_message(|| {
+            let squares = (1..).map(|n| n * n);
+            bassert_iter_eq!(squares.take(4), [1, 4, 8, 16], "oops")
+        });
+        bassert!(
+            message
+                == "assertion failed: `squares.take(4).into_iter().eq([1, 4, 8, 16])`: oops\nfirst difference at index 2: left = `9`, right = `8`"
+        );
+    }
 
-    #[derive(Debug)]
-    #[doc(hidden)]
-    pub enum BassertKind {
-        Eq,
-        Ne,
-        Gt,
-        Lt,
-        Gte,
-        Lte,
-        Match,
+    #[test]
+    fn bassert_iter_eq_failure_prints_next_element_of_longer_iterator() {
+        let message = panic_message(|| bassert_iter_eq!(1..5, 1..4));
+        bassert!(message.ends_with("\nright ended after `3` elements, but left continues with `4`"));
+
+        let message = panic_message(|| bassert_iter_eq!("ab".chars(), "abc".chars()));
+        bassert!(
+            message.ends_with("\nleft ended after `2` elements, but right continues with `'c'`")
+        );
     }
 
-    #[cold]
-    #[track_caller]
-    #[doc(hidden)]
-    pub fn bassert_failed<Lhs, Rhs>(
-        kind: BassertKind,
-        lhs_expr: &'static str,
-        rhs_expr: &'static str,
-        lhs: &Lhs,
-        rhs: &Rhs,
-        args: Option<fmt::Arguments<'_>>,
-    ) -> !
-    where
-        Lhs: fmt::Debug + ?Sized,
-        Rhs: fmt::Debug + ?Sized,
-    {
-        let op = match kind {
-            BassertKind::Eq => "==",
-            BassertKind::Ne => "!=",
-            BassertKind::Gt => ">",
-            BassertKind::Lt => "<",
-            BassertKind::Gte => ">=",
-            BassertKind::Lte => "<=",
-            BassertKind::Match => "=",
-        };
-
-        match args {
-            Some(args) => panic!(
-                r#"assertion failed: `{} {} {}`
-{}: `{:?}`,
-{}: `{:?}`: {}"#,
-                lhs_expr, op, rhs_expr, lhs_expr, lhs, rhs_expr, rhs, args
-            ),
+    #[test]
+    fn bassert_contains_success_passes() {
+        let log = alloc::string::String::from("connection refused");
+        bassert_contains!(log, "refused");
+        bassert_contains!("abc", "", "the empty string is in {}", "every string");
+        bassert_not_contains!(log, "timed out");
+    }
 
-            None => panic!(
-                r#"assertion failed: `{} {} {}`
-{}: `{:?}`,
-{}: `{:?}`"#,
-                lhs_expr, op, rhs_expr, lhs_expr, lhs, rhs_expr, rhs
-            ),
-        }
+    #[test]
+    fn bassert_contains_failure_prints_haystack_and_needle() {
+        let log = "connection refused ";
+        let message = panic_message(|| bassert_contains!(log, "timed out", "oops"));
+        bassert!(
+            message
+                == "assertion failed: `log.contains(\"timed out\")`\nlog: `\"connection refused \"`,\n\"timed out\": `\"timed out\"`: oops"
+        );
     }
 
-    #[cold]
-    #[track_caller]
-    #[doc(hidden)]
-    pub fn bassert_match_failed<Rhs>(
-        pattern: &'static str,
-        rhs_expr: &'static str,
-        rhs: &Rhs,
-        args: Option<fmt::Arguments<'_>>,
-    ) -> !
-    where
-        Rhs: fmt::Debug + ?Sized,
-    {
-        match args {
-            Some(args) => panic!(
-                r#"assertion failed: `{} = {}`
-{}: `{:?}`: {}"#,
-                pattern, rhs_expr, rhs_expr, rhs, args
-            ),
+    #[test]
+    fn bassert_not_contains_failure_prints_offset() {
+        let output = alloc::string::String::from("ok; warning: unused");
+        let message = panic_message(|| bassert_not_contains!(output, "warning"));
+        bassert!(
+            message
+                == "assertion failed: `!output.contains(\"warning\")`\noutput: `\"ok; warning: unused\"`,\n\"warning\": `\"warning\"`\nfound at byte offset `4`"
+        );
+    }
 
-            None => panic!(
-                r#"assertion failed: `{} = {}`
-{}: `{:?}`"#,
-                pattern, rhs_expr, rhs_expr, rhs
-            ),
-        }
+    #[test]
+    fn bassert_ieq_success_passes() {
+        let header = alloc::string::String::from("Content-Type");
+        bassert_ieq!(header, "content-type");
+        bassert_ieq!("ÄBC", "Äbc", "only {} letters are folded", "ASCII");
+        bassert_ieq!("", "");
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn bassert_ieq_failure_prints_originals_and_first_difference() {
+        let header = "Content-Type";
+        let message = panic_message(|| bassert_ieq!(header, "content-length", "oops"));
+        bassert!(
+            message
+                == "assertion failed: `header.eq_ignore_ascii_case(\"content-length\")`\nheader: `\"Content-Type\"`,\n\"content-length\": `\"content-length\"`: oops\nfirst difference at index 8: left = `'T'`, right = `'l'`\nlengths differ: left = `12`, right = `14`"
+        );
+
+        let message = panic_message(|| bassert_ieq!("ä", "Ä"));
+        bassert!(message.ends_with("first difference at index 0: left = `'ä'`, right = `'Ä'`"));
+
+        let message = panic_message(|| bassert_ieq!("abc", "AB"));
+        bassert!(message.ends_with("`\"AB\"`\nlengths differ: left = `3`, right = `2`"));
+    }
 
     #[test]
-    fn gt_success_passes() {
-        let larger = 3;
-        let smaller = 2;
-        bassert!(larger > smaller);
-        bassert!(larger > smaller, "foo bar {}", "some message");
+    fn bassert_ptr_eq_success_passes() {
+        let (a, b) = (String::from("x"), String::from("x"));
+        let same = &a;
+        bassert_ptr_eq!(same, &a);
+        bassert_ptr_eq!(a.as_str(), &a[..], "slices {}", "too");
+        bassert_ptr_ne!(&a, &b);
+        let rc = alloc::rc::Rc::new(5);
+        let clone = alloc::rc::Rc::clone(&rc);
+        bassert_ptr_eq!(&*rc, &*clone);
+        bassert_ptr_ne!(&*rc, &*alloc::rc::Rc::new(5));
     }
 
     #[test]
-    fn gt_complex_success_passes() {
-        let x = 33;
-        bassert!(x < (x + 10));
+    fn bassert_ptr_eq_failure_prints_values_and_addresses() {
+        let (a, b) = (String::from("x"), String::from("y"));
+        let message = panic_message(|| bassert_ptr_eq!(&a, &b, "oops"));
+        bassert!(message
+            .starts_with("assertion failed: `ptr::eq(&a, &b)`\n&a: `\"x\"`,\n&b: `\"y\"`: oops\n"));
+        bassert!((message.lines().last().unwrap())
+            .contains(&format!("left = `{:p}`, right = `{:p}`", &a, &b)));
     }
 
     #[test]
-    #[should_panic(expected = "assertion failed: `smaller > larger`\nsmaller: `2`,\nlarger: `3`")]
-    fn gt_failure_prints_correct_message() {
-        let larger = 3;
-        let smaller = 2;
-        bassert!(smaller > larger);
+    #[should_panic(
+        expected = "assertion failed: `!ptr::eq(&a, a_ref)`\n&a: `1`,\na_ref: `1`\naddresses: left = `0x"
+    )]
+    fn bassert_ptr_ne_failure_prints_values_and_addresses() {
+        let a = 1;
+        let a_ref = &a;
+        bassert_ptr_ne!(&a, a_ref);
+    }
+
+    #[test]
+    fn cross_type_eq_success_passes() {
+        let s = String::from("hello");
+        bassert!(s == "hello");
+        bassert!("hello" == s);
+        let v = alloc::vec![1, 2, 3];
+        let slice: &[i32] = &[1, 2, 3];
+        bassert!(v == slice);
+        let path = std::path::PathBuf::from("/tmp/file");
+        bassert!(path == std::path::Path::new("/tmp/file"));
+        bassert!(path != std::path::Path::new("/tmp"));
     }
 
     #[test]
     #[should_panic(
-        expected = "assertion failed: `smaller > larger`\nsmaller: `2`,\nlarger: `3`: it is broken, because foo"
+        expected = "assertion failed: `path == std::path::Path::new(\"/tmp\")`\npath: `\"/tmp/file\"`,\nstd::path::Path::new(\"/tmp\"): `\"/tmp\"`"
     )]
-    fn gt_failure_with_custom_formatted_message() {
-        let larger = 3;
-        let smaller = 2;
-        bassert!(smaller > larger, "it is broken, because {}", "foo");
+    fn cross_type_eq_failure_prints_both_values() {
+        let path = std::path::PathBuf::from("/tmp/file");
+        bassert!(path == std::path::Path::new("/tmp"));
     }
 
     #[test]
-    fn lt_success_passes() {
-        let larger = 3;
-        let smaller = 2;
-        bassert!(smaller < larger);
+    fn map_eq_success_passes() {
+        let lhs = std::collections::HashMap::from([("a", 1), ("b", 2)]);
+        let rhs = std::collections::HashMap::from([("b", 2), ("a", 1)]);
+        bassert_map_eq!(lhs, rhs);
     }
 
     #[test]
-    #[should_panic(expected = "assertion failed: `larger < smaller`\nlarger: `3`,\nsmaller: `2`")]
-    fn lt_failure_prints_correct_message() {
-        let larger = 3;
-        let smaller = 2;
-        bassert!(larger < smaller);
+    #[should_panic(
+        expected = "assertion failed: `actual == expected`\nactual: `{\"a\": 1, \"b\": 2, \"c\": 3}`,\nexpected: `{\"a\": 1, \"b\": 20, \"d\": 4}`\nkeys only in left: `[\"c\"]`\nkeys only in right: `[\"d\"]`\ndifferent value for key `\"b\"`: left = `2`, right = `20`"
+    )]
+    fn map_eq_failure_lists_differing_keys() {
+        let actual = std::collections::HashMap::from([("c", 3), ("b", 2), ("a", 1)]);
+        let expected = std::collections::HashMap::from([("d", 4), ("a", 1), ("b", 20)]);
+        bassert_map_eq!(actual, expected);
     }
 
     #[test]
     #[should_panic(
-        expected = "assertion failed: `larger < smaller`\nlarger: `3`,\nsmaller: `2`: it is broken, because foo"
+        expected = "assertion failed: `actual == expected`\nactual: `{1: 'x'}`,\nexpected: `{1: 'y'}`: some message\ndifferent value for key `1`: left = `'x'`, right = `'y'`"
     )]
-    fn lt_failure_with_custom_formatted_message() {
-        let larger = 3;
-        let smaller = 2;
-        bassert!(larger < smaller, "it is broken, because {}", "foo");
+    fn map_eq_failure_prints_custom_message() {
+        let actual = alloc::collections::BTreeMap::from([(1, 'x')]);
+        let expected = alloc::collections::BTreeMap::from([(1, 'y')]);
+        bassert_map_eq!(actual, expected, "some {}", "message");
     }
 
     #[test]
-    fn gte_success_passes() {
-        let larger = 3;
-        let smaller = 2;
-        bassert!(larger >= smaller);
-        bassert!(larger >= larger);
-        bassert!(smaller >= smaller);
+    fn bytes_eq_success_passes() {
+        let buffer = alloc::vec![0xde_u8, 0xad, 0xbe, 0xef];
+        bassert_bytes_eq!(buffer, [0xde, 0xad, 0xbe, 0xef]);
+        bassert_bytes_eq!("abc", b"abc");
     }
 
     #[test]
-    #[should_panic(expected = "assertion failed: `smaller >= larger`\nsmaller: `2`,\nlarger: `3`")]
-    fn gte_failure_prints_correct_message() {
-        let larger = 3;
-        let smaller = 2;
-        bassert!(smaller >= larger);
+    fn bytes_eq_failure_prints_hex_dump_around_first_difference() {
+        let result = std::panic::catch_unwind(|| {
+            let actual: alloc::vec::Vec<u8> = (0..40).collect();
+            let mut expected = actual.clone();
+            expected[20] = 0xff;
+            expected.push(40);
+            bassert_bytes_eq!(actual, expected);
+        });
+        let payload = result.unwrap_err();
+        let message = payload.downcast_ref::<String>().unwrap();
+        let expected_message = [
+            "assertion failed: `actual == expected`",
+            "lengths differ: left = `40`, right = `41`",
+            "first difference at offset 20 (0x14):",
+            "  ...",
+            "  00000008  08 09 0a 0b 0c 0d 0e 0f  |  08 09 0a 0b 0c 0d 0e 0f",
+            "> 00000010  10 11 12 13 14 15 16 17  |  10 11 12 13 ff 15 16 17",
+            "  00000018  18 19 1a 1b 1c 1d 1e 1f  |  18 19 1a 1b 1c 1d 1e 1f",
+            "  ...",
+        ];
+        bassert!(*message == expected_message.join("\n"));
     }
 
     #[test]
     #[should_panic(
-        expected = "assertion failed: `smaller >= larger`\nsmaller: `2`,\nlarger: `3`: it was not larger at all"
+        expected = "assertion failed: `actual == expected`: some message\nlengths differ: left = `3`, right = `2`\nfirst difference at offset 2 (0x2):\n> 00000000  01 02 03                 |  01 02"
     )]
-    fn gte_failure_with_custom_message_prints_correct_message() {
-        let larger = 3;
-        let smaller = 2;
-        bassert!(smaller >= larger, "it was not larger {}", "at all");
+    fn bytes_eq_failure_prints_length_mismatch() {
+        let actual = [1_u8, 2, 3];
+        let expected = [1_u8, 2];
+        bassert_bytes_eq!(actual, expected, "some {}", "message");
     }
 
     #[test]
-    fn lte_success_passes() {
-        let larger = 3;
-        let smaller = 2;
-        bassert!(smaller <= larger);
-        bassert!(smaller <= smaller);
-        bassert!(larger <= larger);
+    #[should_panic(
+        expected = "assertion failed: `words < other`\nwords: `[\"a\", \"c\"]`,\nother: `[\"a\", \"b\", \"c\"]`\nfirst difference at index 1: left = `\"c\"`, right = `\"b\"`\nlengths differ: left = `2`, right = `3`"
+    )]
+    fn slice_cmp_failure_prints_first_difference_and_lengths() {
+        let words: &[&str] = &["a", "c"];
+        let other: &[&str] = &["a", "b", "c"];
+        bassert!(words < other);
     }
 
-    #[test]
-    #[should_panic(expected = "assertion failed: `larger <= smaller`\nlarger: `3`,\nsmaller: `2`")]
-    fn lte_failure_prints_correct_message() {
-        let larger = 3;
-        let smaller = 2;
-        bassert!(larger <= smaller);
+    #[cfg(feature = "diff")]
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
     }
 
     #[test]
+    #[cfg(feature = "diff")]
     #[should_panic(
-        expected = "assertion failed: `larger <= smaller`\nlarger: `3`,\nsmaller: `2`: it was not smaller at all"
+        expected = "\ndiff (- a, + b):\n Point {\n     x: 1,\n-    y: 2,\n+    y: 3,\n }"
     )]
-    fn lte_failure_with_custom_message_prints_correct_message() {
-        let larger = 3;
-        let smaller = 2;
-        bassert!(larger <= smaller, "it was not smaller {}", "at all");
+    fn eq_failure_prints_diff() {
+        let a = Point { x: 1, y: 2 };
+        let b = Point { x: 1, y: 3 };
+        bassert!(a == b);
     }
 
     #[test]
-    fn eq_success_passes() {
-        let foo = 42;
-        let bar = 42;
-        bassert!(foo == bar);
+    fn match_guard_success_passes() {
+        let val = Some(10);
+        bassert!(Some(x) if *x > 5 = val);
+        bassert!(Some(x) if *x > 5 && *x < 20 = val, "some message {}", 42);
     }
 
     #[test]
-    #[should_panic(expected = "assertion failed: `larger == smaller`\nlarger: `3`,\nsmaller: `2`")]
-    fn eq_failure_prints_correct_message() {
-        let larger = 3;
-        let smaller = 2;
-        bassert!(larger == smaller);
+    #[should_panic(
+        expected = "assertion failed: `Some(x) if *x > 5 = val`\nguard failed: `*x > 5`\nval: `Some(3)`,\nx: `3`: some message"
+    )]
+    fn match_guard_failure_prints_failed_guard() {
+        let val = Some(3);
+        bassert!(Some(x) if *x > 5 = val, "some {}", "message");
     }
 
     #[test]
     #[should_panic(
-        expected = "assertion failed: `larger == smaller`\nlarger: `3`,\nsmaller: `2`: Huge explosions!"
+        expected = "assertion failed: `Some(x) if *x > 5 = val`\npattern did not match: `Some(x)`\nval: `None`"
     )]
-    fn eq_failure_with_custom_message_prints_correct_message() {
-        let larger = 3;
-        let smaller = 2;
-        bassert!(larger == smaller, "{} explosions!", "Huge");
+    fn match_guard_failure_prints_unmatched_pattern() {
+        let val: Option<i32> = None;
+        bassert!(Some(x) if *x > 5 = val);
+    }
+
+    #[derive(Debug)]
+    struct Size {
+        width: i32,
+        height: i32,
     }
 
     #[test]
-    fn neq_success_passes() {
-        let smaller = 2;
-        let larger = 3;
-        bassert!(smaller != larger);
+    #[should_panic(
+        expected = "assertion failed: `Size { width, height } if width == height = size`\nguard failed: `width == height`\nsize: `Size { width: 1, height: 2 }`,\nwidth: `1`,\nheight: `2`"
+    )]
+    fn match_guard_failure_prints_struct_bindings() {
+        let size = Size {
+            width: 1,
+            height: 2,
+        };
+        bassert!(Size { width, height } if width == height = size);
     }
 
     #[test]
-    #[should_panic(expected = "assertion failed: `foo != bar`\nfoo: `42`,\nbar: `42`")]
-    fn neq_failure_prints_correct_message() {
-        let foo = 42;
-        let bar = 42;
-        bassert!(foo != bar);
+    #[should_panic(
+        expected = "assertion failed: `Err(std::cmp::Ordering::Less) | Ok(_) if false = res`\nguard failed: `false`\nres: `Ok(1)`"
+    )]
+    fn match_guard_failure_on_long_pattern_prints_only_the_value() {
+        let res: Result<i32, std::cmp::Ordering> = Ok(1);
+        bassert!(Err(std::cmp::Ordering::Less) | Ok(_) if false = res);
+    }
+
+    #[test]
+    fn match_guard_with_path_and_unit_variant_fields() {
+        let val = Some(None::<i32>);
+        bassert!(Some(None) if val.is_some() = val);
+
+        let res: Result<i32, i32> = Ok(7);
+        let failure = try_bassert!(Result::Ok(n) if *n > 10 = res).unwrap_err();
+        bassert!((message_of(failure)) == "assertion failed: `Result::Ok(n) if *n > 10 = res`\nguard failed: `*n > 10`\nres: `Ok(7)`,\nn: `7`");
     }
 
     #[test]
     #[should_panic(
-        expected = "assertion failed: `foo != bar`\nfoo: `42`,\nbar: `42`: It be broken"
+        expected = "assertion failed: `Some((a, _)) if *a > 5 = val`\nguard failed: `*a > 5`\nval: `Some((3, 4))`"
     )]
-    fn neq_failure_with_custom_message_prints_correct_message() {
-        let foo = 42;
-        let bar = 42;
-        bassert!(foo != bar, "It be {}", "broken");
+    fn match_guard_failure_on_complex_pattern_prints_only_the_value() {
+        let val = Some((3, 4));
+        bassert!(Some((a, _)) if *a > 5 = val);
+    }
+
+    #[derive(Debug)]
+    struct Line {
+        start: Corner,
+        end: Corner,
+    }
+
+    #[derive(Debug)]
+    struct Corner {
+        x: i32,
+        y: i32,
     }
 
     #[test]
-    fn match_success_passes() {
-        let val: Option<i64> = Some(100);
-        bassert!(Some(_) = val);
+    fn match_supports_bindings_ranges_and_nested_structs() {
+        let val = Some(3);
+        bassert!(Some(_n @ 1..=5) = val);
+        bassert!(Some(n @ (1 | 3)) if *n > 2 = val);
+        bassert!(Some(..=3) = val);
+        bassert!(Some(-10..10) = val);
+        let c = 'q';
+        bassert!('a'..='z' = c);
+
+        let line = Line {
+            start: Corner { x: 0, y: 0 },
+            end: Corner { x: 3, y: 4 },
+        };
+        bassert!(
+            Line {
+                start: Corner { x: 0, .. },
+                end: Corner { y: 1..=9, .. }
+            } = line
+        );
+        bassert!(Line { end: end @ Corner { x, .. }, .. } if end.y > *x = line);
     }
 
     #[test]
-    #[should_panic(expected = "assertion failed: `None = val`\nval: `Some(100)`")]
-    fn match_failure_prints_correct_message() {
-        let val: Option<i64> = Some(100);
-        bassert!(None = val);
+    #[should_panic(expected = "assertion failed: `Some(_n @ 1..=5) = val`\nval: `Some(7)`")]
+    fn match_failure_prints_binding_and_range_pattern() {
+        let val = Some(7);
+        bassert!(Some(_n @ 1..=5) = val);
     }
 
     #[test]
     #[should_panic(
-        expected = "assertion failed: `None = val`\nval: `Some(100)`: That was unexpected! xyzzy plugh"
+        expected = "assertion failed: `Some(n @ 1..=5) if *n % 2 == 0 = val`\nguard failed: `*n % 2 == 0`\nval: `Some(3)`,\nn: `3`"
     )]
-    fn match_failure_with_custom_message_prints_correct_message() {
-        let val: Option<i64> = Some(100);
-        bassert!(None = val, "That was unexpected! {} {}", "xyzzy", "plugh");
+    fn match_guard_failure_prints_at_binding() {
+        let val = Some(3);
+        bassert!(Some(n @ 1..=5) if *n % 2 == 0 = val);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `Line { start: Corner { x: 0, .. }, end: Corner { y: 5..=9, .. } } = line`\nline: `Line { start: Corner { x: 0, y: 0 }, end: Corner { x: 3, y: 4 } }`"
+    )]
+    fn match_failure_prints_nested_struct_pattern() {
+        let line = Line {
+            start: Corner { x: 0, y: 0 },
+            end: Corner { x: 3, y: 4 },
+        };
+        bassert!(
+            Line {
+                start: Corner { x: 0, .. },
+                end: Corner { y: 5..=9, .. }
+            } = line
+        );
+    }
+
+    #[test]
+    fn try_match_guard_failure() {
+        let val = Some(3);
+        let failure = try_bassert!(Some(x) if *x > 5 = val).unwrap_err();
+        bassert!((failure.kind()) == (Some(crate::Kind::Match)));
+        bassert!((failure.lhs_expr()) == "Some(x) if *x > 5");
+        bassert!((failure.rhs()) == (Some("Some(3)")));
+    }
+
+    const ANSWER: u32 = 42;
+    const_bassert!(ANSWER == 42);
+    const_bassert!(ANSWER > 0, "the answer should be positive");
+
+    #[test]
+    fn const_success_compiles_inside_functions() {
+        const_bassert!(ANSWER < 100);
+        const_bassert!(
+            ANSWER.checked_add(1).is_some(),
+            "adding one should not overflow",
+        );
+    }
+
+    fn panic_message(assertion: impl FnOnce() + std::panic::UnwindSafe) -> String {
+        let payload = std::panic::catch_unwind(assertion).unwrap_err();
+        payload.downcast_ref::<String>().unwrap().clone()
+    }
+
+    /// The message of `failure`, without the location `Display` prints after it.
+    fn message_of(failure: crate::BassertFailure) -> String {
+        let message = failure.to_string();
+        let location = format!("\nat {}", failure.location());
+        message.strip_suffix(&location).unwrap().to_string()
+    }
+
+    #[test]
+    fn try_success_returns_ok() {
+        let (x, y, v) = (1_i32, 2, [1]);
+        bassert!((try_bassert!(x < y)) == (Ok(())));
+        bassert!((try_bassert!(x != y, "some message {}", 42)) == (Ok(())));
+        bassert!((try_bassert!(0 <= x < y)) == (Ok(())));
+        bassert!((try_bassert!(x in 0..10)) == (Ok(())));
+        bassert!((try_bassert!(Some(_) = (Some(x)))) == (Ok(())));
+        bassert!((try_bassert!(1.0 ~= 1.0)) == (Ok(())));
+        bassert!((try_bassert!(x < y && y < 3)) == (Ok(())));
+        bassert!((try_bassert!(!v.is_empty())) == (Ok(())));
+        bassert!((try_bassert!(x.is_positive())) == (Ok(())));
+    }
+
+    #[test]
+    fn try_failure_holds_kind_exprs_and_values() {
+        let (foo, bar) = ("foo", "bar");
+        let failure = try_bassert!(foo == bar).unwrap_err();
+        bassert!((failure.kind()) == (Some(crate::Kind::Eq)));
+        bassert!((failure.lhs_expr()) == "foo");
+        bassert!((failure.rhs_expr()) == (Some("bar")));
+        bassert!((failure.lhs()) == (Some("\"foo\"")));
+        bassert!((failure.rhs()) == (Some("\"bar\"")));
+    }
+
+    #[test]
+    fn try_failure_accessors_describe_each_kind_of_assertion() {
+        let (x, v) = (3, vec![1, 2]);
+
+        let failure = try_bassert!(None = (v.first()), "v has {} items", v.len()).unwrap_err();
+        bassert!((failure.kind()) == (Some(crate::Kind::Match)));
+        bassert!((failure.lhs_expr()) == "None");
+        bassert!((failure.rhs_expr()) == (Some("(v.first())")));
+        bassert!((failure.lhs()) == None);
+        bassert!((failure.rhs()) == (Some("Some(1)")));
+        bassert!((failure.message()) == (Some("v has 2 items")));
+
+        let failure = try_bassert!(0 <= x < 2).unwrap_err();
+        bassert!((failure.kind()) == (Some(crate::Kind::Lt)));
+        bassert!((failure.lhs_expr()) == "x");
+        bassert!((failure.lhs()) == (Some("3")));
+        bassert!((failure.rhs()) == (Some("2")));
+
+        let failure = try_bassert!(!v.is_empty() && (v.len()) > 2).unwrap_err();
+        bassert!((failure.kind()) == (Some(crate::Kind::Gt)));
+        bassert!(
+            (failure.message())
+                == (Some("right conjunct of `!v.is_empty() && (v.len()) > 2` failed"))
+        );
+
+        let failure = try_bassert!(v.is_empty()).unwrap_err();
+        bassert!((failure.kind()) == None);
+        bassert!((failure.lhs_expr()) == "v.is_empty()");
+        bassert!((failure.rhs_expr()) == None);
+        bassert!((failure.lhs()) == None);
+        bassert!((failure.message()) == None);
+    }
+
+    #[test]
+    fn try_failure_is_returned_by_question_mark() {
+        fn check(x: i32) -> Result<i32, crate::BassertFailure> {
+            try_bassert!(x > 0)?;
+            Ok(x)
+        }
+
+        bassert!((check(1)) == (Ok(1)));
+        bassert!((check(-1).map_err(|failure| failure.kind())) == (Err(Some(crate::Kind::Gt))));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn prop_failure_is_converted_into_the_error_type() {
+        // Like the `TestCaseError` of `proptest`, which can be converted from any error.
+        #[derive(Debug)]
+        struct TestCaseError(String);
+        impl<E: std::error::Error> From<E> for TestCaseError {
+            fn from(error: E) -> Self {
+                TestCaseError(error.to_string())
+            }
+        }
+        fn check(x: i32) -> Result<(), TestCaseError> {
+            prop_bassert!(x > 0 && x < 10);
+            Ok(())
+        }
+
+        bassert!(Ok(()) = (check(1)));
+        let message = check(10).unwrap_err().0;
+        bassert!((message.lines().next()) == (Some("assertion failed: `x < 10`")));
+    }
+
+    #[test]
+    fn failures_record_the_location_of_the_assertion() {
+        let (x, y, v) = (3, 2, [1, 2]);
+        let line = line!() + 2;
+        let failures = [
+            try_bassert!(x < y).unwrap_err(),
+            try_bassert!(0 <= x < y).unwrap_err(),
+            try_bassert!(None = (v.first())).unwrap_err(),
+            try_bassert!(1.0 ~= 1.5).unwrap_err(),
+            try_bassert!(x > 0 && v.is_empty()).unwrap_err(),
+            try_bassert!(x in 0..3).unwrap_err(),
+        ];
+        for (idx, failure) in failures.iter().enumerate() {
+            bassert!((failure.location().file()) == (file!()));
+            bassert!((failure.location().line()) == (line + idx as u32));
+        }
+        let failure = try_bassert!(x == y).unwrap_err();
+        let location = format!("\nat {}:{}:", file!(), line!() - 1);
+        bassert!((failure.to_string().contains(&location)));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn expressions_are_printed_with_normalized_whitespace() {
+        let (x, y, v) = (3, 2, [1, 2]);
+        bassert!(
+            (message_of(try_bassert!(x+ 1==y).unwrap_err()))
+                == "assertion failed: `x + 1 == y`\nx + 1: `4`,\ny: `2`\ndifference: `2`"
+        );
+        bassert!(
+            (message_of(try_bassert!(x >5 && v.len()==2).unwrap_err()))
+                == "assertion failed: `x > 5`\nx: `3`,\n5: `5`: left conjunct of `x > 5 && v.len() == 2` failed\ndifference: `-2`"
+        );
+        let failure = try_bassert!(v.iter().map(|n| {
+            n * 2
+        }).sum::<i32>() == -1).unwrap_err();
+        bassert!((failure.lhs_expr()) == "v.iter().map(|n| { n * 2 }).sum::<i32>()");
+        bassert!((failure.rhs_expr()) == (Some("-1")));
+    }
+
+    #[test]
+    fn operands_without_debug_print_a_placeholder() {
+        #[derive(PartialEq, PartialOrd)]
+        struct Opaque(u32);
+
+        // No `Debug` bound is needed, not even in generic code.
+        fn check_less<T: PartialOrd>(lhs: T, rhs: T) -> Result<(), crate::BassertFailure> {
+            try_bassert!(lhs < rhs)
+        }
+
+        let (x, y) = (Opaque(2), Opaque(1));
+        bassert!(
+            (panic_message(|| bassert!(x == y)))
+                == "assertion failed: `x == y`\nx: `<does not implement Debug>`,\ny: `<does not implement Debug>`"
+        );
+        bassert!(
+            (message_of(check_less(3, 2).unwrap_err()))
+                == "assertion failed: `lhs < rhs`\nlhs: `<does not implement Debug>`,\nrhs: `<does not implement Debug>`"
+        );
+        bassert!(
+            (message_of(try_bassert!(y < x < (Opaque(1))).unwrap_err()))
+                .starts_with("assertion failed: `y < x < (Opaque(1))`\nbroken link: `x < (Opaque(1))`\ny: `<does not implement Debug>`,")
+        );
+        bassert!((check_less(Opaque(1), Opaque(2)).is_ok()));
+    }
+
+    #[test]
+    fn display_operands_are_printed_using_display() {
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Version(u32, u32);
+        impl core::fmt::Display for Version {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "v{}.{}", self.0, self.1)
+            }
+        }
+
+        let (installed, required) = (Version(1, 2), Version(1, 3));
+        bassert!(#display installed != required);
+        bassert!(
+            (panic_message(|| bassert!(#display installed >= required, "oops")))
+                == "assertion failed: `installed >= required`\ninstalled: `v1.2`,\nrequired: `v1.3`: oops"
+        );
+        let failure = try_bassert!(#display (installed.to_string()) == "v1.3").unwrap_err();
+        bassert!((failure.lhs()) == (Some("v1.2")));
+        bassert!((failure.rhs()) == (Some("v1.3")));
+        let three = 3;
+        bassert!((bassert_val!(#display three > 2)) == 3);
+    }
+
+    #[test]
+    fn try_failure_describes_the_same_message_as_the_panic() {
+        let (x, y, v) = (3, 2, vec![1, 2]);
+        bassert!(
+            (message_of(try_bassert!(x < y, "because {}", 42).unwrap_err()))
+                == (panic_message(|| bassert!(x < y, "because {}", 42)))
+        );
+        bassert!(
+            (message_of(try_bassert!(0 <= x < y).unwrap_err()))
+                == (panic_message(|| bassert!(0 <= x < y)))
+        );
+        bassert!(
+            (message_of(try_bassert!(x in 0..3).unwrap_err()))
+                == (panic_message(|| bassert!(x in 0..3)))
+        );
+        bassert!(
+            (message_of(try_bassert!(None = (v.first())).unwrap_err()))
+                == (panic_message(|| bassert!(None = (v.first()))))
+        );
+        bassert!(
+            (message_of(try_bassert!(1.0 ~= 1.5).unwrap_err()))
+                == (panic_message(|| bassert!(1.0 ~= 1.5)))
+        );
+        bassert!(
+            (message_of(try_bassert!(!v.is_empty() && v.len() > 2).unwrap_err()))
+                == (panic_message(|| bassert!(!v.is_empty() && v.len() > 2)))
+        );
+        bassert!(
+            (message_of(try_bassert!(v.is_empty()).unwrap_err()))
+                == (panic_message(|| bassert!(v.is_empty())))
+        );
+    }
+
+    #[test]
+    fn try_conjunction_short_circuits() {
+        let mut calls = 0;
+        let mut call = || {
+            calls += 1;
+            true
+        };
+        let one = 1;
+        let result = try_bassert!(one > 2 && call());
+        bassert!((result.map_err(|failure| failure.kind())) == (Err(Some(crate::Kind::Gt))));
+        bassert!(calls == 0);
+    }
+
+    #[test]
+    fn write_failure_writes_panic_message() {
+        // Without differences, which `write_failure` does not explain.
+        let (x, y) = ('a', 'b');
+        let mut message = String::new();
+        crate::internal::write_failure(&mut message, crate::Kind::Eq, "x", "y", &x, &y, None)
+            .unwrap();
+        bassert!((message) == (panic_message(|| bassert!(x == y))));
+    }
+
+    #[test]
+    fn expected_message_starts_the_panic_message() {
+        let (x, y) = (3, 2);
+        let expected = crate::expected_message(crate::Kind::Lt, "x", "y", &x, &y);
+        bassert!(expected == "assertion failed: `x < y`\nx: `3`,\ny: `2`");
+        bassert!((panic_message(|| bassert!(x < y))).starts_with(&expected));
+
+        let name = "bassert";
+        let expected =
+            crate::expected_message(crate::Kind::Ne, "name", "\"bassert\"", &name, &"bassert");
+        bassert!((panic_message(|| bassert!(name != "bassert"))) == expected);
+    }
+
+    #[test]
+    fn write_failure_includes_message() {
+        let mut message = String::new();
+        let args = Some(format_args!("expected {}", 1));
+        crate::internal::write_failure(&mut message, crate::Kind::Ne, "1", "one", &1, &1, args)
+            .unwrap();
+        bassert!((message) == ("assertion failed: `1 != one`\n1: `1`,\none: `1`: expected 1"));
     }
 }
@@ -0,0 +1,49 @@
+//! The runtime switch of [`optional_bassert!`](crate::optional_bassert!).
+use core::sync::atomic::{AtomicU8, Ordering};
+
+const UNINITIALIZED: u8 = 0;
+const DISABLED: u8 = 1;
+const ENABLED: u8 = 2;
+
+static STATE: AtomicU8 = AtomicU8::new(UNINITIALIZED);
+
+/// Enables or disables all [`optional_bassert!`](crate::optional_bassert!)s in the process,
+/// overriding the `BASSERT_ENABLE` environment variable.
+///
+/// Other assertions are not affected.
+pub fn set_enabled(enabled: bool) {
+    STATE.store(if enabled { ENABLED } else { DISABLED }, Ordering::Relaxed);
+}
+
+/// Whether [`optional_bassert!`](crate::optional_bassert!)s are checked.
+///
+/// Unless [`set_enabled`] was called before, the first call reads the `BASSERT_ENABLE` environment variable.
+pub(crate) fn is_enabled() -> bool {
+    match STATE.load(Ordering::Relaxed) {
+        UNINITIALIZED => {
+            let state = if env_enabled() { ENABLED } else { DISABLED };
+            // Unless `set_enabled` was called in the meantime.
+            match STATE.compare_exchange(UNINITIALIZED, state, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => state == ENABLED,
+                Err(current) => current == ENABLED,
+            }
+        }
+        state => state == ENABLED,
+    }
+}
+
+#[cfg(feature = "std")]
+fn env_enabled() -> bool {
+    std::env::var("BASSERT_ENABLE").is_ok_and(|value| {
+        matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "1" | "true" | "yes" | "on"
+        )
+    })
+}
+
+#[cfg(not(feature = "std"))]
+fn env_enabled() -> bool {
+    false
+}
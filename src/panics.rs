@@ -0,0 +1,42 @@
+//! Catching the panics of the code passed to [`bassert_panics!`](crate::bassert_panics!)
+//! and [`bassert_no_panic!`](crate::bassert_no_panic!).
+use alloc::string::{String, ToString};
+use core::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+std::thread_local! {
+    /// Whether panics on this thread are currently being caught, so they should not be printed.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Runs `code`, returning the message it panicked with (if it did).
+///
+/// Instead of swapping the panic hook for every call (which would also silence panics on other threads,
+/// like those of tests running in parallel), a hook is installed once which only stays silent
+/// for the threads currently catching a panic, and calls the previously installed hook otherwise.
+pub(crate) fn catch(code: impl FnOnce()) -> Result<(), String> {
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(alloc::boxed::Box::new(move |info| {
+            if !CATCHING.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+
+    let was_catching = CATCHING.with(|catching| catching.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(code));
+    CATCHING.with(|catching| catching.set(was_catching));
+    result.map_err(|payload| {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Box<dyn Any>".to_string()
+        }
+    })
+}
@@ -0,0 +1,260 @@
+//! Styling of the different parts of a failure message.
+//!
+//! Without the `color` feature (or when color is disabled at runtime),
+//! the wrappers in this module format exactly like the values they wrap.
+//! Operand values are formatted by [`format_value`],
+//! which pretty-prints them if [`BassertConfig::pretty`] is set,
+//! and truncates them when longer than [`BassertConfig::max_len`].
+//! Colors and type names can be overridden using [`BassertConfig::color`] and [`BassertConfig::show_types`].
+use crate::BassertConfig;
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Style {
+    Header,
+    Expr,
+    Value,
+}
+
+impl Style {
+    fn ansi_code(self) -> &'static str {
+        match self {
+            Style::Header => "1;31",
+            Style::Expr => "1",
+            Style::Value => "33",
+        }
+    }
+}
+
+pub(crate) struct Styled<T> {
+    inner: T,
+    style: Style,
+    colored: bool,
+}
+
+/// Wraps the parts of a failure message in their [`Style`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Painter {
+    colored: bool,
+}
+
+impl Painter {
+    /// Colors the parts when colored output to stderr is enabled.
+    pub(crate) fn stderr() -> Self {
+        Painter {
+            colored: BassertConfig::current().effective_color(enabled),
+        }
+    }
+
+    /// Never colors the parts.
+    pub(crate) fn plain() -> Self {
+        Painter { colored: false }
+    }
+
+    fn styled<T>(self, inner: T, style: Style) -> Styled<T> {
+        Styled {
+            inner,
+            style,
+            colored: self.colored,
+        }
+    }
+
+    /// The `assertion failed` text every failure message starts with.
+    pub(crate) fn header(self) -> Styled<&'static str> {
+        self.styled("assertion failed", Style::Header)
+    }
+
+    /// The (stringified) expressions and operators of an assertion.
+    pub(crate) fn expr<T>(self, expr: T) -> Styled<T> {
+        self.styled(expr, Style::Expr)
+    }
+
+    /// The (formatted) runtime values of the operands of an assertion.
+    pub(crate) fn value<T>(self, value: T) -> Styled<T> {
+        self.styled(value, Style::Value)
+    }
+}
+
+/// Formats the runtime value of an operand, following the current [`BassertConfig`].
+///
+/// Takes a trait object, so this is compiled only once rather than for every type of operand.
+pub(crate) fn format_value(value: &dyn fmt::Debug) -> String {
+    let config = BassertConfig::current();
+    let formatted = if config.pretty {
+        format!("{:#?}", value)
+    } else {
+        format!("{:?}", value)
+    };
+    let formatted = match config.escape_control {
+        true => escape_control(formatted, config.pretty),
+        false => formatted,
+    };
+    truncate(formatted, config.effective_max_len())
+}
+
+/// Escapes the control characters in `value`, except for line breaks when `keep_newlines` is set.
+fn escape_control(value: String, keep_newlines: bool) -> String {
+    let is_escaped = |c: char| c.is_control() && !(keep_newlines && c == '\n');
+    if !value.contains(is_escaped) {
+        return value;
+    }
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            c if is_escaped(c) => escaped.extend(c.escape_debug()),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The ` (type)` suffix printed after the value of an operand of type `T`.
+///
+/// Only printed with the `type-names` feature; formats as nothing otherwise.
+pub(crate) fn type_name<T: ?Sized>() -> TypeName {
+    TypeName(core::any::type_name::<T>())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TypeName(&'static str);
+
+impl fmt::Display for TypeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if BassertConfig::current().effective_show_types() {
+            write!(f, " ({})", self.0)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Styled<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.colored {
+            return self.inner.fmt(f);
+        }
+
+        write!(f, "\x1b[{}m{}\x1b[0m", self.style.ansi_code(), self.inner)
+    }
+}
+
+fn truncate(formatted: String, max_len: usize) -> String {
+    match formatted.char_indices().nth(max_len) {
+        None => formatted,
+        Some((end, _)) => {
+            let more = formatted[end..].chars().count();
+            format!("{}(… {} more chars)", &formatted[..end], more)
+        }
+    }
+}
+
+#[cfg(all(feature = "color", not(test)))]
+fn enabled() -> bool {
+    use std::io::IsTerminal;
+    use std::sync::OnceLock;
+
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        detect(
+            std::env::var_os("NO_COLOR"),
+            std::env::var_os("CLICOLOR_FORCE"),
+            std::env::var_os("CLICOLOR"),
+            std::io::stderr().is_terminal(),
+        )
+    })
+}
+
+// The crate's own unit tests match on the exact uncolored messages,
+// regardless of the terminal they happen to run in.
+#[cfg(any(not(feature = "color"), test))]
+fn enabled() -> bool {
+    false
+}
+
+/// Follows the informal `NO_COLOR` and `CLICOLOR`/`CLICOLOR_FORCE` standards,
+/// only coloring output written to a terminal otherwise.
+#[cfg(feature = "color")]
+fn detect(
+    no_color: Option<std::ffi::OsString>,
+    clicolor_force: Option<std::ffi::OsString>,
+    clicolor: Option<std::ffi::OsString>,
+    is_terminal: bool,
+) -> bool {
+    if no_color.is_some_and(|val| !val.is_empty()) {
+        return false;
+    }
+    if clicolor_force.is_some_and(|val| !val.is_empty() && val != "0") {
+        return true;
+    }
+    if clicolor.is_some_and(|val| val == "0") {
+        return false;
+    }
+    is_terminal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_styled_formats_like_the_wrapped_value() {
+        let paint = Painter::plain();
+        bassert!((format!("{}", paint.header())) == "assertion failed");
+        bassert!((format!("{}", paint.expr("x + 1"))) == "x + 1");
+        bassert!((format!("{}", paint.value("\"foo\""))) == "\"foo\"");
+    }
+
+    #[test]
+    fn colored_styled_wraps_the_value_in_escape_codes() {
+        let paint = Painter { colored: true };
+        bassert!((format!("{}", paint.header())) == "\x1b[1;31massertion failed\x1b[0m");
+        bassert!((format!("{}", paint.expr("x + 1"))) == "\x1b[1mx + 1\x1b[0m");
+        bassert!((format!("{}", paint.value("\"foo\""))) == "\x1b[33m\"foo\"\x1b[0m");
+    }
+
+    #[test]
+    fn format_value_uses_debug() {
+        bassert!((format_value(&"foo")) == "\"foo\"");
+        bassert!((format_value(&(1, 2))) == "(1, 2)");
+    }
+
+    #[test]
+    fn escape_control_escapes_all_but_kept_newlines() {
+        let value = "a\rb\x1b[0m\tc\nd é".to_string();
+        bassert!((escape_control(value.clone(), false)) == "a\\rb\\u{1b}[0m\\tc\\nd é");
+        bassert!((escape_control(value, true)) == "a\\rb\\u{1b}[0m\\tc\nd é");
+        bassert!((escape_control("plain".to_string(), false)) == "plain");
+    }
+
+    #[test]
+    fn truncate_leaves_short_values_alone() {
+        bassert!((truncate("[1, 2, 3]".to_string(), 9)) == "[1, 2, 3]");
+        bassert!((truncate(String::new(), 0)) == "");
+    }
+
+    #[test]
+    fn truncate_notes_how_many_chars_were_left_out() {
+        bassert!((truncate("[1, 2, 3]".to_string(), 4)) == "[1, (… 5 more chars)");
+        bassert!((truncate("ééééé".to_string(), 2)) == "éé(… 3 more chars)");
+    }
+
+    #[test]
+    #[cfg(feature = "color")]
+    fn detect_follows_env_vars_and_terminal() {
+        let set = |val: &str| Some(std::ffi::OsString::from(val));
+
+        bassert!(detect(None, None, None, true));
+        bassert!(!detect(None, None, None, false));
+        bassert!(!detect(set("1"), None, None, true));
+        bassert!(!detect(set("1"), set("1"), None, true));
+        bassert!(detect(set(""), None, None, true));
+        bassert!(detect(None, set("1"), None, false));
+        bassert!(!detect(None, set("0"), None, false));
+        bassert!(!detect(None, None, set("0"), true));
+        bassert!(detect(None, None, set("1"), true));
+        bassert!(!detect(None, None, set("1"), false));
+        bassert!(detect(None, set("1"), set("0"), false));
+    }
+}
@@ -0,0 +1,34 @@
+// Aborting cannot be caught, so the failing assertion runs in a child process running this same test.
+#![cfg(feature = "abort-on-failure")]
+use bassert::bassert;
+use std::process::Command;
+
+#[test]
+fn failure_prints_message_and_aborts() {
+    if std::env::var_os("BASSERT_ABORT_CHILD").is_some() {
+        let (x, y) = (1, 2);
+        bassert!(x == y, "x and y differ");
+        return;
+    }
+
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args([
+            "failure_prints_message_and_aborts",
+            "--exact",
+            "--nocapture",
+        ])
+        .env("BASSERT_ABORT_CHILD", "1")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    bassert!(!output.status.success());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        bassert!((output.status.signal()) == (Some(6)));
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    bassert!(stderr.contains("assertion failed: `x == y`\nx: `1`,\ny: `2`: x and y differ"));
+    bassert!(stderr.contains(&format!("\nat {}:", file!())));
+    bassert!(!stderr.contains("panicked"));
+}
@@ -0,0 +1,18 @@
+// Whether backtraces are enabled is only checked once per process,
+// so this test lives in its own test binary.
+// The thread name would be printed after the expression.
+#![cfg(all(feature = "backtrace", not(feature = "thread-info")))]
+use bassert::try_bassert;
+
+#[test]
+fn failure_message_ends_with_backtrace() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+    let (x, y) = (10, 20);
+    let failure = try_bassert!(x == y).unwrap_err();
+    let message = failure.to_string();
+    // With the `type-names` feature, the values are followed by their types.
+    let (header, backtrace) = message.split_once("\nbacktrace:\n").unwrap();
+    assert!(header.starts_with("assertion failed: `x == y`\nx: `10`"));
+    assert!(header.ends_with("difference: `-10`"));
+    assert!(backtrace.contains("failure_message_ends_with_backtrace"));
+}
@@ -0,0 +1,205 @@
+// The configuration is global to the process,
+// so these tests live in their own test binary to not influence any others.
+// The exact messages below assume the default message format.
+#![cfg(all(
+    feature = "std",
+    not(feature = "type-names"),
+    not(feature = "thread-info"),
+    not(feature = "source-snippet"),
+    not(feature = "backtrace")
+))]
+use bassert::{bassert, try_bassert, BassertConfig, MessageStyle};
+use std::panic;
+use std::sync::{Mutex, PoisonError};
+
+static CONFIG_LOCK: Mutex<()> = Mutex::new(());
+
+fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+    let payload = panic::catch_unwind(f).unwrap_err();
+    payload.downcast_ref::<String>().unwrap().clone()
+}
+
+#[test]
+fn pretty_formats_operands_with_alternate_debug() {
+    let _lock = CONFIG_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    BassertConfig::new().pretty(true).install();
+
+    let message = panic_message(|| {
+        let point = Some((1, 2));
+        bassert!(None = point);
+    });
+    bassert!(
+        message
+            == "assertion failed: `None = point`\npoint: `Some(\n    (\n        1,\n        2,\n    ),\n)`\nactual variant: `Some`"
+    );
+
+    BassertConfig::new().install();
+    bassert!((BassertConfig::current()) == (BassertConfig::new()));
+}
+
+#[test]
+fn max_len_truncates_long_operands() {
+    let _lock = CONFIG_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    BassertConfig::new().max_len(10).install();
+
+    let message = panic_message(|| {
+        let list: Vec<i32> = (0..100).collect();
+        bassert!(None = (list.first()));
+    });
+    bassert!(
+        message
+            == "assertion failed: `None = (list.first())`\n(list.first()): `Some(0)`\nactual variant: `Some`"
+    );
+
+    let message = panic_message(|| {
+        let list: Vec<i32> = (0..100).collect();
+        bassert!(list == (vec![1]));
+    });
+    // With the `diff` feature, a diff is printed after this.
+    bassert!(message.starts_with(
+        "assertion failed: `list == (vec![1])`\nlist: `[0, 1, 2, (… 380 more chars)`,\n(vec![1]): `[1]`"
+    ));
+
+    BassertConfig::new().install();
+}
+
+#[test]
+fn compact_style_prints_a_single_line() {
+    let _lock = CONFIG_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    BassertConfig::new().style(MessageStyle::Compact).install();
+
+    let message = panic_message(|| {
+        let (a, b) = (1, 2);
+        bassert!(a == b, "a and b differ");
+    });
+    bassert!(message == "assertion failed: `a == b` (a: `1`, b: `2`): a and b differ");
+
+    let message = panic_message(|| {
+        let x = 15;
+        bassert!(0 < x < 10);
+    });
+    bassert!(
+        message
+            == "assertion failed: `0 < x < 10` (broken link: `x < 10`, 0: `0`, x: `15`, 10: `10`)"
+    );
+
+    let message = panic_message(|| {
+        let flag = false;
+        bassert!(flag);
+    });
+    bassert!(message == "assertion failed: `flag`");
+
+    let flag = false;
+    let failure = try_bassert!(flag).unwrap_err();
+    let expected = format!("assertion failed: `flag` at {}", failure.location());
+    bassert!((failure.to_string()) == expected);
+
+    BassertConfig::new().install();
+}
+
+#[test]
+fn values_first_style_prints_values_before_expression() {
+    let _lock = CONFIG_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    BassertConfig::new()
+        .style(MessageStyle::ValuesFirst)
+        .install();
+
+    let message = panic_message(|| {
+        let (a, b) = (1, 2);
+        bassert!(a > b, "a is too small");
+    });
+    bassert!(
+        message == "a: `1`,\nb: `2`\nassertion failed: `a > b`: a is too small\ndifference: `-1`"
+    );
+
+    BassertConfig::new().install();
+}
+
+#[test]
+fn show_types_prints_operand_types() {
+    let _lock = CONFIG_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    BassertConfig::new().show_types(true).install();
+
+    let message = panic_message(|| {
+        let (x, y) = (10u8, 20u8);
+        bassert!(x == y);
+    });
+    bassert!(message.starts_with("assertion failed: `x == y`\nx: `10` (u8),\ny: `20` (u8)"));
+
+    BassertConfig::new().install();
+}
+
+#[test]
+#[allow(deprecated)] // Comparing literals keeps the expected messages short.
+fn color_forces_colored_messages() {
+    let _lock = CONFIG_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+
+    BassertConfig::new().color(false).install();
+    let message = panic_message(|| bassert!(1 == 2));
+    bassert!(message.starts_with("assertion failed: `1 == 2`"));
+
+    BassertConfig::new().color(true).install();
+    let message = panic_message(|| bassert!(1 == 2));
+    BassertConfig::new().install();
+    // Without the `color` feature, messages are never colored.
+    let colored = message.starts_with("\x1b[1;31massertion failed\x1b[0m: `\x1b[1m1 == 2\x1b[0m`");
+    bassert!(colored == (cfg!(feature = "color")));
+}
+
+#[test]
+fn escape_control_escapes_control_characters_in_operands() {
+    #[derive(PartialEq)]
+    struct Raw(&'static str);
+    impl std::fmt::Debug for Raw {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    let _lock = CONFIG_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let (x, y) = (Raw("ab\rc\n"), Raw("\x1b[2J"));
+    let message = panic_message(|| bassert!(x == y));
+    bassert!(message.starts_with("assertion failed: `x == y`\nx: `ab\rc\n`,\ny: `\x1b[2J`"));
+
+    BassertConfig::new().escape_control(true).install();
+    let message = panic_message(|| bassert!(x == y));
+    BassertConfig::new().install();
+    bassert!(message.starts_with("assertion failed: `x == y`\nx: `ab\\rc\\n`,\ny: `\\u{1b}[2J`"));
+}
+
+#[test]
+fn align_lines_up_the_values() {
+    let _lock = CONFIG_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    BassertConfig::new().align(true).install();
+
+    let (v, y) = (vec![1, 2, 3], 4);
+    let message = panic_message(|| bassert!((v.len()) == y));
+    bassert!(
+        message.starts_with("assertion failed: `(v.len()) == y`\n(v.len()): `3`,\ny:         `4`")
+    );
+    let message = panic_message(|| bassert!(0 < y < 3));
+    bassert!(message.contains("\n0: `0`,\ny: `4`,\n3: `3`"));
+
+    BassertConfig::new().install();
+}
+
+#[test]
+fn verbose_operators_describes_comparisons_in_words() {
+    let _lock = CONFIG_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    BassertConfig::new().verbose_operators(true).install();
+
+    let (x, y, v) = (1, 2, [1, 2]);
+    let message = panic_message(|| bassert!(x > y));
+    bassert!(message.starts_with("assertion failed: `x` is not greater than `y`\nx: `1`,\ny: `2`"));
+    let message = panic_message(|| bassert!(None = (v.first())));
+    bassert!(message.starts_with("assertion failed: `(v.first())` does not match `None`"));
+    // Only single comparisons are described in words.
+    let message = panic_message(|| bassert!(0 < x < 1));
+    bassert!(message.starts_with("assertion failed: `0 < x < 1`\nbroken link: `x < 1`"));
+    let message = panic_message(|| bassert!(v.is_empty()));
+    bassert!(message.starts_with("assertion failed: `v.is_empty()`"));
+    let failure = try_bassert!(x == y).unwrap_err();
+    bassert!((failure.to_string()).starts_with("assertion failed: `x` is not equal to `y`"));
+
+    BassertConfig::new().install();
+}
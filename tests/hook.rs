@@ -0,0 +1,54 @@
+// The failure hook is global to the process,
+// so these tests live in their own test binary to not influence any others.
+#![cfg(all(feature = "std", feature = "assertions"))]
+use bassert::{bassert, set_failure_hook, take_failure_hook, try_bassert};
+use std::panic;
+use std::sync::{Arc, Mutex, PoisonError};
+
+static HOOK_LOCK: Mutex<()> = Mutex::new(());
+
+#[test]
+fn hook_is_called_before_panicking() {
+    let _lock = HOOK_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let hook_seen = Arc::clone(&seen);
+    set_failure_hook(Box::new(move |failure| {
+        hook_seen.lock().unwrap().push((
+            failure.lhs_expr().to_string(),
+            failure.lhs().map(String::from),
+        ));
+    }));
+
+    let x = 1;
+    bassert!(x == 1);
+    let result = panic::catch_unwind(|| bassert!(x == 2));
+    assert!(take_failure_hook().is_some());
+
+    assert!(result.is_err());
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [("x".to_string(), Some("1".to_string()))]
+    );
+}
+
+#[test]
+fn hook_is_not_called_for_returned_failures() {
+    let _lock = HOOK_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let seen = Arc::new(Mutex::new(0));
+    let hook_seen = Arc::clone(&seen);
+    set_failure_hook(Box::new(move |_| *hook_seen.lock().unwrap() += 1));
+
+    let x = 1;
+    assert!(try_bassert!(x == 2).is_err());
+    take_failure_hook();
+
+    assert_eq!(*seen.lock().unwrap(), 0);
+}
+
+#[test]
+fn without_hook_failures_only_panic() {
+    let _lock = HOOK_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    assert!(take_failure_hook().is_none());
+    let x = 1;
+    assert!(panic::catch_unwind(|| bassert!(x == 2)).is_err());
+}
@@ -0,0 +1,11 @@
+// Expanding the macros must not trigger lints in the calling crate.
+#![deny(unused_parens, unused_braces)]
+use bassert::bassert;
+
+#[test]
+fn negated_parenthesized_comparison_does_not_trigger_unused_parens() {
+    let a = 1;
+    let b = 2;
+    bassert!(!(a == b));
+    bassert!(!(a == b), "a and b are {}", "equal");
+}
@@ -0,0 +1,49 @@
+// Without the `assertions` feature, `bassert!` is only type-checked.
+#![cfg(not(feature = "assertions"))]
+use bassert::{bassert, bassert_iter_eq, bassert_matches, bassert_val, debug_bassert, try_bassert};
+
+#[test]
+fn failing_assertions_do_not_panic() {
+    let x = 1;
+    bassert!(x == 2);
+    bassert!(x > 2, "some {}", "message");
+    debug_bassert!(x in 2..3);
+}
+
+#[test]
+fn assertions_are_not_evaluated() {
+    let mut calls = 0;
+    let mut next = || {
+        calls += 1;
+        calls
+    };
+    bassert!(next() == 1);
+    bassert!(calls == 0);
+}
+
+#[test]
+fn try_bassert_is_not_affected() {
+    let x = 1;
+    assert!(try_bassert!(x == 2).is_err());
+}
+
+#[test]
+#[should_panic(expected = "first difference at index 1: left = `2`, right = `3`")]
+fn bassert_iter_eq_is_not_affected() {
+    let a = [1, 2];
+    let b = [1, 3];
+    bassert_iter_eq!(a.iter(), b.iter());
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `Some(_) = value`")]
+fn bassert_matches_is_not_affected() {
+    let value: Option<i32> = None;
+    bassert_matches!(value, Some(_));
+}
+
+#[test]
+fn bassert_val_returns_lhs_without_checking() {
+    let x = bassert_val!(1 + 1 == 3);
+    assert_eq!(x, 2);
+}
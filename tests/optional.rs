@@ -0,0 +1,35 @@
+// Whether optional assertions are enabled is global to the process,
+// so these tests live in their own test binary to not influence any others.
+// The thread name would be printed after the expression.
+#![cfg(all(feature = "std", feature = "assertions", not(feature = "thread-info")))]
+use bassert::optional_bassert;
+use std::panic;
+use std::sync::{Mutex, PoisonError};
+
+static ENABLED_LOCK: Mutex<()> = Mutex::new(());
+
+#[test]
+fn disabled_assertions_are_not_evaluated() {
+    let _lock = ENABLED_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    bassert::set_enabled(false);
+    let mut calls = 0;
+    let mut next = || {
+        calls += 1;
+        calls
+    };
+    optional_bassert!(next() == 2);
+    assert_eq!(calls, 0);
+}
+
+#[test]
+fn enabled_assertions_are_checked() {
+    let _lock = ENABLED_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    bassert::set_enabled(true);
+    let x = 1;
+    optional_bassert!(x == 1);
+    let result = panic::catch_unwind(|| optional_bassert!(x == 2));
+    bassert::set_enabled(false);
+    let payload = result.unwrap_err();
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("assertion failed: `x == 2`\nx: `1`"));
+}
@@ -0,0 +1,68 @@
+// Catching panics installs a panic hook, so these tests live in their own test binary.
+// With the `backtrace` feature, the messages may be followed by a backtrace.
+// With the `thread-info` feature, the thread name would be printed after the expression.
+#![cfg(all(feature = "std", not(feature = "thread-info")))]
+use bassert::{bassert_no_panic, bassert_panics};
+use std::panic;
+
+fn panic_message(assertion: impl FnOnce() + panic::UnwindSafe) -> String {
+    let payload = panic::catch_unwind(assertion).unwrap_err();
+    payload.downcast_ref::<String>().unwrap().clone()
+}
+
+fn fail(message: &str) {
+    panic!("failed: {}", message)
+}
+
+#[test]
+fn panicking_code_passes() {
+    bassert_panics!(fail("boom"));
+    bassert_panics!(fail("boom"), "boom");
+    bassert_panics!(fail("boom"), String::from("failed: "));
+    bassert_panics!({
+        let v: Vec<i32> = Vec::new();
+        v[0]
+    });
+}
+
+#[test]
+fn code_not_panicking_passes() {
+    let mut calls = 0;
+    bassert_no_panic!(calls += 1);
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn code_not_panicking_fails() {
+    let message = panic_message(|| bassert_panics!(1 + 1));
+    assert!(
+        message.starts_with("assertion failed: `1 + 1`\nexpected a panic, but it did not panic")
+    );
+
+    let message = panic_message(|| bassert_panics!(1 + 1, "boom"));
+    assert!(message.starts_with(
+        "assertion failed: `1 + 1`\nexpected a panic containing `boom`, but it did not panic"
+    ));
+}
+
+#[test]
+fn panic_with_other_message_fails() {
+    let message = panic_message(|| bassert_panics!(fail("bang"), "boom"));
+    assert!(message.starts_with(
+        "assertion failed: `fail(\"bang\")`\nexpected a panic containing `boom`, but it panicked with: `failed: bang`"
+    ));
+}
+
+#[test]
+fn unexpected_panic_fails() {
+    let message = panic_message(|| bassert_no_panic!(fail("bang")));
+    assert!(message.starts_with(
+        "assertion failed: `fail(\"bang\")`\nexpected no panic, but it panicked with: `failed: bang`"
+    ));
+}
+
+#[test]
+fn panics_with_non_string_payload_are_caught() {
+    let message = panic_message(|| bassert_no_panic!(panic::panic_any(42)));
+    assert!(message.contains("but it panicked with: `Box<dyn Any>`"));
+}
@@ -0,0 +1,123 @@
+// The type names would be printed after every value.
+#![cfg(all(feature = "power", not(feature = "type-names")))]
+use bassert::power_bassert;
+
+struct User {
+    age: u32,
+}
+
+#[test]
+fn success_passes() {
+    let user = User { age: 30 };
+    let bonus = 2;
+    power_bassert!(user.age + bonus > 18);
+    power_bassert!(user.age.pow(2) == 900 && bonus.is_power_of_two());
+}
+
+#[test]
+#[should_panic(
+    expected = "assertion failed: `user.age + bonus > limit`\nuser.age: `3`,\nbonus: `2`,\nuser.age + bonus: `5`,\nlimit: `10`"
+)]
+fn failure_prints_sub_expressions() {
+    let user = User { age: 3 };
+    let bonus = 2;
+    let limit = 10;
+    power_bassert!(user.age + bonus > limit);
+}
+
+#[test]
+#[should_panic(
+    expected = "assertion failed: `v.len() == max(a, b)`\nv.len(): `3`,\nmax(a, b): `4`"
+)]
+fn failure_prints_calls() {
+    let v = [1, 2, 3];
+    let (a, b) = (4, 1);
+    power_bassert!(v.len() == max(a, b));
+}
+
+fn max(a: usize, b: usize) -> usize {
+    a.max(b)
+}
+
+#[test]
+#[should_panic(
+    expected = "assertion failed: `names[idx].trim().len() > 3`\nidx: `1`,\nnames[idx].trim(): `\"ab\"`,\nnames[idx].trim().len(): `2`"
+)]
+fn failure_prints_nested_method_calls_and_indices() {
+    let names = ["alice ", " ab "];
+    let idx = 1;
+    power_bassert!(names[idx].trim().len() > 3);
+}
+
+#[test]
+#[should_panic(
+    expected = "assertion failed: `x > 0 && !flag`\nx: `1`,\nx > 0: `true`,\nflag: `true`"
+)]
+fn failure_prints_boolean_sub_expressions() {
+    let x = 1;
+    let flag = true;
+    power_bassert!(x > 0 && !flag);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `x == 2`\nx: `1`: x should be 2, not 1")]
+fn failure_prints_custom_message() {
+    let x = 1;
+    power_bassert!(x == 2, "x should be 2, not {}", x);
+}
+
+struct NoDebug(u8);
+
+impl NoDebug {
+    fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `NoDebug(1).value() == 2`\nNoDebug(1).value(): `1`")]
+fn sub_expressions_without_debug_are_skipped() {
+    power_bassert!(NoDebug(1).value() == 2);
+}
+
+#[test]
+fn sub_expressions_are_evaluated_once() {
+    let mut calls = 0;
+    let mut next = || {
+        calls += 1;
+        calls
+    };
+    power_bassert!(next() + next() == 3);
+    power_bassert!(calls == 2);
+}
+
+#[test]
+fn moved_values_are_not_recorded_twice() {
+    let greeting = String::from("Hello");
+    let mut list = vec![1];
+    power_bassert!(greeting + ", world" == "Hello, world");
+    power_bassert!(push_and_len(&mut list, 2) == 2);
+    power_bassert!(list.last().copied() == Some(2));
+}
+
+fn push_and_len(list: &mut Vec<i32>, value: i32) -> usize {
+    list.push(value);
+    list.len()
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `x ~= 1.5`\nx: `1.0`,\n1.5: `1.5`")]
+fn unsupported_assertions_use_bassert() {
+    let x = 1.0;
+    power_bassert!(x ~= 1.5);
+}
+
+#[test]
+fn unsupported_assertions_success_passes() {
+    let x = 5;
+    let val = Some(3);
+    power_bassert!(x in 0..10);
+    power_bassert!(Some(_) = val);
+    power_bassert!(0 <= x < 10);
+    power_bassert!([1, 2].iter().any(|&n| n == 2));
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "source-snippet")]
+use bassert::try_bassert;
+use std::sync::{Mutex, PoisonError};
+
+// The source file is read relative to the current directory, which one of the tests changes.
+static DIR_LOCK: Mutex<()> = Mutex::new(());
+
+#[test]
+fn failure_message_contains_source_line() {
+    let _lock = DIR_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let (x, y) = (10, 20);
+    let failure = try_bassert!(x == y).unwrap_err();
+    let line = line!() - 1;
+    let message = failure.to_string();
+    let snippet = format!(
+        "\nsource:\n{line} |     let failure = try_bassert!(x == y).unwrap_err();\n{:w$} |                   ^",
+        "",
+        w = line.to_string().len()
+    );
+    assert!(message.contains(&snippet), "{}", message);
+}
+
+#[test]
+fn failures_without_source_file_leave_out_the_snippet() {
+    let _lock = DIR_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    // The file of the location is not found when running from another directory.
+    let dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(std::env::temp_dir()).unwrap();
+    let one = 1;
+    let failure = try_bassert!(one > 2);
+    std::env::set_current_dir(dir).unwrap();
+    assert!(!failure.unwrap_err().to_string().contains("\nsource:\n"));
+}
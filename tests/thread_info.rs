@@ -0,0 +1,32 @@
+#![cfg(feature = "thread-info")]
+use bassert::try_bassert;
+use std::thread;
+
+#[test]
+fn failure_message_contains_thread_name() {
+    let message = thread::Builder::new()
+        .name("worker-3".to_string())
+        .spawn(|| {
+            let (x, y) = (1, 2);
+            try_bassert!(x == y).unwrap_err().to_string()
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    assert!(message.starts_with("assertion failed: `x == y` (thread: worker-3)\n"));
+}
+
+#[test]
+fn failure_message_contains_thread_id_of_unnamed_threads() {
+    let (message, id) = thread::Builder::new()
+        .spawn(|| {
+            let (x, y) = (1, 2);
+            let message = try_bassert!(x == y).unwrap_err().to_string();
+            (message, thread::current().id())
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    let expected = format!("assertion failed: `x == y` (thread: {:?})\n", id);
+    assert!(message.starts_with(&expected));
+}
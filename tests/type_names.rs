@@ -0,0 +1,30 @@
+// The thread name would be printed after the expression.
+#![cfg(all(feature = "type-names", not(feature = "thread-info")))]
+use bassert::bassert;
+
+#[test]
+#[should_panic(expected = "assertion failed: `x == y`\nx: `10` (i32),\ny: `20` (i32)")]
+fn comparison_failure_prints_operand_types() {
+    let x = 10;
+    let y = 20;
+    bassert!(x == y);
+}
+
+#[test]
+#[should_panic(
+    expected = "assertion failed: `None = val`\nval: `Some(\"foo\")` (core::option::Option<&str>)"
+)]
+fn match_failure_prints_operand_type() {
+    let val = Some("foo");
+    bassert!(None = val);
+}
+
+#[test]
+#[should_panic(
+    expected = "assertion failed: `0 <= idx < len`\nbroken link: `idx < len`\n0: `0` (usize),\nidx: `5` (usize),\nlen: `3` (usize)"
+)]
+fn chain_failure_prints_operand_types() {
+    let idx: usize = 5;
+    let len: usize = 3;
+    bassert!(0 <= idx < len);
+}